@@ -0,0 +1,383 @@
+//! Parses generated fixtures with both `parser` and `serde_json`.
+//!
+//! Run with `cargo bench`, or `cargo bench -- <filter>` for matching fixtures
+//! only, such as `cargo bench -- strings`. Criterion keeps the previous run's
+//! results under `target/criterion` and reports the change against them.
+
+use std::time::{Duration, Instant};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use parser::json;
+
+/// An object nested `depth` levels deep, each level holding a few scalars.
+///
+/// Both parsers refuse nesting deeper than 128 levels by default.
+fn nested_object(depth: usize) -> String {
+    let mut out = String::new();
+    for i in 0..depth {
+        out.push_str(&format!(
+            r#"{{"id": {}, "name": "level", "ok": true, "child": "#,
+            i
+        ));
+    }
+    out.push_str("null");
+    out.push_str(&"}".repeat(depth));
+    out
+}
+
+/// A flat array of `len` integers and floats.
+fn number_array(len: usize) -> String {
+    let numbers: Vec<_> = (0..len)
+        .map(|i| match i % 3 {
+            0 => format!("{}", i * 7919),
+            1 => format!("-{}.{}", i, i % 100),
+            _ => format!("{}e-3", i),
+        })
+        .collect();
+    format!("[{}]", numbers.join(", "))
+}
+
+/// A flat array of `len` strings, some of them with escapes.
+fn string_array(len: usize) -> String {
+    let strings: Vec<_> = (0..len)
+        .map(|i| match i % 4 {
+            0 => format!(r#""key {} with \"quotes\" and \n newline""#, i),
+            1 => format!(r#""café {}""#, i),
+            _ => format!(r#""plain string number {}""#, i),
+        })
+        .collect();
+    format!("[{}]", strings.join(", "))
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let fixtures = [
+        ("nested", nested_object(100)),
+        ("numbers", number_array(10_000)),
+        ("strings", string_array(10_000)),
+    ];
+    let mut group = c.benchmark_group("parse");
+    for (name, input) in &fixtures {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("parser", name), input, |b, input| {
+            b.iter(|| json::parse_complete(black_box(input)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("serde_json", name), input, |b, input| {
+            b.iter(|| serde_json::from_str::<serde_json::Value>(black_box(input)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// Takes `value` apart without recursing, since dropping a deeply nested value
+/// whole would recurse as deep as it is nested.
+fn dismantle(value: json::JsonValue) {
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            json::JsonValue::Array(items) => stack.extend(items),
+            json::JsonValue::Object(map) => stack.extend(map.into_values()),
+            _ => {}
+        }
+    }
+}
+
+/// Parses arrays and objects nested 50,000 levels deep, with `max_depth`
+/// raised to allow them, timing only the parsing. Run with
+/// `cargo bench -- deep_nesting`.
+fn bench_deep_nesting(c: &mut Criterion) {
+    let depth = 50_000;
+    let options = json::ParseOptions {
+        max_depth: depth,
+        ..Default::default()
+    };
+    let fixtures = [
+        ("arrays", "[".repeat(depth) + &"]".repeat(depth)),
+        (
+            "objects",
+            r#"{"a":"#.repeat(depth - 1) + "{}" + &"}".repeat(depth - 1),
+        ),
+    ];
+    let mut group = c.benchmark_group("deep_nesting");
+    group.sample_size(20);
+    for (name, input) in &fixtures {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), input, |b, input| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let start = Instant::now();
+                    let value = json::parse_with_options(black_box(input), &options).unwrap();
+                    total += start.elapsed();
+                    dismantle(value);
+                }
+                total
+            })
+        });
+    }
+    group.finish();
+}
+
+/// A single string of `len` bytes, with an escape every 64 bytes if `escaped`.
+fn long_string(len: usize, escaped: bool) -> String {
+    let chunk = if escaped {
+        format!("{}\\n", "x".repeat(62))
+    } else {
+        "x".repeat(64)
+    };
+    format!(r#""{}""#, chunk.repeat(len / 64))
+}
+
+/// The fastest of a few parses of a string of `mb` MB.
+fn time_long_string(mb: usize, escaped: bool) -> Duration {
+    let input = long_string(mb << 20, escaped);
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(json::parse_complete(black_box(&input)).unwrap());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+/// Parses strings of 1 MB and 5 MB. Parsing is linear in the length of a
+/// string, so both sizes should report the same throughput.
+///
+/// Before benchmarking, this checks that the 5 MB string takes less than
+/// twice as long per byte as the 1 MB one; a quadratic parser would take five
+/// times as long.
+fn bench_long_string(c: &mut Criterion) {
+    for escaped in [false, true] {
+        let ratio =
+            time_long_string(5, escaped).as_secs_f64() / time_long_string(1, escaped).as_secs_f64();
+        assert!(
+            ratio < 5.0 * 2.0,
+            "a 5 MB string took {:.1} times as long as a 1 MB one",
+            ratio
+        );
+    }
+    let mut group = c.benchmark_group("long_string");
+    group.sample_size(20);
+    for escaped in [false, true] {
+        let kind = if escaped { "escaped" } else { "plain" };
+        for mb in [1, 5] {
+            let input = long_string(mb << 20, escaped);
+            group.throughput(Throughput::Bytes(input.len() as u64));
+            let id = BenchmarkId::new(kind, format!("{}MB", mb));
+            group.bench_with_input(id, &input, |b, input| {
+                b.iter(|| json::parse_complete(black_box(input)).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Parses an array of 100k numbers, whose elements are collected on a scratch
+/// stack and moved into an array of exactly their number once it is closed;
+/// `tests/alloc.rs` counts the allocations this makes. Run with
+/// `cargo bench -- large_array`.
+fn bench_large_array(c: &mut Criterion) {
+    let input = number_array(100_000);
+    let mut group = c.benchmark_group("large_array");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("parser", |b| {
+        b.iter(|| json::parse_complete(black_box(&input)).unwrap())
+    });
+    group.bench_function("serde_json", |b| {
+        b.iter(|| serde_json::from_str::<serde_json::Value>(black_box(&input)).unwrap())
+    });
+    group.finish();
+}
+
+/// An object with `len` integer keys, like telemetry indexed by id.
+fn int_keyed_object(len: usize) -> String {
+    let members: Vec<_> = (0..len)
+        .map(|i| format!(r#""{}": {}"#, i * 3, i % 100))
+        .collect();
+    format!("{{{}}}", members.join(", "))
+}
+
+/// Parses an object with 10k integer keys, keyed by string and by integer.
+/// Run with `cargo bench -- int_keys`.
+fn bench_int_keys(c: &mut Criterion) {
+    let input = int_keyed_object(10_000);
+    let mut group = c.benchmark_group("int_keys");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("string", |b| {
+        b.iter(|| json::parse_complete(black_box(&input)).unwrap())
+    });
+    group.bench_function("integer", |b| {
+        b.iter(|| json::parse_int_keyed(black_box(&input)).unwrap())
+    });
+    group.finish();
+}
+
+/// An object with `len` short keys needing no unescaping.
+fn simple_keyed_object(len: usize) -> String {
+    let members: Vec<_> = (0..len).map(|i| format!(r#""key{}": {}"#, i, i)).collect();
+    format!("{{{}}}", members.join(", "))
+}
+
+/// Parses an object with 1,000 simple keys, borrowing them from the input and
+/// allocating each. Run with `cargo bench -- borrowed_keys`.
+fn bench_borrowed_keys(c: &mut Criterion) {
+    let input = simple_keyed_object(1000);
+    let mut group = c.benchmark_group("borrowed_keys");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("parse_borrowed", |b| {
+        b.iter(|| json::parse_borrowed(black_box(&input)).unwrap())
+    });
+    group.bench_function("parse_complete", |b| {
+        b.iter(|| json::parse_complete(black_box(&input)).unwrap())
+    });
+    group.finish();
+}
+
+/// Checks the structure of the `parse` fixtures, against parsing them.
+/// Run with `cargo bench -- structure`.
+fn bench_structure(c: &mut Criterion) {
+    let fixtures = [
+        ("numbers", number_array(10_000)),
+        ("strings", string_array(10_000)),
+    ];
+    let mut group = c.benchmark_group("structure");
+    for (name, input) in &fixtures {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("validate", name), input, |b, input| {
+            b.iter(|| json::validate_structure(black_box(input)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("parse", name), input, |b, input| {
+            b.iter(|| json::parse_document(black_box(input)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// An array of `len` rows of an id, a name and a score.
+fn row_array(len: usize) -> String {
+    let rows: Vec<_> = (0..len)
+        .map(|i| format!(r#"[{}, "user {}", {}.5]"#, i, i, i % 100))
+        .collect();
+    format!("[{}]", rows.join(", "))
+}
+
+/// Reads 10k rows as tuples, directly and by converting a parsed document.
+/// Run with `cargo bench -- rows`.
+fn bench_rows(c: &mut Criterion) {
+    let input = row_array(10_000);
+    let mut group = c.benchmark_group("rows");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("parse_rows", |b| {
+        b.iter(|| json::parse_rows::<(i64, String, f64)>(black_box(&input)).unwrap())
+    });
+    group.bench_function("parse_document", |b| {
+        b.iter(|| {
+            let value = json::parse_document(black_box(&input)).unwrap();
+            value
+                .elements()
+                .map(|row| {
+                    let id = row[0].as_i64().unwrap();
+                    let name = row[1].as_str().unwrap().to_string();
+                    (id, name, row[2].as_f64().unwrap())
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    group.finish();
+}
+
+/// An array of `len` objects with a dozen members each.
+fn object_array(len: usize) -> String {
+    let objects: Vec<_> = (0..len)
+        .map(|i| {
+            let members: Vec<_> = (0..12)
+                .map(|k| format!(r#""field_{}": {}"#, k, i + k))
+                .collect();
+            format!("{{{}}}", members.join(", "))
+        })
+        .collect();
+    format!("[{}]", objects.join(", "))
+}
+
+/// An array of `len` objects with two members, one of them a keyword.
+fn small_object_array(len: usize) -> String {
+    let objects: Vec<_> = (0..len)
+        .map(|i| format!(r#"{{"id": {}, "ok": {}}}"#, i, i % 2 == 0))
+        .collect();
+    format!("[{}]", objects.join(", "))
+}
+
+/// Parses 10k objects of a dozen members and 100k of two, hashing keys with
+/// whichever hasher backs `Map`. Compare `cargo bench -- objects` with and
+/// without `--features ahash`.
+fn bench_objects(c: &mut Criterion) {
+    let mut group = c.benchmark_group("objects");
+    for (name, input) in [
+        ("parse", object_array(10_000)),
+        ("small", small_object_array(100_000)),
+    ] {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| json::parse_document(black_box(&input)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// `count` small objects, each a document of its own.
+#[cfg(feature = "arena")]
+fn small_documents(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| {
+            format!(
+                r#"{{"id": {}, "name": "user {}", "tags": ["a", "b"], "active": true}}"#,
+                i, i
+            )
+        })
+        .collect()
+}
+
+/// Parses 100k small documents one after another, with and without an arena
+/// reset between them. Run with `cargo bench --features arena -- arena`.
+#[cfg(feature = "arena")]
+fn bench_arena(c: &mut Criterion) {
+    let documents = small_documents(100_000);
+    let mut group = c.benchmark_group("arena");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(documents.len() as u64));
+    group.bench_function("heap", |b| {
+        b.iter(|| {
+            for input in &documents {
+                black_box(json::parse_complete(black_box(input)).unwrap());
+            }
+        })
+    });
+    group.bench_function("arena", |b| {
+        let mut arena = json::Bump::new();
+        b.iter(|| {
+            for input in &documents {
+                black_box(json::parse_in(black_box(input), &arena).unwrap());
+                arena.reset();
+            }
+        })
+    });
+    group.finish();
+}
+
+#[cfg(not(feature = "arena"))]
+fn bench_arena(_c: &mut Criterion) {}
+
+criterion_group!(
+    benches,
+    bench_parse,
+    bench_deep_nesting,
+    bench_long_string,
+    bench_large_array,
+    bench_arena,
+    bench_int_keys,
+    bench_borrowed_keys,
+    bench_structure,
+    bench_rows,
+    bench_objects
+);
+criterion_main!(benches);
@@ -1,150 +1,3119 @@
-use std::collections::HashMap;
+#[cfg(feature = "arena")]
+mod arena;
+mod array_stream;
+mod borrowed;
+mod build;
+pub mod combinators;
+mod complete;
+mod convert;
+mod diagnostic;
+mod error;
+mod extract;
+mod flatten;
+mod int_keyed;
+mod intern;
+#[cfg(feature = "serde_json")]
+mod interop;
+mod macros;
+mod merge;
+mod number;
+mod options;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod patch;
+mod pointer;
+mod query;
+#[cfg(feature = "std")]
+mod read;
+mod recover;
+mod rows;
+mod schema;
+mod ser;
+#[cfg(feature = "serde")]
+mod serde_support;
+mod span;
+mod stats;
+mod stream;
+mod structure;
+mod subtree;
+mod token;
+#[cfg(feature = "toml")]
+mod toml_interop;
+mod transform;
+mod value;
+mod visit;
+#[cfg(feature = "serde_yaml")]
+mod yaml_interop;
+
+#[cfg(feature = "arena")]
+pub use arena::{parse_in, ArenaValue};
+pub use array_stream::ArrayStream;
+pub use borrowed::{parse_borrowed, JsonValueRef, MapRef};
+pub use build::{parse_with, JsonBuilder};
+#[cfg(feature = "arena")]
+pub use bumpalo::Bump;
+pub use complete::collect_keys_at;
+pub use convert::ConversionError;
+pub use diagnostic::{parse_with_comments, parse_with_diagnostics, Diagnostic, DiagnosticKind};
+pub use error::{Error, ErrorKind, ParseError};
+pub use extract::{ExtractError, Field, FromJson};
+pub use int_keyed::{parse_int_keyed, IntKeyedMap, IntKeyedValue, IntMap};
+pub use intern::{parse_interned, InternedMap, InternedValue};
+pub use merge::ArrayMerge;
+pub use number::Number;
+pub use options::{DuplicateKeyPolicy, NumberHook, ParseOptions, SurrogatePolicy, JSON_WHITESPACE};
+#[cfg(feature = "parallel")]
+pub use parallel::parse_array_parallel;
+pub use patch::{apply_patch, PatchError};
+#[cfg(feature = "std")]
+pub use read::{parse_reader, ReadError};
+pub use recover::{parse_recovering, resync};
+pub use rows::{parse_rows, FromJsonRow};
+pub use schema::{Schema, ValidationError};
+pub use ser::{escape_string, FloatFormat, SerializeOptions};
+pub use span::{parse_with_spans, Spans};
+pub use stats::{parse_with_stats, parse_with_stats_options, ParseStats};
+pub use stream::{parse_streaming, StreamParser};
+pub use structure::validate_structure;
+pub use subtree::parse_subtree;
+pub use token::{tokenize, Token, TokenKind};
+pub use transform::{PathSegment, PruneOptions};
+pub use visit::{parse_events, Visitor};
+
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::{self, Vec},
+};
+use core::convert::TryFrom;
 
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_till},
-    character::complete::multispace0,
-    combinator::{map, value},
-    error::context,
-    multi::separated_list0,
-    number::complete::double,
-    sequence::{delimited, separated_pair},
-    IResult,
+    bytes::complete::{tag, take_while, take_while1, take_while_m_n},
+    character::complete::{char, digit0, digit1, one_of, satisfy},
+    combinator::{all_consuming, cut, map, map_opt, map_res, opt, recognize, value},
+    error::{context, make_error},
+    sequence::{preceded, terminated, tuple},
+    Err, IResult,
 };
 
+type Res<'a, T> = IResult<&'a str, T, Error<'a>>;
+
+/// The map backing [`JsonValue::Object`], chosen by the `map-index`,
+/// `map-hash` and `map-btree` features.
+///
+/// By default, with the `map-index` feature or its alias `preserve_order`,
+/// this is an `IndexMap` that keeps members in the order they appeared in the
+/// input. With `map-hash` it is a `HashMap`, and with `map-btree`, or when the
+/// `std` feature is disabled, a `BTreeMap` keeping members sorted by key.
+/// Exactly one is used: `map-btree` wins over `map-hash`, and either over the
+/// default `map-index`, so `--features map-btree` needs no
+/// `--no-default-features`.
+///
+/// The hashed maps use `std`'s `SipHash`, or with the `ahash` feature the
+/// cheaper aHash. Both are randomly keyed, so that input crafted for its keys
+/// to collide cannot turn insertions into linear scans, but aHash is built
+/// for speed rather than to withstand a determined attacker as `SipHash`
+/// aims to, so reserve it for trusted input. Create one with
+/// `Map::default()`, which works whichever map and hasher back it.
+pub type Map = MapImpl<String, JsonValue>;
+
+/// How the hashed maps backing [`Map`] hash keys.
+#[cfg(all(
+    all(feature = "std", not(feature = "map-btree")),
+    not(feature = "ahash")
+))]
+type MapHasher = std::collections::hash_map::RandomState;
+#[cfg(all(all(feature = "std", not(feature = "map-btree")), feature = "ahash"))]
+type MapHasher = ahash::RandomState;
+
+#[cfg(all(
+    feature = "preserve_order",
+    not(feature = "map-btree"),
+    not(feature = "map-hash")
+))]
+type MapImpl<K, V> = indexmap::IndexMap<K, V, MapHasher>;
+#[cfg(all(
+    feature = "std",
+    not(feature = "map-btree"),
+    any(feature = "map-hash", not(feature = "preserve_order"))
+))]
+type MapImpl<K, V> = std::collections::HashMap<K, V, MapHasher>;
+#[cfg(any(feature = "map-btree", not(feature = "std")))]
+type MapImpl<K, V> = alloc::collections::BTreeMap<K, V>;
+
+/// A member of a [`Map`] that may or may not be present, from
+/// [`JsonValue::entry`]: the `Entry` type of whichever map backs `Map`.
+#[cfg(all(
+    feature = "preserve_order",
+    not(feature = "map-btree"),
+    not(feature = "map-hash")
+))]
+pub type Entry<'a> = indexmap::map::Entry<'a, String, JsonValue>;
+/// A member of a [`Map`] that may or may not be present, from
+/// [`JsonValue::entry`]: the `Entry` type of whichever map backs `Map`.
+#[cfg(all(
+    feature = "std",
+    not(feature = "map-btree"),
+    any(feature = "map-hash", not(feature = "preserve_order"))
+))]
+pub type Entry<'a> = std::collections::hash_map::Entry<'a, String, JsonValue>;
+/// A member of a [`Map`] that may or may not be present, from
+/// [`JsonValue::entry`]: the `Entry` type of whichever map backs `Map`.
+#[cfg(any(feature = "map-btree", not(feature = "std")))]
+pub type Entry<'a> = alloc::collections::btree_map::Entry<'a, String, JsonValue>;
+
+/// Creates a map with room for `capacity` members, if the map type supports
+/// reserving room; a `BTreeMap` does not.
+#[cfg_attr(
+    any(feature = "map-btree", not(feature = "std")),
+    allow(unused_variables)
+)]
+fn map_with_capacity<K, V>(capacity: usize) -> MapImpl<K, V> {
+    #[cfg(all(feature = "std", not(feature = "map-btree")))]
+    return MapImpl::with_capacity_and_hasher(capacity, MapHasher::default());
+    #[cfg(any(feature = "map-btree", not(feature = "std")))]
+    return MapImpl::new();
+}
+
+/// Removes the member `key` from `map`, keeping the order of the others.
+fn remove_member(map: &mut Map, key: &str) -> Option<JsonValue> {
+    #[cfg(all(
+        feature = "preserve_order",
+        not(feature = "map-btree"),
+        not(feature = "map-hash")
+    ))]
+    return map.shift_remove(key);
+    #[cfg(not(all(
+        feature = "preserve_order",
+        not(feature = "map-btree"),
+        not(feature = "map-hash")
+    )))]
+    return map.remove(key);
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum JsonValue {
+    /// A number written with a fraction or an exponent, `-0`, or an integer
+    /// too large for `Int`.
     Number(f64),
+    /// A number written without a fraction or an exponent.
+    Int(i64),
+    /// The text of a number exactly as written, when parsing with
+    /// [`ParseOptions::raw_numbers`].
+    RawNumber(String),
+    /// An integer too large for `Int`, with the `bigint` feature.
+    #[cfg(feature = "bigint")]
+    BigInt(num_bigint::BigInt),
     Str(String),
     Bool(bool),
     Null,
-    Object(HashMap<String, JsonValue>),
+    Object(Map),
     Array(Vec<JsonValue>),
 }
 
-fn parse_string(input: &str) -> IResult<&str, &str> {
-    context(
-        "string",
-        delimited(tag("\""), take_till(|c| c == '"'), tag("\"")),
+/// A value other than an array or an object, as parsed from the input.
+#[derive(Debug, Clone, PartialEq)]
+enum Scalar<'a> {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    /// The text of a number, when parsing with [`ParseOptions::raw_numbers`].
+    Raw(&'a str),
+    /// The text of an integer too large for `Int`.
+    #[cfg(feature = "bigint")]
+    BigInt(&'a str),
+    Str(Cow<'a, str>),
+}
+
+/// Builds the values the grammar parses: the owned [`JsonValue`], the
+/// borrowed [`JsonValueRef`], the events of a [`Visitor`], or the values of
+/// a [`JsonBuilder`].
+///
+/// Containers are built from their contents once they are closed; the
+/// `begin_` and `key` hooks report them as they are opened and parsed, and
+/// `span` reports where each value was found.
+trait Builder<'a> {
+    type Value;
+    type Object;
+
+    fn null(&mut self) -> Self::Value;
+    fn bool(&mut self, b: bool) -> Self::Value;
+    fn int(&mut self, i: i64) -> Self::Value;
+    fn float(&mut self, f: f64) -> Self::Value;
+    fn string(&mut self, s: Cow<'a, str>) -> Self::Value;
+    /// Builds a string that contained escapes from `s`, where it was decoded
+    /// into a buffer reused for the whole document, and by default copies it
+    /// into a string of its own.
+    fn decoded_string(&mut self, s: &str) -> Self::Value {
+        self.string(Cow::Owned(s.into()))
+    }
+    /// Makes the key of a member from `key`, decoded as for
+    /// [`Builder::decoded_string`], and by default copies it.
+    fn decoded_key(&mut self, key: &str) -> Cow<'a, str> {
+        Cow::Owned(key.into())
+    }
+    /// Builds a number kept as written, and by default converts it as usual.
+    fn raw_number(&mut self, token: &'a str) -> Self::Value {
+        self.scalar(number(token))
+    }
+    /// Builds an integer too large for `i64`, and by default converts it to
+    /// the nearest `f64` as without the `bigint` feature.
+    #[cfg(feature = "bigint")]
+    fn big_int(&mut self, token: &'a str) -> Self::Value {
+        self.float(token.parse().unwrap_or(f64::NAN))
+    }
+
+    fn begin_array(&mut self) {}
+    fn array(&mut self, items: Vec<Self::Value>) -> Self::Value;
+    /// Builds an array from its elements drained from the end of the scratch
+    /// stack of [`parse_container`], by default collecting them for
+    /// [`Builder::array`].
+    fn array_from(&mut self, items: vec::Drain<'_, Self::Value>) -> Self::Value {
+        self.array(items.collect())
+    }
+
+    fn begin_object(&mut self) {}
+    fn key(&mut self, _key: &str) {}
+    fn new_object(&mut self, capacity: usize) -> Self::Object;
+    fn contains_key(&self, object: &Self::Object, key: &str) -> bool;
+    /// Adds a member, replacing an earlier one with the same key.
+    fn insert(&mut self, object: &mut Self::Object, key: Cow<'a, str>, value: Self::Value);
+    fn object(&mut self, object: Self::Object) -> Self::Value;
+
+    /// Called with the input at the start of a member whose key an earlier
+    /// member of the same object has, unless that fails the parse.
+    fn duplicate_key(&mut self, _at: &'a str, _key: &str) {}
+    /// Called with the input at a comma accepted before a closing bracket.
+    fn trailing_comma(&mut self, _at: &'a str) {}
+    /// Called with the input at whitespace skipped between tokens, and its
+    /// length, when it may hold comments.
+    fn comments(&mut self, _at: &'a str, _len: usize) {}
+
+    /// Called after each value is built, with the input at its start and the
+    /// input following it.
+    fn span(&mut self, _start: &'a str, _end: &'a str) {}
+
+    /// Called with the error in a malformed element of an array or object.
+    /// Returning `Ok` skips the element and carries on parsing after it; by
+    /// default the error is returned and parsing stops.
+    fn recover(&mut self, error: Error<'a>) -> Result<(), Error<'a>> {
+        Err(error)
+    }
+
+    /// Builds the value a [`ParseOptions::number_hook`] returned for a number,
+    /// or returns `None` to build the number as usual, as builders of values
+    /// other than [`JsonValue`] do by default.
+    fn hooked_number(&mut self, _value: JsonValue) -> Option<Self::Value> {
+        None
+    }
+
+    fn scalar(&mut self, scalar: Scalar<'a>) -> Self::Value {
+        match scalar {
+            Scalar::Null => self.null(),
+            Scalar::Bool(b) => self.bool(b),
+            Scalar::Int(i) => self.int(i),
+            Scalar::Float(f) => self.float(f),
+            Scalar::Raw(token) => self.raw_number(token),
+            #[cfg(feature = "bigint")]
+            Scalar::BigInt(token) => self.big_int(token),
+            Scalar::Str(s) => self.string(s),
+        }
+    }
+}
+
+/// Builds a [`JsonValue`].
+struct ValueBuilder;
+
+impl<'a> Builder<'a> for ValueBuilder {
+    type Value = JsonValue;
+    type Object = Map;
+
+    fn null(&mut self) -> JsonValue {
+        JsonValue::Null
+    }
+
+    fn bool(&mut self, b: bool) -> JsonValue {
+        JsonValue::Bool(b)
+    }
+
+    fn int(&mut self, i: i64) -> JsonValue {
+        JsonValue::Int(i)
+    }
+
+    fn float(&mut self, f: f64) -> JsonValue {
+        JsonValue::Number(f)
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) -> JsonValue {
+        JsonValue::Str(s.into_owned())
+    }
+
+    fn raw_number(&mut self, token: &'a str) -> JsonValue {
+        JsonValue::RawNumber(token.to_string())
+    }
+
+    #[cfg(feature = "bigint")]
+    fn big_int(&mut self, token: &'a str) -> JsonValue {
+        // Every integer token is a valid `BigInt` literal.
+        token
+            .parse()
+            .map_or(JsonValue::Number(f64::NAN), JsonValue::BigInt)
+    }
+
+    fn array(&mut self, items: Vec<JsonValue>) -> JsonValue {
+        JsonValue::Array(items)
+    }
+
+    fn new_object(&mut self, capacity: usize) -> Map {
+        map_with_capacity(capacity)
+    }
+
+    fn hooked_number(&mut self, value: JsonValue) -> Option<JsonValue> {
+        Some(value)
+    }
+
+    fn contains_key(&self, object: &Map, key: &str) -> bool {
+        object.contains_key(key)
+    }
+
+    fn insert(&mut self, object: &mut Map, key: Cow<'a, str>, value: JsonValue) {
+        object.insert(key.into_owned(), value);
+    }
+
+    fn object(&mut self, object: Map) -> JsonValue {
+        JsonValue::Object(object)
+    }
+}
+
+enum StringFragment<'a> {
+    Literal(&'a str),
+    Escaped(char),
+}
+
+fn parse_hex4(input: &str) -> Res<'_, u32> {
+    map_res(
+        take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()),
+        |hex| u32::from_str_radix(hex, 16),
     )(input)
 }
 
-fn parse_bool(input: &str) -> IResult<&str, bool> {
-    alt((value(true, tag("true")), value(false, tag("false"))))(input)
+/// Decodes the `XXXX` part of a `\uXXXX` escape, combining a high surrogate
+/// with the `\uXXXX` low surrogate that must follow it.
+fn parse_unicode_escape(input: &str) -> Res<'_, char> {
+    let (rest, first) = parse_hex4(input)?;
+    match first {
+        0xD800..=0xDBFF => map_opt(preceded(tag("\\u"), parse_hex4), |second| match second {
+            0xDC00..=0xDFFF => {
+                char::from_u32(0x10000 + ((first - 0xD800) << 10) + (second - 0xDC00))
+            }
+            _ => None,
+        })(rest),
+        _ => match char::from_u32(first) {
+            Some(c) => Ok((rest, c)),
+            None => Err(Err::Error(make_error(input, nom::error::ErrorKind::MapOpt))),
+        },
+    }
+}
+
+/// Decodes a `\uXXXX` escape like [`parse_unicode_escape`], replacing a
+/// lone surrogate with U+FFFD REPLACEMENT CHARACTER.
+fn parse_unicode_escape_lossy(input: &str) -> Res<'_, char> {
+    match parse_unicode_escape(input) {
+        Err(Err::Error(_)) => value('\u{FFFD}', parse_hex4)(input),
+        decoded => decoded,
+    }
 }
 
-fn parse_null(input: &str) -> IResult<&str, JsonValue> {
-    value(JsonValue::Null, tag("null"))(input)
+/// Decodes a `\uXXXX` escape given what follows its `u`.
+type UnicodeEscape = for<'a> fn(&'a str) -> Res<'a, char>;
+
+/// Decodes what follows the backslash of one of the escapes JSON defines,
+/// with `unicode` decoding `\u` escapes.
+fn parse_escaped(input: &str, unicode: UnicodeEscape) -> Res<'_, char> {
+    alt((
+        value('"', char('"')),
+        value('\\', char('\\')),
+        value('/', char('/')),
+        value('\u{08}', char('b')),
+        value('\u{0C}', char('f')),
+        value('\n', char('n')),
+        value('\r', char('r')),
+        value('\t', char('t')),
+        preceded(char('u'), unicode),
+    ))(input)
 }
 
-fn parse_array(input: &str) -> IResult<&str, Vec<JsonValue>> {
+fn parse_escape(input: &str, unicode: UnicodeEscape) -> Res<'_, char> {
     context(
-        "array",
-        delimited(
-            tag("["),
-            separated_list0(tag(","), delimited(multispace0, parse, multispace0)),
-            tag("]"),
-        ),
+        "escape",
+        preceded(char('\\'), |i| parse_escaped(i, unicode)),
     )(input)
 }
 
-fn parse_object(input: &str) -> IResult<&str, HashMap<String, JsonValue>> {
-    let parse_pair = separated_pair(
-        delimited(multispace0, parse_string, multispace0),
-        tag(":"),
-        delimited(multispace0, parse, multispace0),
-    );
+/// Decodes an escape like [`parse_escape`], also taking a backslash before
+/// any other character but `u` to stand for that character.
+fn parse_lenient_escape(input: &str, unicode: UnicodeEscape) -> Res<'_, char> {
     context(
-        "object",
-        delimited(
-            tag("{"),
-            map(
-                separated_list0(tag(","), parse_pair),
-                |pairs: Vec<(&str, JsonValue)>| {
-                    let mut map = HashMap::new();
-                    for (k, v) in pairs {
-                        map.insert(k.to_string(), v);
-                    }
-                    map
-                },
-            ),
-            tag("}"),
+        "escape",
+        preceded(
+            char('\\'),
+            alt((|i| parse_escaped(i, unicode), satisfy(|c| c != 'u'))),
         ),
     )(input)
 }
 
-pub fn parse(input: &str) -> IResult<&str, JsonValue> {
+/// Decodes an escape like [`parse_escape`], also taking `\'` to stand for a
+/// single quote.
+fn parse_single_quoted_escape(input: &str, unicode: UnicodeEscape) -> Res<'_, char> {
     context(
-        "parse",
-        delimited(
-            multispace0,
-            alt((
-                map(parse_object, |x| JsonValue::Object(x)),
-                map(parse_array, |x| JsonValue::Array(x)),
-                map(double, |x| JsonValue::Number(x)),
-                map(parse_string, |s| JsonValue::Str(s.to_string())),
-                map(parse_bool, |x| JsonValue::Bool(x)),
-                parse_null,
-            )),
-            multispace0,
+        "escape",
+        preceded(
+            char('\\'),
+            alt((value('\'', char('\'')), |i| parse_escaped(i, unicode))),
         ),
     )(input)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+/// Whether `c` may appear unescaped in a string delimited by `quote`: it may
+/// unless it is the quote, the backslash or one of the control characters
+/// U+0000 through U+001F.
+fn is_string_char(c: char, quote: char) -> bool {
+    c != quote && c != '\\' && c >= '\u{20}'
+}
 
-    #[test]
-    fn test_stirng() {
-        assert_eq!(
-            parse_string(r#""1234 asdf" jjjj"#),
-            Ok((" jjjj", "1234 asdf"))
-        );
+/// Parses a string, borrowing it from the input unless it contains escapes.
+fn parse_string(input: &str) -> Res<'_, Cow<'_, str>> {
+    parse_escaped_string(input, '"', parse_escape, parse_unicode_escape)
+}
+
+/// Parses a string like [`parse_string`], with lenient escapes, single
+/// quotes and lone surrogates if `options` allows them.
+fn parse_string_with<'a>(input: &'a str, options: &ParseOptions) -> Res<'a, Cow<'a, str>> {
+    let mut decoded = String::new();
+    let (rest, literal) = parse_string_into(input, options, &mut decoded)?;
+    Ok((rest, literal.map_or(Cow::Owned(decoded), Cow::Borrowed)))
+}
+
+/// Parses a string like [`parse_string_with`], returning it borrowed from the
+/// input unless it contains escapes, or else `None` with the string decoded
+/// into `decoded` in place of what it held.
+fn parse_string_into<'a>(
+    input: &'a str,
+    options: &ParseOptions,
+    decoded: &mut String,
+) -> Res<'a, Option<&'a str>> {
+    let unicode: UnicodeEscape = match options.on_invalid_surrogate {
+        SurrogatePolicy::Error => parse_unicode_escape,
+        SurrogatePolicy::Replace => parse_unicode_escape_lossy,
+    };
+    let (quote, escape): (_, fn(&'a str, UnicodeEscape) -> Res<'a, char>) =
+        if options.allow_single_quotes && input.starts_with('\'') {
+            if options.lenient_escapes {
+                ('\'', parse_lenient_escape)
+            } else {
+                ('\'', parse_single_quoted_escape)
+            }
+        } else if options.lenient_escapes {
+            ('"', parse_lenient_escape)
+        } else {
+            ('"', parse_escape)
+        };
+    parse_escaped_string_into(input, quote, escape, unicode, decoded)
+}
+
+/// Parses a string delimited by `quote` whose escapes are decoded by
+/// `escape`, with `unicode` decoding `\u` escapes.
+fn parse_escaped_string<'a>(
+    input: &'a str,
+    quote: char,
+    escape: fn(&'a str, UnicodeEscape) -> Res<'a, char>,
+    unicode: UnicodeEscape,
+) -> Res<'a, Cow<'a, str>> {
+    let mut decoded = String::new();
+    let (rest, literal) = parse_escaped_string_into(input, quote, escape, unicode, &mut decoded)?;
+    Ok((rest, literal.map_or(Cow::Owned(decoded), Cow::Borrowed)))
+}
+
+/// Parses a string like [`parse_escaped_string`], decoding one that contains
+/// escapes into `decoded` as [`parse_string_into`] does.
+fn parse_escaped_string_into<'a>(
+    input: &'a str,
+    quote: char,
+    escape: fn(&'a str, UnicodeEscape) -> Res<'a, char>,
+    unicode: UnicodeEscape,
+    decoded: &mut String,
+) -> Res<'a, Option<&'a str>> {
+    if !input.starts_with(quote) {
+        // Fail without a context, which would allocate, as parsing a keyword
+        // or number in the same place first tries a string.
+        return Err(Err::Error(Error::new(input, ErrorKind::Char(quote))));
     }
+    let is_literal = move |c| is_string_char(c, quote);
+    context("string", move |input: &'a str| {
+        let (rest, literal) = preceded(char(quote), take_while(is_literal))(input)?;
+        if let Some(rest) = rest.strip_prefix(quote) {
+            return Ok((rest, Some(literal)));
+        }
+        decoded.clear();
+        decoded.push_str(literal);
+        let unescaped = |i| unescape_into(i, &mut *decoded, quote, escape, unicode);
+        let (rest, _) = cut(terminated(unescaped, char(quote)))(rest)?;
+        Ok((rest, None))
+    })(input)
+}
 
-    #[test]
-    fn test_bool() {
-        assert_eq!(parse_bool("true"), Ok(("", true)));
-        assert_eq!(parse_bool("falseff"), Ok(("ff", false)));
+/// Decodes the characters and escapes at the start of `input` up to where a
+/// string delimited by `quote` would end or fail, appending them to `decoded`.
+fn unescape_into<'a>(
+    mut input: &'a str,
+    decoded: &mut String,
+    quote: char,
+    escape: fn(&'a str, UnicodeEscape) -> Res<'a, char>,
+    unicode: UnicodeEscape,
+) -> Res<'a, ()> {
+    let is_literal = move |c| is_string_char(c, quote);
+    let mut fragment = alt((
+        map(take_while1(is_literal), StringFragment::Literal),
+        map(move |i| escape(i, unicode), StringFragment::Escaped),
+    ));
+    // Appending grows the string geometrically, so unescaping stays linear
+    // in the length of the string.
+    loop {
+        // Stop where no escape starts without trying one, whose failure would
+        // allocate its context.
+        match input.chars().next() {
+            Some(c) if is_literal(c) || c == '\\' => {}
+            _ => return Ok((input, ())),
+        }
+        let (rest, parsed) = match fragment(input) {
+            Ok(parsed) => parsed,
+            Err(Err::Error(_)) => return Ok((input, ())),
+            Err(e) => return Err(e),
+        };
+        match parsed {
+            StringFragment::Literal(literal) => decoded.push_str(literal),
+            StringFragment::Escaped(c) => decoded.push(c),
+        }
+        input = rest;
     }
+}
 
-    #[test]
-    fn test_array() {
-        assert_eq!(
-            parse_array("[123.4]"),
-            Ok(("", vec![JsonValue::Number(123.4)]))
-        );
-        assert_eq!(
-            parse_array(r#"[ 333,  "wow"   ,null  ]"#),
-            Ok((
-                "",
-                vec![
-                    JsonValue::Number(333.),
-                    JsonValue::Str("wow".to_string()),
-                    JsonValue::Null
-                ]
-            ))
+/// Recognizes a number following the JSON grammar: an optional minus, an
+/// integer part without leading zeros, then an optional fraction and exponent.
+fn recognize_number(input: &str) -> Res<'_, &str> {
+    let (rest, token) = recognize(tuple((
+        opt(char('-')),
+        alt((tag("0"), preceded(satisfy(|c| c.is_ascii_digit()), digit0))),
+        opt(preceded(char('.'), cut(digit1))),
+        opt(tuple((one_of("eE"), opt(one_of("+-")), cut(digit1)))),
+    )))(input)?;
+    if rest.starts_with(|c: char| c.is_ascii_digit()) {
+        // Only a leading zero can end the integer part before a digit.
+        return Err(Err::Failure(make_error(rest, nom::error::ErrorKind::Digit)));
+    }
+    Ok((rest, token))
+}
+
+/// Whether `token` is written as an integer whose value an `f64` cannot hold
+/// exactly.
+fn is_imprecise_integer(token: &str) -> bool {
+    if token.contains(['.', 'e', 'E']) {
+        return false;
+    }
+    let f: f64 = token.parse().unwrap_or(f64::INFINITY);
+    // Formatting with a precision writes the exact value of the float.
+    !f.is_finite() || format!("{:.0}", f) != token
+}
+
+/// Converts a number token to an `Int` if it is written without a fraction or
+/// an exponent and fits, and to a `Float` otherwise. With the `bigint`
+/// feature, an integer that does not fit is kept as a `BigInt` instead.
+///
+/// `-0` is a `Float`, since only a float can keep the sign of a zero.
+fn number(token: &str) -> Scalar<'_> {
+    if token == "-0" {
+        return Scalar::Float(-0.);
+    }
+    if !token.contains(['.', 'e', 'E']) {
+        if let Ok(i) = token.parse() {
+            return Scalar::Int(i);
+        }
+        #[cfg(feature = "bigint")]
+        return Scalar::BigInt(token);
+    }
+    // Every token of the JSON number grammar is a valid `f64` literal.
+    Scalar::Float(token.parse().unwrap_or(f64::NAN))
+}
+
+fn parse_number(input: &str) -> Res<'_, Scalar<'_>> {
+    let (rest, token) = number_literal(input)?;
+    Ok((rest, finite_number(input, token)?))
+}
+
+/// Converts the number token at the start of `input` like [`number`],
+/// failing with [`ErrorKind::NumberOutOfRange`] if it is too large for an
+/// `f64`, as JSON has no infinity. A number too small for an `f64` is
+/// rounded to zero instead, keeping its sign.
+fn finite_number<'a>(input: &'a str, token: &'a str) -> Result<Scalar<'a>, Err<Error<'a>>> {
+    match number(token) {
+        Scalar::Float(f) if f.is_infinite() => {
+            Err(Err::Failure(Error::new(input, ErrorKind::NumberOutOfRange)))
+        }
+        scalar => Ok(scalar),
+    }
+}
+
+/// Recognizes a number like [`recognize_number`] when `input` starts with a
+/// minus or a digit, failing with [`ErrorKind::InvalidNumber`] unless the
+/// whole run of number characters there, such as `1.2.3`, is one number.
+fn number_literal(input: &str) -> Res<'_, &str> {
+    if !input.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+        return Err(Err::Error(make_error(input, nom::error::ErrorKind::Digit)));
+    }
+    let is_number_char = |c: char| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-');
+    let run = input.find(|c| !is_number_char(c)).unwrap_or(input.len());
+    match recognize_number(input) {
+        Ok((rest, token)) if token.len() == run => Ok((rest, token)),
+        _ => {
+            let kind = ErrorKind::InvalidNumber(input[..run].to_string());
+            Err(Err::Failure(Error::new(input, kind)))
+        }
+    }
+}
+
+/// Parses the `NaN`, `Infinity` and `-Infinity` extensions if `options`
+/// allows them.
+fn parse_non_finite<'a>(input: &'a str, options: &ParseOptions) -> Res<'a, Scalar<'a>> {
+    if !options.allow_nan {
+        return Err(Err::Error(make_error(input, nom::error::ErrorKind::Tag)));
+    }
+    map(
+        alt((
+            value(f64::NAN, keyword("NaN")),
+            value(f64::INFINITY, keyword("Infinity")),
+            value(f64::NEG_INFINITY, keyword("-Infinity")),
+        )),
+        Scalar::Float,
+    )(input)
+}
+
+/// Parses a hexadecimal integer such as `0xFF` or `-0x10` if `options`
+/// allows them, failing on a prefix not followed by hexadecimal digits.
+fn parse_hex<'a>(input: &'a str, options: &ParseOptions) -> Res<'a, Scalar<'a>> {
+    let unsigned = input.strip_prefix('-').unwrap_or(input);
+    let digits = match unsigned
+        .strip_prefix("0x")
+        .or_else(|| unsigned.strip_prefix("0X"))
+    {
+        Some(digits) if options.allow_hex_numbers => digits,
+        _ => {
+            return Err(Err::Error(make_error(
+                input,
+                nom::error::ErrorKind::HexDigit,
+            )))
+        }
+    };
+    let len = digits
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(digits.len());
+    let (token, rest) = input.split_at(input.len() - digits.len() + len);
+    if len == 0 || !digits[..len].chars().all(|c| c.is_ascii_hexdigit()) {
+        let kind = ErrorKind::InvalidNumber(token.to_string());
+        return Err(Err::Failure(Error::new(input, kind)));
+    }
+    let magnitude = i128::from_str_radix(&digits[..len], 16).ok();
+    let signed = if unsigned.len() < input.len() {
+        magnitude.map(|m| -m)
+    } else {
+        magnitude
+    };
+    match signed.and_then(|i| i64::try_from(i).ok()) {
+        Some(i) => Ok((rest, Scalar::Int(i))),
+        None => Err(Err::Failure(Error::new(input, ErrorKind::NumberOutOfRange))),
+    }
+}
+
+/// Parses a number with a leading `+` or a decimal point without digits on
+/// one side, such as `+1.5`, `.5` or `5.`, as the JSON number it stands for,
+/// failing on a run of number characters that is not one even so. A number
+/// JSON accepts as written is left to [`parse_number`].
+fn parse_relaxed_number(input: &str) -> Res<'_, Scalar<'_>> {
+    let is_number_char = |c: char| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-');
+    let run = input.find(|c| !is_number_char(c)).unwrap_or(input.len());
+    let token = &input[..run];
+    let unsigned = token.strip_prefix(['+', '-']).unwrap_or(token);
+    let split = unsigned.find(['e', 'E']).unwrap_or(unsigned.len());
+    let (mantissa, exponent) = unsigned.split_at(split);
+    let invalid = || {
+        let kind = ErrorKind::InvalidNumber(token.to_string());
+        Err(Err::Failure(Error::new(input, kind)))
+    };
+    if mantissa.starts_with(['+', '-']) {
+        return invalid();
+    }
+    if !mantissa.contains(|c: char| c.is_ascii_digit()) {
+        return match token.chars().next() {
+            Some('+' | '.') => invalid(),
+            _ => Err(Err::Error(make_error(input, nom::error::ErrorKind::Digit))),
+        };
+    }
+    let mut normalized = String::with_capacity(token.len() + 2);
+    if token.starts_with('-') {
+        normalized.push('-');
+    }
+    if mantissa.starts_with('.') {
+        normalized.push('0');
+    }
+    normalized.push_str(mantissa);
+    if mantissa.ends_with('.') {
+        normalized.push('0');
+    }
+    normalized.push_str(exponent);
+    if normalized == token {
+        return Err(Err::Error(make_error(input, nom::error::ErrorKind::Digit)));
+    }
+    match recognize_number(&normalized) {
+        Ok(("", _)) => {}
+        _ => return invalid(),
+    }
+    let rest = &input[run..];
+    if normalized == unsigned {
+        // Only a `+` was dropped, so the number is the rest of the token,
+        // which may be an integer.
+        return Ok((rest, finite_number(input, unsigned)?));
+    }
+    match normalized.parse::<f64>() {
+        Ok(f) if f.is_finite() => Ok((rest, Scalar::Float(f))),
+        _ => Err(Err::Failure(Error::new(input, ErrorKind::NumberOutOfRange))),
+    }
+}
+
+/// Matches `word` where it is not immediately followed by a letter, digit
+/// or underscore, so that `trueish` is not read as `true`.
+fn keyword<'a>(word: &'static str) -> impl Fn(&'a str) -> Res<'a, &'a str> {
+    move |input| {
+        let (rest, token) = tag(word)(input)?;
+        match rest.chars().next() {
+            Some(c) if c.is_alphanumeric() || c == '_' => {
+                Err(Err::Error(make_error(input, nom::error::ErrorKind::Tag)))
+            }
+            _ => Ok((rest, token)),
+        }
+    }
+}
+
+fn parse_bool(input: &str) -> Res<'_, bool> {
+    alt((value(true, keyword("true")), value(false, keyword("false"))))(input)
+}
+
+fn parse_null(input: &str) -> Res<'_, Scalar<'_>> {
+    value(Scalar::Null, keyword("null"))(input)
+}
+
+/// Skips whitespace and, if `options` allows them, `//` and `/* */` comments.
+///
+/// This is the only place the parser skips anything between tokens, so what
+/// is accepted there is the same around every token. A byte order mark or
+/// NUL following what was skipped fails the parse, unless `options` counts
+/// it as whitespace.
+fn skip_ws<'a>(input: &'a str, options: &ParseOptions) -> Res<'a, ()> {
+    let mut input = input.trim_start_matches(options.whitespace);
+    loop {
+        if let Some(c @ ('\u{FEFF}' | '\0')) = input.chars().next() {
+            let error = Error::new(input, ErrorKind::UnexpectedChar(c));
+            return Err(Err::Failure(error));
+        }
+        if !options.allow_comments {
+            return Ok((input, ()));
+        }
+        if let Some(comment) = input.strip_prefix("//") {
+            input = comment.find('\n').map_or("", |end| &comment[end..]);
+        } else if let Some(comment) = input.strip_prefix("/*") {
+            match comment.find("*/") {
+                Some(end) => input = &comment[end + 2..],
+                None => {
+                    let error = make_error(input, nom::error::ErrorKind::TakeUntil);
+                    return Err(Err::Failure(error));
+                }
+            }
+        } else {
+            return Ok((input, ()));
+        }
+        input = input.trim_start_matches(options.whitespace);
+    }
+}
+
+/// Skips whitespace and comments like [`skip_ws`], passing what was skipped
+/// to `builder` if it may hold comments.
+fn skip_ws_in<'a, B: Builder<'a>>(
+    input: &'a str,
+    options: &ParseOptions,
+    builder: &mut B,
+) -> Res<'a, ()> {
+    let (rest, _) = skip_ws(input, options)?;
+    if options.allow_comments && rest.len() < input.len() {
+        builder.comments(input, input.len() - rest.len());
+    }
+    Ok((rest, ()))
+}
+
+/// Fails once a container opened at nesting level `depth` exceeds the
+/// configured maximum depth.
+fn check_depth<'a>(input: &'a str, options: &ParseOptions, depth: usize) -> Res<'a, ()> {
+    if depth > options.max_depth {
+        return Err(Err::Failure(Error::new(
+            input,
+            ErrorKind::DepthLimitExceeded,
+        )));
+    }
+    Ok((input, ()))
+}
+
+/// Fails on a string or key parsed from `input` that exceeds the configured
+/// maximum length.
+fn check_length<'a>(input: &'a str, options: &ParseOptions, s: &str) -> Res<'a, ()> {
+    if s.len() > options.max_string_length {
+        return Err(Err::Failure(Error::new(input, ErrorKind::StringTooLong)));
+    }
+    Ok((input, ()))
+}
+
+/// An array or object that has been opened but not yet closed.
+///
+/// Its elements, or its members' keys and values, are kept at the end of
+/// the scratch stacks of [`parse_container`] until it is closed, so it can
+/// then be built with exactly the capacity it needs.
+#[derive(Clone, Copy)]
+enum Frame {
+    /// An array whose elements start at the given index of the values.
+    Array(usize),
+    /// An object whose members start at the given indices of the keys and
+    /// values.
+    Object(usize, usize),
+}
+
+impl Frame {
+    fn context(&self) -> &'static str {
+        match self {
+            Frame::Array(_) => "array",
+            Frame::Object(..) => "object",
+        }
+    }
+
+    fn open(&self) -> char {
+        match self {
+            Frame::Array(_) => '[',
+            Frame::Object(..) => '{',
+        }
+    }
+
+    fn close(&self) -> char {
+        match self {
+            Frame::Array(_) => ']',
+            Frame::Object(..) => '}',
+        }
+    }
+}
+
+/// What [`Error::expected`] names where a value is expected.
+const VALUE_TOKENS: &[&str] = &["string", "number", "object", "array", "bool", "null"];
+
+/// What [`Error::expected`] names at the start of an array's first element.
+const VALUE_TOKENS_OR_CLOSE: &[&str] =
+    &["string", "number", "object", "array", "bool", "null", "]"];
+
+/// Names `expected` as what `error` expected if it occurred right at `at`,
+/// rather than within what starts there.
+fn expecting<'a>(
+    error: Err<Error<'a>>,
+    at: &str,
+    expected: &'static [&'static str],
+) -> Err<Error<'a>> {
+    error.map(|mut e| {
+        if e.input.len() == at.len() && e.expected.is_empty() {
+            e.expected = expected;
+        }
+        e
+    })
+}
+
+/// Puts `error` in the contexts of the containers open on `stack`, as if they
+/// had been parsed recursively, and makes it a failure.
+fn in_stack<'a>(error: Err<Error<'a>>, stack: &[Frame]) -> Err<Error<'a>> {
+    match error {
+        Err::Error(mut e) | Err::Failure(mut e) => {
+            for (level, frame) in stack.iter().enumerate().rev() {
+                e.context.push(frame.context());
+                if level > 0 {
+                    e.context.push("parse");
+                }
+            }
+            Err::Failure(e)
+        }
+        incomplete => incomplete,
+    }
+}
+
+/// The containers open in [`parse_container`], the scratch stacks their
+/// contents are collected on, and the builder they are built with.
+struct Stack<'a, 'b, B: Builder<'a>> {
+    frames: Vec<Frame>,
+    /// Object keys, with where each member started.
+    keys: Vec<(&'a str, Cow<'a, str>)>,
+    values: Vec<B::Value>,
+    /// Where each open container started.
+    starts: Vec<&'a str>,
+    /// The number of elements and members started so far.
+    elements: usize,
+    /// The buffer strings and keys containing escapes are decoded into.
+    decoded: String,
+    builder: &'b mut B,
+}
+
+impl<'a, 'b, B: Builder<'a>> Stack<'a, 'b, B> {
+    /// Opens the array or object at the start of `input`, which is nested at
+    /// level `depth`.
+    fn open(&mut self, input: &'a str, options: &ParseOptions, depth: usize) -> Res<'a, ()> {
+        let frame = match input.chars().next() {
+            Some('[') => Frame::Array(self.values.len()),
+            Some('{') => Frame::Object(self.keys.len(), self.values.len()),
+            _ => return Err(Err::Error(make_error(input, nom::error::ErrorKind::Char))),
+        };
+        self.frames.push(frame);
+        self.starts.push(input);
+        check_depth(&input[1..], options, depth).map_err(|e| self.error(e))?;
+        match frame {
+            Frame::Array(_) => self.builder.begin_array(),
+            Frame::Object(..) => self.builder.begin_object(),
+        }
+        Ok((&input[1..], ()))
+    }
+
+    /// Parses an object key and the colon after it with [`parse_key`].
+    fn key(&mut self, input: &'a str, options: &ParseOptions) -> Res<'a, (Cow<'a, str>, &'a str)> {
+        parse_key(input, options, self.builder, &mut self.decoded)
+    }
+
+    /// Parses and builds a value other than an array or an object with
+    /// [`parse_scalar_in`].
+    fn scalar(&mut self, input: &'a str, options: &ParseOptions) -> Res<'a, B::Value> {
+        context("parse", |i| {
+            parse_scalar_in(i, options, self.builder, &mut self.decoded)
+        })(input)
+    }
+
+    /// Closes the innermost container, which ends before `end`, building it
+    /// from its contents.
+    fn close(&mut self, options: &ParseOptions, end: &'a str) -> Result<B::Value, Err<Error<'a>>> {
+        let value = match self.frames.last() {
+            Some(&Frame::Array(start)) => {
+                self.frames.pop();
+                self.builder.array_from(self.values.drain(start..))
+            }
+            Some(&Frame::Object(keys, values)) => {
+                let mut object = self.builder.new_object(self.keys.len() - keys);
+                let mut duplicate = None;
+                let members = self.keys.drain(keys..).zip(self.values.drain(values..));
+                if options.duplicate_keys == DuplicateKeyPolicy::Collect {
+                    collect_members(self.builder, &mut object, members);
+                } else {
+                    for ((at, key), value) in members {
+                        if self.builder.contains_key(&object, &key) {
+                            if options.duplicate_keys == DuplicateKeyPolicy::Error {
+                                duplicate = Some((at, key));
+                                break;
+                            }
+                            self.builder.duplicate_key(at, &key);
+                            if options.duplicate_keys == DuplicateKeyPolicy::FirstWins {
+                                continue;
+                            }
+                        }
+                        self.builder.insert(&mut object, key, value);
+                    }
+                }
+                if let Some((at, key)) = duplicate {
+                    let kind = ErrorKind::DuplicateKey(key.into_owned());
+                    return Err(self.error(Err::Failure(Error::new(at, kind))));
+                }
+                self.frames.pop();
+                self.builder.object(object)
+            }
+            None => unreachable!("no container is open"),
+        };
+        let start = self.starts.pop().unwrap();
+        self.builder.span(start, end);
+        Ok(value)
+    }
+
+    /// The error for input ending inside the innermost container, located at
+    /// its opener, where `expected` would have been accepted instead.
+    fn unclosed(&self, expected: &'static [&'static str]) -> Err<Error<'a>> {
+        let frame = self.frames.last().unwrap();
+        let mut error = Error::new(
+            self.starts.last().unwrap(),
+            ErrorKind::Unclosed(frame.open()),
         );
+        error.expected = expected;
+        Err::Failure(error)
     }
 
-    #[test]
-    fn test_nested() {
-        assert_eq!(
-            parse_array(r#"[1, [2, [3], true, "[not an array]"], false]"#),
-            Ok((
-                "",
-                vec![
-                    JsonValue::Number(1.),
-                    JsonValue::Array(vec![
-                        JsonValue::Number(2.),
-                        JsonValue::Array(vec![JsonValue::Number(3.)]),
-                        JsonValue::Bool(true),
-                        JsonValue::Str("[not an array]".to_string()),
-                    ]),
-                    JsonValue::Bool(false),
-                ]
-            ))
-        )
+    /// Puts `error` in the contexts of the open containers, and records the
+    /// path of the value it occurred in.
+    fn error(&self, error: Err<Error<'a>>) -> Err<Error<'a>> {
+        match in_stack(error, &self.frames) {
+            Err::Failure(mut e) => {
+                e.path = self.path();
+                Err::Failure(e)
+            }
+            incomplete => incomplete,
+        }
+    }
+
+    /// Offers the builder `error`, which occurred in the element starting at
+    /// `from`. If the builder recovers from it, returns where the element ends.
+    fn recover(&mut self, error: Err<Error<'a>>, from: &'a str) -> Result<&'a str, Err<Error<'a>>> {
+        match self.error(error) {
+            Err::Error(e) | Err::Failure(e) => match self.builder.recover(e) {
+                Ok(()) => Ok(skip_element(from)),
+                Err(e) => Err(Err::Failure(e)),
+            },
+            incomplete => Err(incomplete),
+        }
+    }
+
+    /// The JSON Pointer of the value being parsed in the innermost container.
+    ///
+    /// An array contributes the index of its next element; an object, the key
+    /// of a member whose value has not been added yet.
+    fn path(&self) -> String {
+        let (mut keys, mut values) = (self.keys.len(), self.values.len());
+        let mut tokens = Vec::new();
+        for frame in self.frames.iter().rev() {
+            match *frame {
+                Frame::Array(start) => {
+                    tokens.push((values - start).to_string());
+                    values = start;
+                }
+                Frame::Object(key_start, value_start) => {
+                    if keys - key_start > values - value_start {
+                        tokens.push(pointer::escape(&self.keys[keys - 1].1));
+                    }
+                    keys = key_start;
+                    values = value_start;
+                }
+            }
+        }
+        let mut path = String::new();
+        for token in tokens.iter().rev() {
+            path.push('/');
+            path.push_str(token);
+        }
+        path
+    }
+}
+
+/// Parses the array or object at the start of `input`, which is nested at
+/// level `depth`.
+///
+/// The containers nested inside it are kept on an explicit stack instead of
+/// being parsed recursively, so the nesting depth is bounded only by
+/// [`ParseOptions::max_depth`] and not by the size of the call stack.
+fn parse_container<'a, B: Builder<'a>>(
+    input: &'a str,
+    options: &ParseOptions,
+    depth: usize,
+    builder: &mut B,
+) -> Res<'a, B::Value> {
+    let mut stack = Stack {
+        frames: Vec::new(),
+        keys: Vec::new(),
+        values: Vec::new(),
+        starts: Vec::new(),
+        elements: 0,
+        decoded: String::new(),
+        builder,
+    };
+    let (mut input, _) = stack.open(input, options, depth)?;
+    // Whether the innermost container was just opened, and so may be closed
+    // without a trailing comma.
+    let mut first = true;
+    // The last comma, once there has been one.
+    let mut comma = input;
+    loop {
+        // `input` follows the opener or a comma of the innermost container.
+        let (rest, _) = skip_ws_in(input, options, stack.builder).map_err(|e| stack.error(e))?;
+        let frame = *stack.frames.last().unwrap();
+        // The element parsed, or `None` if the builder recovered from it
+        // being malformed.
+        let mut value =
+            if rest.starts_with(frame.close()) && (first || options.allow_trailing_comma) {
+                if !first {
+                    stack.builder.trailing_comma(comma);
+                }
+                input = &rest[1..];
+                Some(stack.close(options, input)?)
+            } else {
+                stack.elements += 1;
+                if stack.elements > options.max_total_elements {
+                    let error = Error::new(rest, ErrorKind::TooManyElements);
+                    return Err(stack.error(Err::Failure(error)));
+                }
+                // Where the value of the element starts, after the key of a
+                // member.
+                let at = match frame {
+                    _ if rest.is_empty() => {
+                        let expected: &[_] = match frame {
+                            Frame::Array(_) if first => VALUE_TOKENS_OR_CLOSE,
+                            Frame::Array(_) => VALUE_TOKENS,
+                            Frame::Object(..) if first => &["string", "}"],
+                            Frame::Object(..) => &["string"],
+                        };
+                        input = stack.recover(stack.unclosed(expected), rest)?;
+                        None
+                    }
+                    Frame::Array(_) if !first && rest.starts_with([',', frame.close()]) => {
+                        let mut error = Error::new(rest, ErrorKind::ExpectedValue);
+                        error.expected = VALUE_TOKENS;
+                        input = stack.recover(Err::Failure(error), rest)?;
+                        None
+                    }
+                    Frame::Array(_) => Some(rest),
+                    Frame::Object(..) => match stack.key(rest, options) {
+                        Ok((after, (key, end))) => {
+                            // What is skipped between the key and the colon.
+                            let gap = end.len() - after.len() - 1;
+                            if options.allow_comments && gap > 0 {
+                                stack.builder.comments(end, gap);
+                            }
+                            check_length(rest, options, &key).map_err(|e| stack.error(e))?;
+                            stack.builder.key(&key);
+                            stack.keys.push((rest, key));
+                            let (after, _) = skip_ws_in(after, options, stack.builder)
+                                .map_err(|e| stack.error(e))?;
+                            Some(after)
+                        }
+                        Err(e) => {
+                            let expected: &[_] = if first { &["string", "}"] } else { &["string"] };
+                            input = stack.recover(expecting(e, rest, expected), rest)?;
+                            None
+                        }
+                    },
+                };
+                match at {
+                    None => None,
+                    Some(at) if at.starts_with(['[', '{']) => {
+                        let (after, _) = stack.open(at, options, depth + stack.frames.len())?;
+                        input = after;
+                        first = true;
+                        continue;
+                    }
+                    Some("") => {
+                        let resumed = stack.recover(stack.unclosed(VALUE_TOKENS), "");
+                        stack.keys.pop();
+                        input = resumed?;
+                        None
+                    }
+                    Some(at) => match parse_hooked_number(at, options, stack.builder) {
+                        Some((after, value)) => {
+                            input = after;
+                            stack.builder.span(at, after);
+                            Some(value)
+                        }
+                        None => match stack.scalar(at, options) {
+                            Ok((after, value)) => {
+                                input = after;
+                                stack.builder.span(at, after);
+                                Some(value)
+                            }
+                            Err(e) => {
+                                let expected = match frame {
+                                    Frame::Array(_) if first => VALUE_TOKENS_OR_CLOSE,
+                                    _ => VALUE_TOKENS,
+                                };
+                                let resumed = stack.recover(expecting(e, at, expected), rest);
+                                if let Frame::Object(..) = frame {
+                                    stack.keys.pop();
+                                }
+                                input = resumed?;
+                                None
+                            }
+                        },
+                    },
+                }
+            };
+        // Add `value` to the innermost container, closing each container
+        // that ends after it, until one continues with a comma.
+        loop {
+            if let Some(value) = value {
+                if stack.frames.is_empty() {
+                    return Ok((input, value));
+                }
+                stack.values.push(value);
+            }
+            let (mut rest, _) =
+                skip_ws_in(input, options, stack.builder).map_err(|e| stack.error(e))?;
+            let close = stack.frames.last().unwrap().close();
+            if rest.is_empty() {
+                let expected: &[_] = if close == ']' {
+                    &[",", "]"]
+                } else {
+                    &[",", "}"]
+                };
+                rest = stack.recover(stack.unclosed(expected), rest)?;
+            } else if !rest.starts_with([',', close]) {
+                let mut error = Error::new(rest, ErrorKind::ExpectedSeparator(close));
+                error.expected = if close == ']' {
+                    &[",", "]"]
+                } else {
+                    &[",", "}"]
+                };
+                rest = stack.recover(Err::Failure(error), rest)?;
+            }
+            if let Some(after) = rest.strip_prefix(',') {
+                comma = rest;
+                input = after;
+                first = false;
+                break;
+            }
+            // Once recovered, a container left open by malformed input ends
+            // where its parent continues.
+            input = rest.strip_prefix(close).unwrap_or(rest);
+            value = Some(stack.close(options, input)?);
+        }
+    }
+}
+
+/// Parses an unquoted object key: a letter, `_` or `$`, then any letters,
+/// digits, `_` and `$`.
+fn parse_identifier(input: &str) -> Res<'_, Cow<'_, str>> {
+    let part = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    map(
+        recognize(preceded(
+            satisfy(move |c| part(c) && !c.is_numeric()),
+            take_while(part),
+        )),
+        Cow::Borrowed,
+    )(input)
+}
+
+/// Parses an object key, quoted or, if `options` allows it, unquoted.
+fn parse_key_token<'a>(input: &'a str, options: &ParseOptions) -> Res<'a, Cow<'a, str>> {
+    if options.allow_unquoted_keys && !input.starts_with(['"', '\'']) {
+        parse_identifier(input)
+    } else {
+        parse_string_with(input, options)
+    }
+}
+
+/// Parses an object key and the colon after it, returning the key and the
+/// input following it.
+///
+/// A quoted key containing escapes is decoded into `decoded` and made a key
+/// by `builder`.
+fn parse_key<'a, B: Builder<'a>>(
+    input: &'a str,
+    options: &ParseOptions,
+    builder: &mut B,
+    decoded: &mut String,
+) -> Res<'a, (Cow<'a, str>, &'a str)> {
+    let (rest, key) = if options.allow_unquoted_keys && !input.starts_with(['"', '\'']) {
+        parse_identifier(input)?
+    } else {
+        let (rest, literal) = parse_string_into(input, options, decoded)?;
+        let key = match literal {
+            Some(literal) => Cow::Borrowed(literal),
+            None => builder.decoded_key(decoded),
+        };
+        (rest, key)
+    };
+    let (after, _) = preceded(|i| skip_ws(i, options), char(':'))(rest)?;
+    Ok((after, (key, rest)))
+}
+
+/// Inserts `members` into `object`, gathering the values of each key that
+/// appears more than once into an array, in the order they appeared.
+fn collect_members<'a, B: Builder<'a>>(
+    builder: &mut B,
+    object: &mut B::Object,
+    members: impl Iterator<Item = ((&'a str, Cow<'a, str>), B::Value)>,
+) {
+    let mut index: MapImpl<Cow<'a, str>, usize> = map_with_capacity(0);
+    let mut groups: Vec<(Cow<'a, str>, Vec<B::Value>)> = Vec::new();
+    for ((at, key), value) in members {
+        match index.get(&key) {
+            Some(&i) => {
+                builder.duplicate_key(at, &key);
+                groups[i].1.push(value);
+            }
+            None => {
+                index.insert(key.clone(), groups.len());
+                groups.push((key, Vec::from([value])));
+            }
+        }
+    }
+    for (key, mut values) in groups {
+        let value = if values.len() > 1 {
+            builder.array(values)
+        } else {
+            values.remove(0)
+        };
+        builder.insert(object, key, value);
+    }
+}
+
+/// Skips the malformed element at the start of `input`, up to the comma or
+/// closing bracket that follows it outside any nested container or string.
+fn skip_element(input: &str) -> &str {
+    let mut depth = 0;
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                let mut escaped = false;
+                loop {
+                    match chars.next() {
+                        None => return &input[input.len()..],
+                        Some(_) if escaped => escaped = false,
+                        Some((_, '\\')) => escaped = true,
+                        Some((_, '"')) => break,
+                        Some(_) => {}
+                    }
+                }
+            }
+            '[' | '{' => depth += 1,
+            ']' | '}' | ',' if depth == 0 => return &input[i..],
+            ']' | '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    &input[input.len()..]
+}
+
+/// Parses a value other than an array or an object.
+fn parse_scalar<'a>(input: &'a str, options: &ParseOptions) -> Res<'a, Scalar<'a>> {
+    if options.allow_hex_numbers {
+        match parse_hex(input, options) {
+            Err(Err::Error(_)) => {}
+            result => return result,
+        }
+    }
+    if options.relaxed_numbers {
+        match parse_relaxed_number(input) {
+            Err(Err::Error(_)) => {}
+            result => return result,
+        }
+    }
+    if options.raw_numbers || options.reject_imprecise_integers {
+        if let Ok((rest, token)) = number_literal(input) {
+            if options.reject_imprecise_integers && is_imprecise_integer(token) {
+                return Err(Err::Failure(Error::new(input, ErrorKind::ImpreciseInteger)));
+            }
+            let scalar = if options.raw_numbers {
+                Scalar::Raw(token)
+            } else {
+                finite_number(input, token)?
+            };
+            return Ok((rest, scalar));
+        }
+    }
+    let (rest, scalar) = alt((
+        |i| parse_non_finite(i, options),
+        parse_number,
+        map(|i| parse_string_with(i, options), Scalar::Str),
+        map(parse_bool, Scalar::Bool),
+        parse_null,
+    ))(input)?;
+    if let Scalar::Str(s) = &scalar {
+        check_length(input, options, s)?;
+    }
+    Ok((rest, scalar))
+}
+
+/// Parses a value other than an array or an object like [`parse_scalar`],
+/// building it with `builder`.
+///
+/// A string containing escapes is decoded into `decoded` and built by
+/// [`Builder::decoded_string`].
+fn parse_scalar_in<'a, B: Builder<'a>>(
+    input: &'a str,
+    options: &ParseOptions,
+    builder: &mut B,
+    decoded: &mut String,
+) -> Res<'a, B::Value> {
+    if !(input.starts_with('"') || options.allow_single_quotes && input.starts_with('\'')) {
+        let (rest, scalar) = parse_scalar(input, options)?;
+        return Ok((rest, builder.scalar(scalar)));
+    }
+    let (rest, literal) = parse_string_into(input, options, decoded)?;
+    check_length(input, options, literal.unwrap_or(decoded))?;
+    let value = match literal {
+        Some(literal) => builder.string(Cow::Borrowed(literal)),
+        None => builder.decoded_string(decoded),
+    };
+    Ok((rest, value))
+}
+
+/// Builds the number at the start of `input` with the value
+/// `options.number_hook` returns for it, if there is a hook and both it and
+/// `builder` take the number.
+fn parse_hooked_number<'a, B: Builder<'a>>(
+    input: &'a str,
+    options: &ParseOptions,
+    builder: &mut B,
+) -> Option<(&'a str, B::Value)> {
+    let hook = options.number_hook.as_ref()?;
+    let (rest, token) = number_literal(input).ok()?;
+    let value = builder.hooked_number(hook.call(token)?)?;
+    Some((rest, value))
+}
+
+/// Parses a value nested inside `depth` arrays and objects.
+fn parse_value<'a, B: Builder<'a>>(
+    input: &'a str,
+    options: &ParseOptions,
+    depth: usize,
+    builder: &mut B,
+) -> Res<'a, B::Value> {
+    context("parse", |input| {
+        let (input, _) = skip_ws_in(input, options, builder)?;
+        if input.is_empty() {
+            let mut error = Error::new(input, ErrorKind::EmptyInput);
+            error.expected = VALUE_TOKENS;
+            return Err(Err::Error(error));
+        }
+        let (rest, value) = if input.starts_with(['[', '{']) {
+            parse_container(input, options, depth + 1, builder)?
+        } else {
+            let (rest, value) = match parse_hooked_number(input, options, builder) {
+                Some(hooked) => hooked,
+                None => parse_scalar_in(input, options, builder, &mut String::new())
+                    .map_err(|e| expecting(e, input, VALUE_TOKENS))?,
+            };
+            builder.span(input, rest);
+            (rest, value)
+        };
+        let (rest, _) = skip_ws_in(rest, options, builder)?;
+        Ok((rest, value))
+    })(input)
+}
+
+/// Parses `input` as a whole JSON document, reporting failures as a
+/// [`ParseError`] with a line and column.
+///
+/// Anything other than whitespace after the top-level value is rejected; see
+/// [`parse_prefix`] or [`parse_with_rest`] to parse a value followed by other
+/// input, and [`combinators`] to parse values within a `nom` grammar.
+///
+/// This and the other parsing functions never panic: any input, however
+/// malformed or deeply nested, yields either a value or an error.
+pub fn parse(input: &str) -> Result<JsonValue, ParseError> {
+    parse_complete(input).map_err(|e| ParseError::new(input, e))
+}
+
+/// Parses `input` as a whole JSON document like [`parse`], reporting
+/// failures as the `nom` error.
+pub fn parse_complete(input: &str) -> Result<JsonValue, Err<Error<'_>>> {
+    parse_with_options(input, &ParseOptions::default())
+}
+
+/// Parses `input` as a whole JSON document, like [`parse_complete`], with
+/// the extensions and restrictions selected by `options`.
+pub fn parse_with_options<'a>(
+    input: &'a str,
+    options: &ParseOptions,
+) -> Result<JsonValue, Err<Error<'a>>> {
+    parse_document_with(input, options, &mut ValueBuilder)
+}
+
+/// Parses `input` as a whole JSON document with `builder`, applying the
+/// document-level checks of `options`.
+fn parse_document_with<'a, B: Builder<'a>>(
+    input: &'a str,
+    options: &ParseOptions,
+    builder: &mut B,
+) -> Result<B::Value, Err<Error<'a>>> {
+    if input.len() > options.max_input_bytes {
+        return Err(Err::Failure(Error::new(input, ErrorKind::InputTooLarge)));
+    }
+    let input = match input.strip_prefix('\u{FEFF}') {
+        Some(rest) if options.strip_bom => rest,
+        _ => input,
+    };
+    if options.require_container_root {
+        let (rest, _) = skip_ws(input, options)?;
+        if !rest.starts_with(['[', '{']) {
+            let error = Error::new(rest, ErrorKind::ContainerRootRequired);
+            return Err(Err::Failure(error));
+        }
+    }
+    let (_, result) = all_consuming(|i| parse_value(i, options, 0, builder))(input)?;
+    Ok(result)
+}
+
+/// Parses `input` as a whole JSON document. The same as [`parse`].
+pub fn parse_document(input: &str) -> Result<JsonValue, ParseError> {
+    parse(input)
+}
+
+/// Parses UTF-8 encoded `input` as a whole JSON document like
+/// [`parse_document`], failing with [`ErrorKind::InvalidUtf8`] at the offset
+/// of the first invalid byte sequence.
+pub fn parse_bytes(input: &[u8]) -> Result<JsonValue, ParseError> {
+    match core::str::from_utf8(input) {
+        Ok(input) => parse_document(input),
+        Err(e) => {
+            let valid = core::str::from_utf8(&input[..e.valid_up_to()]).unwrap();
+            Err(ParseError::at(valid, valid.len(), ErrorKind::InvalidUtf8))
+        }
+    }
+}
+
+/// Parses the JSON value at the start of `input`, returning it with the
+/// number of bytes it took, for reading values one at a time from a buffer.
+///
+/// Whitespace before the value is consumed while whitespace after it is not,
+/// so `input[consumed..]` starts right after the value. Any input may follow
+/// the value.
+pub fn parse_prefix(input: &str) -> Result<(JsonValue, usize), ParseError> {
+    match combinators::parse_value(input) {
+        Ok((rest, value)) => {
+            let consumed = &input[..input.len() - rest.len()];
+            Ok((value, consumed.trim_end_matches(JSON_WHITESPACE).len()))
+        }
+        Err(e) => Err(ParseError::new(input, e)),
+    }
+}
+
+/// Parses the JSON value at the start of `input` like [`parse_prefix`],
+/// returning it with the rest of `input`, for handing whatever follows the
+/// value to another parser.
+///
+/// The rest starts right after the value, before any whitespace following
+/// it, so `[1] # comment` leaves `" # comment"`.
+pub fn parse_with_rest(input: &str) -> Result<(JsonValue, &str), ParseError> {
+    parse_prefix(input).map(|(value, consumed)| (value, &input[consumed..]))
+}
+
+/// Rewrites the JSON document `input` without any insignificant whitespace.
+///
+/// Numbers are kept as written, as with [`ParseOptions::raw_numbers`], while
+/// strings are re-escaped minimally.
+pub fn minify(input: &str) -> Result<String, ParseError> {
+    minify_raw(input).map(|value| value.to_string())
+}
+
+/// Rewrites the JSON document `input` like [`minify`], also sorting the
+/// members of every object by key as
+/// [`to_string_canonical`](JsonValue::to_string_canonical) does.
+pub fn minify_sorted(input: &str) -> Result<String, ParseError> {
+    minify_raw(input).map(|value| value.to_string_canonical())
+}
+
+/// Parses `input` for [`minify`], keeping numbers as written.
+fn minify_raw(input: &str) -> Result<JsonValue, ParseError> {
+    let options = ParseOptions {
+        raw_numbers: true,
+        ..ParseOptions::default()
+    };
+    parse_with_options(input, &options).map_err(|e| ParseError::new(input, e))
+}
+
+/// Decodes the escapes in `s`, the inside of a JSON string without its
+/// quotes, as parsing the string would.
+///
+/// A quote, control character or backslash that cannot appear there
+/// unescaped fails with [`ErrorKind::UnexpectedChar`] located at it.
+/// [`escape_string`] reverses this.
+pub fn unescape_string(s: &str) -> Result<String, ParseError> {
+    let mut unescaped = String::new();
+    match unescape_into(s, &mut unescaped, '"', parse_escape, parse_unicode_escape) {
+        Ok(("", _)) => Ok(unescaped),
+        Ok((rest, _)) => {
+            let c = rest.chars().next().unwrap();
+            Err(ParseError::at(
+                s,
+                s.len() - rest.len(),
+                ErrorKind::UnexpectedChar(c),
+            ))
+        }
+        Err(e) => Err(ParseError::new(s, e)),
+    }
+}
+
+/// Parses each non-blank line of `input` as a separate JSON document, as in
+/// JSON Lines / NDJSON.
+///
+/// Lines may end in `\n`, `\r\n` or a lone `\r`, mixed within one input, and
+/// the last line need not end at all.
+///
+/// A malformed record yields an error located within the whole of `input`
+/// and does not stop the records after it from being parsed.
+pub fn parse_lines(input: &str) -> impl Iterator<Item = Result<JsonValue, ParseError>> + '_ {
+    input
+        .split(&['\n', '\r'][..])
+        .filter(|line| !line.trim().is_empty())
+        .map(move |line| {
+            let end = line.as_ptr() as usize - input.as_ptr() as usize + line.len();
+            parse_complete(line).map_err(|e| ParseError::new(&input[..end], e))
+        })
+}
+
+/// Parses the JSON values concatenated in `input`, one after another, with or
+/// without whitespace between them.
+///
+/// Values merely written back to back must each be unambiguous where it
+/// ends, so only a number or keyword followed by another number or keyword
+/// needs whitespace after it. The first malformed value yields an error
+/// located within the whole of `input`, and no values follow it.
+pub fn parse_many(input: &str) -> impl Iterator<Item = Result<JsonValue, ParseError>> + '_ {
+    let mut rest = Some(input);
+    core::iter::from_fn(move || {
+        let result =
+            skip_ws(rest?, &ParseOptions::default()).and_then(|(remaining, _)| match remaining {
+                "" => Ok(None),
+                _ => combinators::parse_value(remaining).map(Some),
+            });
+        match result {
+            Ok(None) => {
+                rest = None;
+                None
+            }
+            Ok(Some((after, value))) => {
+                rest = Some(after);
+                Some(Ok(value))
+            }
+            Err(e) => {
+                rest = None;
+                Some(Err(ParseError::new(input, e)))
+            }
+        }
+    })
+}
+
+/// Parses `input` as a whole JSON document, failing with
+/// [`ErrorKind::DepthLimitExceeded`] instead of recursing into arrays and
+/// objects nested more than `max_depth` levels deep.
+pub fn parse_with_limit(input: &str, max_depth: usize) -> Result<JsonValue, Err<Error<'_>>> {
+    let options = ParseOptions {
+        max_depth,
+        ..Default::default()
+    };
+    parse_with_options(input, &options)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Unwraps a failure into where and why parsing stopped.
+    fn failure(err: Err<Error<'_>>) -> (&str, ErrorKind) {
+        match err {
+            Err::Failure(e) => (e.input, e.kind),
+            _ => panic!("expected a failure, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_stirng() {
+        assert_eq!(
+            parse_string(r#""1234 asdf" jjjj"#),
+            Ok((" jjjj", "1234 asdf".into()))
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(parse_string(r#""a\"b""#), Ok(("", "a\"b".into())));
+        assert_eq!(
+            parse_string(r#""line1\nline2""#),
+            Ok(("", "line1\nline2".into()))
+        );
+        assert_eq!(
+            parse_string(r#""\\\/\b\f\r\t""#),
+            Ok(("", "\\/\u{08}\u{0C}\r\t".into()))
+        );
+        assert!(parse_string(r#""abc\"#).is_err());
+        assert!(parse_string(r#""\x""#).is_err());
+    }
+
+    #[test]
+    fn test_unicode_escape() {
+        assert_eq!(parse_string(r#""caf\u00e9""#), Ok(("", "café".into())));
+        assert_eq!(
+            parse_string(r#""\uD83D\uDE00""#),
+            Ok(("", "\u{1F600}".into()))
+        );
+        assert!(parse_string(r#""\uD83D""#).is_err());
+        assert!(parse_string(r#""\uD83D\u0041""#).is_err());
+        assert!(parse_string(r#""\uDE00""#).is_err());
+        assert!(parse_string(r#""\u00e""#).is_err());
+    }
+
+    #[test]
+    fn test_lone_surrogates() {
+        assert!(parse_complete(r#""\uD800""#).is_err());
+        let replace = ParseOptions {
+            on_invalid_surrogate: SurrogatePolicy::Replace,
+            ..Default::default()
+        };
+        let parse = |input| parse_with_options(input, &replace);
+        assert_eq!(parse(r#""\uD800""#), Ok(JsonValue::from("\u{FFFD}")));
+        assert_eq!(
+            parse(r#"{"\uDE00a": "\uD83D\u0041\uD83D\uDE00"}"#),
+            parse_complete(r#"{"\uFFFDa": "\uFFFDA\uD83D\uDE00"}"#)
+        );
+        assert_eq!(
+            parse(r#""\uD800\uD800\uDC00""#),
+            Ok(JsonValue::from("\u{FFFD}\u{10000}"))
+        );
+        assert!(parse(r#""\uD800\u00""#).is_err());
+        let single = ParseOptions {
+            allow_single_quotes: true,
+            lenient_escapes: true,
+            ..replace
+        };
+        assert_eq!(
+            parse_with_options(r"'\uDBFF\q'", &single),
+            Ok(JsonValue::from("\u{FFFD}q"))
+        );
+    }
+
+    #[test]
+    fn test_lenient_escapes() {
+        let options = ParseOptions {
+            lenient_escapes: true,
+            ..Default::default()
+        };
+        let input = r#"{"it\'s": "\'\a\%\n\u0041"}"#;
+        assert_eq!(
+            parse_with_options(input, &options).unwrap()["it's"],
+            JsonValue::from("'a%\nA")
+        );
+        assert!(parse_complete(r#""\'""#).is_err());
+        assert!(parse_complete(input).is_err());
+        assert!(parse_with_options(r#""\u12""#, &options).is_err());
+        assert!(parse_with_options(r#""\"#, &options).is_err());
+    }
+
+    #[test]
+    fn test_unquoted_keys() {
+        let options = ParseOptions {
+            allow_unquoted_keys: true,
+            ..Default::default()
+        };
+        let value = parse_with_options(r#"{name: "x", "quoted key": 1, _id$2 : true}"#, &options);
+        assert_eq!(
+            value.unwrap(),
+            parse_complete(r#"{"name": "x", "quoted key": 1, "_id$2": true}"#).unwrap()
+        );
+        assert!(parse_with_options("{$: [], é: 1}", &options).is_ok());
+        let error = parse_with_options("{na-me: 1}", &options).unwrap_err();
+        assert_eq!(failure(error), ("-me: 1}", ErrorKind::Char(':')));
+        assert!(parse_with_options("{2a: 1}", &options).is_err());
+        assert!(parse_with_options("{a b: 1}", &options).is_err());
+        assert!(parse_complete("{name: 1}").is_err());
+    }
+
+    #[test]
+    fn test_json5() {
+        let input = r#"// Server settings
+{
+    name: 'api',
+    "port": 8080, /* overridable */
+    hosts: ['a.example', "b.example",],
+    retry: {limit: 3, backoff: Infinity},
+    ratio: NaN,
+}
+"#;
+        let value = parse_with_options(input, &ParseOptions::json5()).unwrap();
+        let expected = parse_complete(
+            r#"{"name": "api", "port": 8080, "hosts": ["a.example", "b.example"],
+                "retry": {"limit": 3, "backoff": 0}, "ratio": 0}"#,
+        )
+        .unwrap();
+        let (value, expected) = match (value, expected) {
+            (JsonValue::Object(v), JsonValue::Object(e)) => (v, e),
+            _ => panic!("expected objects"),
+        };
+        assert_eq!(value.len(), expected.len());
+        assert!(expected.keys().all(|k| value.contains_key(k)));
+        assert_eq!(value["hosts"], expected["hosts"]);
+        assert_eq!(value["retry"]["backoff"], JsonValue::Number(f64::INFINITY));
+        assert!(matches!(value["ratio"], JsonValue::Number(f) if f.is_nan()));
+
+        assert!(parse_with_options(input, &ParseOptions::strict()).is_err());
+        assert!(parse_with_options("{a: 0x10}", &ParseOptions::json5()).is_err());
+    }
+
+    #[test]
+    fn test_hex_numbers() {
+        let options = ParseOptions {
+            allow_hex_numbers: true,
+            ..Default::default()
+        };
+        let parse_hex =
+            |input| parse_with_options(input, &options).map_err(|e| ParseError::new(input, e));
+        assert_eq!(parse_hex("0xFF"), Ok(JsonValue::Int(255)));
+        assert_eq!(parse_hex("0X10"), Ok(JsonValue::Int(16)));
+        assert_eq!(
+            parse_hex("[-0xa, 0x7fffffffffffffff, 1.5]"),
+            parse("[-10, 9223372036854775807, 1.5]")
+        );
+        assert_eq!(
+            parse_hex("-0x8000000000000000"),
+            Ok(JsonValue::Int(i64::MIN))
+        );
+
+        let error = parse_hex("0x").unwrap_err();
+        assert_eq!(
+            (error.offset, &error.kind),
+            (0, &ErrorKind::InvalidNumber("0x".into()))
+        );
+        assert_eq!(
+            parse_hex("[0x]").unwrap_err().kind,
+            ErrorKind::InvalidNumber("0x".into())
+        );
+        assert_eq!(
+            parse_hex("0xFG").unwrap_err().kind,
+            ErrorKind::InvalidNumber("0xFG".into())
+        );
+        assert_eq!(
+            parse_hex("0x8000000000000000").unwrap_err().kind,
+            ErrorKind::NumberOutOfRange
+        );
+        let raw = ParseOptions {
+            raw_numbers: true,
+            ..options
+        };
+        assert_eq!(
+            parse_with_options("[0x1F, 2]", &raw),
+            Ok(JsonValue::Array(vec![
+                JsonValue::Int(31),
+                JsonValue::RawNumber("2".into())
+            ]))
+        );
+
+        assert!(parse("0xFF").is_err());
+        assert!(parse_with_options("0xFF", &ParseOptions::json5()).is_err());
+    }
+
+    #[test]
+    fn test_relaxed_numbers() {
+        let options = ParseOptions {
+            relaxed_numbers: true,
+            ..Default::default()
+        };
+        let parse_relaxed =
+            |input| parse_with_options(input, &options).map_err(|e| ParseError::new(input, e));
+        assert_eq!(parse_relaxed("+1.5"), Ok(JsonValue::Number(1.5)));
+        assert_eq!(parse_relaxed(".5"), Ok(JsonValue::Number(0.5)));
+        assert_eq!(parse_relaxed("5."), Ok(JsonValue::Number(5.)));
+        assert_eq!(
+            parse_relaxed("[+1, -.5, +.25e1, 5.E2, 1.5, -2, +9223372036854775807]"),
+            parse("[1, -0.5, 2.5, 500.0, 1.5, -2, 9223372036854775807]")
+        );
+        assert_eq!(
+            parse_relaxed(r#"{"a": +0}"#),
+            Ok(parse(r#"{"a": 0}"#).unwrap())
+        );
+
+        for input in [
+            "+", ".", "+.", "-.", ".e1", "++1", "+-1", "1..5", "+01", ".5.", "+1e",
+        ] {
+            let error = parse_relaxed(input).unwrap_err();
+            assert_eq!(
+                (error.offset, &error.kind),
+                (0, &ErrorKind::InvalidNumber(input.into())),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            parse_relaxed("+1e400").unwrap_err().kind,
+            ErrorKind::NumberOutOfRange
+        );
+        assert_eq!(
+            parse_relaxed(".5e400").unwrap_err().kind,
+            ErrorKind::NumberOutOfRange
+        );
+
+        let raw = ParseOptions {
+            raw_numbers: true,
+            ..options
+        };
+        assert_eq!(
+            parse_with_options("[+1, .5, 2]", &raw),
+            Ok(JsonValue::Array(vec![
+                JsonValue::Int(1),
+                JsonValue::Number(0.5),
+                JsonValue::RawNumber("2".into())
+            ]))
+        );
+
+        for input in ["+1.5", ".5", "5.", "[+1]"] {
+            assert!(parse(input).is_err(), "{}", input);
+        }
+        assert_eq!(
+            parse("5.").unwrap_err().kind,
+            ErrorKind::InvalidNumber("5.".into())
+        );
+    }
+
+    #[test]
+    fn test_single_quotes() {
+        let options = ParseOptions {
+            allow_single_quotes: true,
+            ..Default::default()
+        };
+        let value = parse_with_options(
+            r#"{'key': 'hello', "b": ['say "hi"', 'it\'s', "it's", '\u0041\n']}"#,
+            &options,
+        )
+        .unwrap();
+        assert_eq!(value["key"], JsonValue::from("hello"));
+        assert_eq!(
+            value["b"],
+            parse_complete(r#"["say \"hi\"", "it's", "it's", "A\n"]"#).unwrap()
+        );
+        assert!(parse_with_options("'a\"'", &options).is_ok());
+        assert!(parse_with_options("'open", &options).is_err());
+        assert!(parse_with_options(r#""a\'""#, &options).is_err());
+        assert!(parse_complete("'hello'").is_err());
+        assert!(parse_complete(r#"{'a': 1}"#).is_err());
+    }
+
+    #[test]
+    fn test_control_character() {
+        assert_eq!(
+            failure(parse_string("\"line1\nline2\"").unwrap_err()),
+            ("\nline2\"", ErrorKind::Char('"'))
+        );
+        assert!(parse_string("\"a\tb\"").is_err());
+        assert!(parse_string("\"\u{0}\"").is_err());
+        assert_eq!(
+            parse_string(r#""line1\nline2""#),
+            Ok(("", "line1\nline2".into()))
+        );
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn test_number() {
+        assert_eq!(parse_number("42"), Ok(("", Scalar::Int(42))));
+        assert_eq!(parse_number("-7"), Ok(("", Scalar::Int(-7))));
+        assert_eq!(parse_number("3.14"), Ok(("", Scalar::Float(3.14))));
+        assert_eq!(parse_number("1e5"), Ok(("", Scalar::Float(1e5))));
+        assert_eq!(
+            parse_number("9007199254740993"),
+            Ok(("", Scalar::Int(9007199254740993)))
+        );
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(
+            parse_number("99999999999999999999"),
+            Ok(("", Scalar::Float(1e20)))
+        );
+        assert_eq!(parse_number("-0.5"), Ok(("", Scalar::Float(-0.5))));
+        assert_eq!(parse_number("0e-2,"), Ok((",", Scalar::Float(0.0))));
+        assert_eq!(
+            failure(parse_number("01").unwrap_err()),
+            ("01", ErrorKind::InvalidNumber("01".to_string()))
+        );
+        assert!(parse_complete("[-01]").is_err());
+        assert!(parse_complete("+1").is_err());
+        assert!(parse_complete(".5").is_err());
+        assert!(parse_complete("1.").is_err());
+        assert!(parse_complete("1e").is_err());
+        assert!(parse_complete("-").is_err());
+    }
+
+    #[test]
+    fn test_number_limits() {
+        let zero = parse("-0").unwrap();
+        assert!(matches!(zero, JsonValue::Number(f) if f == 0. && f.is_sign_negative()));
+        assert_eq!(zero.to_string(), "-0.0");
+        assert!(matches!(parse("-0.0"), Ok(JsonValue::Number(f)) if f.is_sign_negative()));
+        assert_eq!(parse("0"), Ok(JsonValue::Int(0)));
+
+        assert_eq!(parse("1e-400"), Ok(JsonValue::Number(0.)));
+        assert!(matches!(parse("-1e-400"), Ok(JsonValue::Number(f)) if f.is_sign_negative()));
+
+        let error = parse("[1, 1e400]").unwrap_err();
+        assert_eq!(
+            (error.offset, &error.kind),
+            (4, &ErrorKind::NumberOutOfRange)
+        );
+        assert_eq!(
+            parse("-1e400").unwrap_err().kind,
+            ErrorKind::NumberOutOfRange
+        );
+        assert_eq!(
+            parse("1.8e308").unwrap_err().kind,
+            ErrorKind::NumberOutOfRange
+        );
+        assert_eq!(parse("1.7e308"), Ok(JsonValue::Number(1.7e308)));
+        #[cfg(not(feature = "bigint"))]
+        assert_eq!(
+            parse(&"9".repeat(400)).unwrap_err().kind,
+            ErrorKind::NumberOutOfRange
+        );
+        let raw = ParseOptions {
+            raw_numbers: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_with_options("1e400", &raw),
+            Ok(JsonValue::RawNumber("1e400".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_invalid_number() {
+        let invalid = |input: &str| {
+            let error = parse(input).unwrap_err();
+            match error.kind {
+                ErrorKind::InvalidNumber(token) => (error.offset, token),
+                kind => panic!("{:?} for {:?}", kind, input),
+            }
+        };
+        assert_eq!(invalid("1.2.3"), (0, "1.2.3".to_string()));
+        assert_eq!(invalid("1e"), (0, "1e".to_string()));
+        assert_eq!(invalid("--5"), (0, "--5".to_string()));
+        assert_eq!(invalid("[1, -, 2]"), (4, "-".to_string()));
+        assert_eq!(invalid(r#"{"a": 1e+-2}"#), (6, "1e+-2".to_string()));
+        assert_eq!(invalid("1e+"), (0, "1e+".to_string()));
+        assert_eq!(invalid("1e++5"), (0, "1e++5".to_string()));
+        assert_eq!(invalid("1.e5"), (0, "1.e5".to_string()));
+        assert_eq!(invalid("[-.]"), (1, "-.".to_string()));
+        assert_eq!(invalid("-1.-5"), (0, "-1.-5".to_string()));
+        assert_eq!(parse("1.5e+3"), Ok(JsonValue::Number(1500.)));
+        assert_eq!(parse("-0.5E-1"), Ok(JsonValue::Number(-0.05)));
+        let error = parse("[0,\n 1.2.3]").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 2, column 2: invalid number literal '1.2.3' at /1"
+        );
+        let raw = ParseOptions {
+            raw_numbers: true,
+            ..Default::default()
+        };
+        let error = parse_with_options("1.2.3", &raw).unwrap_err();
+        assert_eq!(
+            failure(error).1,
+            ErrorKind::InvalidNumber("1.2.3".to_string())
+        );
+        assert!(matches!(parse("1e5x").unwrap_err().kind, ErrorKind::Nom(_)));
+    }
+
+    #[test]
+    fn test_bool() {
+        assert_eq!(parse_bool("true"), Ok(("", true)));
+        assert_eq!(parse_bool("false]"), Ok(("]", false)));
+        assert!(parse_bool("falseff").is_err());
+        assert!(parse_bool("true_").is_err());
+        assert!(parse_bool("true1").is_err());
+        assert!(parse_complete("[true]").is_ok());
+        assert!(parse_complete("[trueish]").is_err());
+        assert!(parse_complete("{\"a\": nullable}").is_err());
+        for input in ["True", "FALSE", "NULL", "Null", "[tRue]"] {
+            assert!(parse_complete(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_array() {
+        assert_eq!(
+            parse_value("[123.4]", &ParseOptions::default(), 0, &mut ValueBuilder),
+            Ok(("", JsonValue::Array(vec![JsonValue::Number(123.4)])))
+        );
+        assert_eq!(
+            parse_value(
+                r#"[ 333,  "wow"   ,null  ]"#,
+                &ParseOptions::default(),
+                0,
+                &mut ValueBuilder
+            ),
+            Ok((
+                "",
+                JsonValue::Array(vec![
+                    JsonValue::Int(333),
+                    JsonValue::Str("wow".to_string()),
+                    JsonValue::Null
+                ])
+            ))
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        assert_eq!(
+            parse_value(
+                r#"[1, [2, [3], true, "[not an array]"], false]"#,
+                &ParseOptions::default(),
+                0,
+                &mut ValueBuilder
+            ),
+            Ok((
+                "",
+                JsonValue::Array(vec![
+                    JsonValue::Int(1),
+                    JsonValue::Array(vec![
+                        JsonValue::Int(2),
+                        JsonValue::Array(vec![JsonValue::Int(3)]),
+                        JsonValue::Bool(true),
+                        JsonValue::Str("[not an array]".to_string()),
+                    ]),
+                    JsonValue::Bool(false),
+                ])
+            ))
+        )
+    }
+
+    #[test]
+    fn test_complete() {
+        let mut object = Map::default();
+        object.insert("a".to_string(), JsonValue::Int(1));
+        assert_eq!(parse_complete(r#"{"a":1} "#), Ok(JsonValue::Object(object)));
+        assert!(parse_complete(r#"{"a":1}x"#).is_err());
+        assert!(parse_complete("[1,2]]]").is_err());
+        assert!(parse_complete("").is_err());
+    }
+
+    #[test]
+    fn test_small_objects() {
+        let objects: Vec<_> = (0..200)
+            .map(|i| {
+                format!(
+                    r#"{{"id": {}, "ok": true, "tag": null, "s": "{}", "e": {{}}}}"#,
+                    i, i
+                )
+            })
+            .collect();
+        let input = format!("[{}]", objects.join(", "));
+        let expected: Vec<_> = (0..200)
+            .map(|i| {
+                let mut object = Map::default();
+                object.insert("id".to_string(), JsonValue::Int(i));
+                object.insert("ok".to_string(), JsonValue::Bool(true));
+                object.insert("tag".to_string(), JsonValue::Null);
+                object.insert("s".to_string(), JsonValue::Str(i.to_string()));
+                object.insert("e".to_string(), JsonValue::Object(Map::default()));
+                JsonValue::Object(object)
+            })
+            .collect();
+        let expected = JsonValue::Array(expected);
+        assert_eq!(parse_complete(&input), Ok(expected.clone()));
+        for duplicate_keys in [
+            DuplicateKeyPolicy::Error,
+            DuplicateKeyPolicy::FirstWins,
+            DuplicateKeyPolicy::Collect,
+        ] {
+            let options = ParseOptions {
+                duplicate_keys,
+                ..Default::default()
+            };
+            assert_eq!(parse_with_options(&input, &options), Ok(expected.clone()));
+        }
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "preserve_order",
+        not(feature = "map-btree"),
+        not(feature = "map-hash")
+    ))]
+    fn test_object_order() {
+        let value = parse_complete(r#"{"z":1,"a":2}"#).unwrap();
+        assert_eq!(value.to_string(), r#"{"z":1,"a":2}"#);
+    }
+
+    #[test]
+    fn test_duplicate_key() {
+        let input = r#"{"a":1, "a":2}"#;
+        let mut object = Map::default();
+        object.insert("a".to_string(), JsonValue::Int(2));
+        assert_eq!(parse_complete(input), Ok(JsonValue::Object(object)));
+
+        let options = ParseOptions {
+            duplicate_keys: DuplicateKeyPolicy::Error,
+            ..Default::default()
+        };
+        assert_eq!(
+            failure(parse_with_options(input, &options).unwrap_err()),
+            (r#""a":2}"#, ErrorKind::DuplicateKey("a".to_string()))
+        );
+        assert!(parse_with_options(r#"{"a":{"a":1}, "b":[{"a":2}]}"#, &options).is_ok());
+
+        let parse = |input, duplicate_keys| {
+            let options = ParseOptions {
+                duplicate_keys,
+                ..Default::default()
+            };
+            parse_with_options(input, &options).unwrap()
+        };
+        assert_eq!(
+            parse(input, DuplicateKeyPolicy::LastWins),
+            parse_complete(r#"{"a":2}"#).unwrap()
+        );
+        assert_eq!(
+            parse(input, DuplicateKeyPolicy::FirstWins),
+            parse_complete(r#"{"a":1}"#).unwrap()
+        );
+        assert_eq!(
+            parse(input, DuplicateKeyPolicy::Collect),
+            parse_complete(r#"{"a":[1,2]}"#).unwrap()
+        );
+        let value = parse(
+            r#"{"a":[1], "b":2, "a":{"c":3,"c":4}, "a":null}"#,
+            DuplicateKeyPolicy::Collect,
+        );
+        assert_eq!(
+            value,
+            parse_complete(r#"{"a":[[1], {"c":[3,4]}, null], "b":2}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        assert!(parse_with_limit("[[1]]", 2).is_ok());
+        assert!(parse_with_limit(r#"{"a":{"b":1}}"#, 2).is_ok());
+        assert_eq!(
+            failure(parse_with_limit("[[[1]]]", 2).unwrap_err()),
+            ("1]]]", ErrorKind::DepthLimitExceeded)
+        );
+        assert!(parse_with_limit(r#"[{"a":[]}]"#, 2).is_err());
+        assert!(parse_with_limit("1", 0).is_ok());
+
+        let deep = "[".repeat(10_000) + &"]".repeat(10_000);
+        assert!(parse_complete(&deep).is_err());
+        let nested = "[".repeat(128) + &"]".repeat(128);
+        assert!(parse_complete(&nested).is_ok());
+    }
+
+    #[test]
+    fn test_limits() {
+        let input = r#"{"key": ["abc", [1, 2]], "k": "a\"b"}"#;
+        let limits = ParseOptions {
+            max_input_bytes: input.len(),
+            max_total_elements: 6,
+            max_string_length: 3,
+            ..Default::default()
+        };
+        assert!(parse_with_options(input, &limits).is_ok());
+
+        let options = ParseOptions {
+            max_input_bytes: input.len() - 1,
+            ..limits.clone()
+        };
+        let (rest, kind) = failure(parse_with_options(input, &options).unwrap_err());
+        assert_eq!((rest.len(), kind), (input.len(), ErrorKind::InputTooLarge));
+
+        let options = ParseOptions {
+            max_total_elements: 5,
+            ..limits.clone()
+        };
+        let (rest, kind) = failure(parse_with_options(input, &options).unwrap_err());
+        assert_eq!(
+            (rest, kind),
+            (r#""k": "a\"b"}"#, ErrorKind::TooManyElements)
+        );
+
+        let options = ParseOptions {
+            max_string_length: 2,
+            ..limits.clone()
+        };
+        let (rest, kind) = failure(parse_with_options(input, &options).unwrap_err());
+        assert_eq!(
+            (rest.len(), kind),
+            (input.len() - 1, ErrorKind::StringTooLong)
+        );
+        let (rest, kind) = failure(parse_with_options(r#"["abc"]"#, &options).unwrap_err());
+        assert_eq!((rest, kind), (r#""abc"]"#, ErrorKind::StringTooLong));
+    }
+
+    #[test]
+    fn test_deep_nesting() {
+        let depth = 50_000;
+        let input = r#"{"a":["#.repeat(depth) + "1" + &"]}".repeat(depth);
+        let mut value = parse_with_limit(&input, 2 * depth).unwrap();
+        // Take the value apart level by level, since dropping it whole would
+        // recurse as deep as it is nested.
+        let mut levels = 0;
+        while let JsonValue::Object(object) = value {
+            value = match object.into_iter().next() {
+                Some((_, JsonValue::Array(mut items))) => items.pop().unwrap(),
+                _ => panic!("expected an array"),
+            };
+            levels += 1;
+        }
+        assert_eq!((levels, value), (depth, JsonValue::Int(1)));
+
+        assert_eq!(
+            failure(parse_with_limit(&input, 2 * depth - 1).unwrap_err()).1,
+            ErrorKind::DepthLimitExceeded
+        );
+    }
+
+    #[test]
+    fn test_document_error() {
+        let error = parse_document("[1,\n  \"abc").unwrap_err();
+        assert_eq!((error.offset, error.line, error.column), (10, 2, 7));
+        assert_eq!(error.kind, ErrorKind::Char('"'));
+        assert_eq!(error.context, vec!["string", "parse", "array", "parse"]);
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 2, column 7: expected '\"' in string at /1"
+        );
+
+        let error = parse_document("{\"éé\": 1}}").unwrap_err();
+        assert_eq!((error.offset, error.line, error.column), (11, 1, 10));
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 1, column 10: unexpected trailing input"
+        );
+    }
+
+    #[test]
+    fn test_error_context() {
+        let error = parse_document(r#"[{"ok": 1, "bad\q": 2}]"#).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Char('"'));
+        assert_eq!(
+            error.context,
+            ["string", "object", "parse", "array", "parse"]
+        );
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 1, column 16: expected '\"' in string at /0"
+        );
+        let error = parse_document(r#"{"open": 1, "key"#).unwrap_err();
+        assert_eq!(error.context, ["string", "object", "parse"]);
+    }
+
+    #[test]
+    fn test_parse_prefix() {
+        let input = "true false";
+        let (value, consumed) = parse_prefix(input).unwrap();
+        assert_eq!((value, consumed), (JsonValue::Bool(true), 4));
+        assert_eq!(&input[consumed..], " false");
+        let (value, consumed) = parse_prefix(&input[consumed..]).unwrap();
+        assert_eq!((value, consumed), (JsonValue::Bool(false), 6));
+
+        let input = " \n[1, {\"a\": 2}]\n{}x";
+        let (value, consumed) = parse_prefix(input).unwrap();
+        assert_eq!(value, parse_complete("[1, {\"a\": 2}]").unwrap());
+        assert_eq!(&input[consumed..], "\n{}x");
+        assert_eq!(parse_prefix("\"s\"").unwrap().1, 3);
+        assert_eq!(parse_prefix("   ").unwrap_err().kind, ErrorKind::EmptyInput);
+        assert_eq!(parse_prefix("[1, ").unwrap_err().offset, 0);
+    }
+
+    #[test]
+    fn test_parse_with_rest() {
+        assert_eq!(
+            parse_with_rest("[1] # comment"),
+            Ok((parse_complete("[1]").unwrap(), " # comment"))
+        );
+        assert_eq!(
+            parse_with_rest(" {\"a\": null}\n"),
+            Ok((parse_complete("{\"a\": null}").unwrap(), "\n"))
+        );
+        assert_eq!(parse_with_rest("12"), Ok((JsonValue::Int(12), "")));
+        let error = parse_with_rest("[1, # comment").unwrap_err();
+        assert_eq!(error, parse_prefix("[1, # comment").unwrap_err());
+    }
+
+    #[test]
+    fn test_minify() {
+        let input = "{\n  \"b\": [\n    1.50,\n    true ,\t null\n  ],\r\n  \"a\" :  {  }\n}\n";
+        let minified = minify(input).unwrap();
+        assert_eq!(minified.len(), r#"{"b":[1.50,true,null],"a":{}}"#.len());
+        assert_eq!(
+            parse_complete(&minified).unwrap(),
+            parse_complete(input).unwrap()
+        );
+        assert_eq!(
+            minify_sorted(input).unwrap(),
+            r#"{"a":{},"b":[1.50,true,null]}"#
+        );
+        assert_eq!(minify(" \"a b\" ").unwrap(), "\"a b\"");
+        assert_eq!(minify("[1,]").unwrap_err().offset, 3);
+    }
+
+    #[test]
+    fn test_separator_errors() {
+        let error = parse("[1,,2]").unwrap_err();
+        assert_eq!((error.offset, &error.kind), (3, &ErrorKind::ExpectedValue));
+        assert_eq!(error.kind.to_string(), "expected value after ','");
+        assert_eq!(parse("[1, ]").unwrap_err().kind, ErrorKind::ExpectedValue);
+
+        let error = parse("[1 2]").unwrap_err();
+        assert_eq!(
+            (error.offset, &error.kind),
+            (3, &ErrorKind::ExpectedSeparator(']'))
+        );
+        assert_eq!(error.kind.to_string(), "expected ',' or ']'");
+        assert_eq!(error.path, "/1");
+        let error = parse(r#"{"a":1 "b":2}"#).unwrap_err();
+        assert_eq!(
+            (error.offset, &error.kind),
+            (7, &ErrorKind::ExpectedSeparator('}'))
+        );
+        assert_eq!(error.kind.to_string(), "expected ',' or '}'");
+        assert_eq!(
+            parse("[[1}").unwrap_err().kind,
+            ErrorKind::ExpectedSeparator(']')
+        );
+        assert_eq!(parse("[1").unwrap_err().kind, ErrorKind::Unclosed('['));
+
+        // Content after a nested closing bracket is reported where it starts,
+        // as the enclosing container's missing separator.
+        let error = parse(r#"{"a":[1,2]x}"#).unwrap_err();
+        assert_eq!(
+            (error.offset, &error.kind, error.path.as_str()),
+            (10, &ErrorKind::ExpectedSeparator('}'), "")
+        );
+        let error = parse("[[1,2] 3]").unwrap_err();
+        assert_eq!(
+            (error.offset, &error.kind),
+            (7, &ErrorKind::ExpectedSeparator(']'))
+        );
+    }
+
+    #[test]
+    fn test_unclosed() {
+        let error = parse(r#"{"a":1"#).unwrap_err();
+        assert_eq!((error.offset, &error.kind), (0, &ErrorKind::Unclosed('{')));
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 1, column 1: unclosed object in object"
+        );
+        let error = parse("[1, 2").unwrap_err();
+        assert_eq!((error.offset, &error.kind), (0, &ErrorKind::Unclosed('[')));
+        assert_eq!(error.kind.to_string(), "unclosed array");
+
+        // The innermost container left open is named, wherever its value
+        // was cut off.
+        let input = "{\n  \"a\": [1, {\"b\": [";
+        for end in [input.len(), input.len() - 1, input.len() - 6] {
+            let error = parse(&input[..end]).unwrap_err();
+            assert_eq!(
+                error.kind,
+                ErrorKind::Unclosed(if end == input.len() { '[' } else { '{' })
+            );
+        }
+        let error = parse(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 18));
+        assert_eq!(error.path, "/a/1/b/0");
+        let error = parse(&input[..input.find('{').unwrap() + 1]).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Unclosed('{'));
+        let error = parse(&input[..input.find(':').unwrap() + 1]).unwrap_err();
+        assert_eq!((error.offset, &error.kind), (0, &ErrorKind::Unclosed('{')));
+
+        // Elsewhere the input ending is not reported as unclosed.
+        assert_eq!(parse(r#"["abc"#).unwrap_err().kind, ErrorKind::Char('"'));
+        assert_eq!(parse("").unwrap_err().kind, ErrorKind::EmptyInput);
+    }
+
+    #[test]
+    fn test_expected() {
+        let expected = |input| parse(input).unwrap_err().expected;
+        let value = ["string", "number", "object", "array", "bool", "null"];
+        assert_eq!(expected(r#"{"a":"#), value);
+        assert_eq!(expected(r#"{"a": }"#), value);
+        assert_eq!(expected(r#"{"a":1"#), [",", "}"]);
+        assert_eq!(expected("[1 2]"), [",", "]"]);
+        assert_eq!(expected("[1,"), value);
+        assert_eq!(expected("[1,]"), value);
+        assert_eq!(expected("["), [&value[..], &["]"]].concat());
+        assert_eq!(expected("{"), ["string", "}"]);
+        assert_eq!(expected(r#"{"a":1,"#), ["string"]);
+        assert_eq!(expected(r#"{"a""#), [":"]);
+        assert_eq!(expected(""), value);
+        assert_eq!(expected("   x"), value);
+        assert_eq!(expected(r#"["abc"#), ["\""]);
+        assert!(expected("[1] 2").is_empty());
+    }
+
+    #[test]
+    fn test_parse_many() {
+        let values: Vec<_> = parse_many(r#"{"a":1} [1,2] true"#).collect();
+        assert_eq!(
+            values,
+            [
+                parse(r#"{"a":1}"#),
+                parse("[1,2]"),
+                Ok(JsonValue::Bool(true))
+            ]
+        );
+        let values: Vec<_> = parse_many("{\"b\":2}[3]\"s\"null\n1 2\n")
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(values.len(), 6);
+        assert_eq!(values[2], JsonValue::Str("s".to_string()));
+        assert_eq!(values[5], JsonValue::Int(2));
+
+        let results: Vec<_> = parse_many("[1] [2, } [3]").collect();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].as_ref().unwrap_err().offset, 8);
+        assert_eq!(parse_many(" \n ").count(), 0);
+        assert!(parse_many("truefalse").next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let error = parse_document("").unwrap_err();
+        assert_eq!((error.offset, &error.kind), (0, &ErrorKind::EmptyInput));
+        assert_eq!(
+            error.to_string(),
+            "parse error at line 1, column 1: unexpected end of input: expected a JSON value"
+        );
+        let error = parse_document(" \n\t ").unwrap_err();
+        assert_eq!((error.offset, error.line, error.column), (4, 2, 3));
+        assert_eq!(error.kind, ErrorKind::EmptyInput);
+        assert_eq!(parse("  ").unwrap_err().kind, ErrorKind::EmptyInput);
+        assert_ne!(parse_document("[").unwrap_err().kind, ErrorKind::EmptyInput);
+    }
+
+    #[test]
+    fn test_empty_containers() {
+        let comments = ParseOptions {
+            allow_comments: true,
+            ..Default::default()
+        };
+        for (input, expected) in [
+            ("[]", JsonValue::Array(Vec::new())),
+            ("[ ]", JsonValue::Array(Vec::new())),
+            ("[\n\t]", JsonValue::Array(Vec::new())),
+            ("{}", JsonValue::Object(Map::default())),
+            ("{ }", JsonValue::Object(Map::default())),
+            (" {\r\n} ", JsonValue::Object(Map::default())),
+        ] {
+            assert_eq!(parse_complete(input), Ok(expected.clone()), "{:?}", input);
+            assert_eq!(
+                parse_borrowed(input).map(|(_, v)| v.into_owned()),
+                Ok(expected.clone()),
+                "{:?}",
+                input
+            );
+            let commented = input.replacen(' ', " /* c */ ", 1);
+            assert_eq!(
+                parse_with_options(&commented, &comments),
+                Ok(expected),
+                "{:?}",
+                commented
+            );
+        }
+        let nested = parse_complete(r#"{"a":[]}"#).unwrap();
+        assert_eq!(nested["a"], JsonValue::Array(Vec::new()));
+        assert_eq!(parse_complete(r#"{ "a" : [ ] }"#), Ok(nested));
+        let deep = parse_complete("[[ ], { }, [[{}]], {\"b\": {\"c\": [ {} ] }}]").unwrap();
+        assert_eq!(deep[0].len(), Some(0));
+        assert_eq!(deep[1].len(), Some(0));
+        assert_eq!(deep[2][0][0], JsonValue::Object(Map::default()));
+        assert_eq!(deep[3]["b"]["c"][0].len(), Some(0));
+        assert!(parse_complete("[,]").is_err());
+        assert!(parse_complete("{ , }").is_err());
+    }
+
+    #[test]
+    fn test_error_path() {
+        let input = r#"{"users": [{}, {}, {}, {"name": "d", "email": tru}]}"#;
+        let error = parse_document(input).unwrap_err();
+        assert_eq!(error.path, "/users/3/email");
+        assert_eq!(error.offset, input.find("tru").unwrap());
+        assert!(error.to_string().ends_with(" at /users/3/email"));
+
+        let error = parse_document(r#"[0, {"a/b~": [1, 2 3]}]"#).unwrap_err();
+        assert_eq!(error.path, "/1/a~1b~0/2");
+
+        let options = ParseOptions {
+            duplicate_keys: DuplicateKeyPolicy::Error,
+            ..Default::default()
+        };
+        let error = parse_with_options(r#"[{"a": {"b": 1, "b": 2}}]"#, &options).unwrap_err();
+        match error {
+            Err::Failure(e) => assert_eq!(e.path, "/0/a"),
+            _ => panic!("expected a failure"),
+        }
+
+        assert_eq!(parse_document("tru").unwrap_err().path, "");
+    }
+
+    #[test]
+    fn test_bytes() {
+        let value = parse_bytes("[\"é\"]".as_bytes()).unwrap();
+        assert_eq!(
+            value,
+            JsonValue::Array(vec![JsonValue::Str("é".to_string())])
+        );
+
+        let error = parse_bytes(b"[\n\"\xc3\xa9\xc3\x28\"]").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::InvalidUtf8);
+        assert_eq!((error.offset, error.line, error.column), (5, 2, 3));
+    }
+
+    #[test]
+    fn test_unescape_string() {
+        let text = "say \"hi\"\n\tto caf\u{e9} \u{1F600} / \\ \u{1}";
+        let escaped = escape_string(text);
+        assert_eq!(
+            escaped,
+            "say \\\"hi\\\"\\n\\tto caf\u{e9} \u{1F600} / \\\\ \\u0001"
+        );
+        assert_eq!(unescape_string(&escaped).as_deref(), Ok(text));
+        assert_eq!(
+            unescape_string(r#"caf\u00e9 \ud83d\ude00 \/ \b\f\r"#).as_deref(),
+            Ok("caf\u{e9} \u{1F600} / \u{8}\u{c}\r")
+        );
+        assert_eq!(
+            escape_string(&unescape_string(r#"\"\n\u0041"#).unwrap()),
+            r#"\"\nA"#
+        );
+        assert_eq!(unescape_string("").as_deref(), Ok(""));
+
+        for (s, offset, c) in [
+            (r#"a"b"#, 1, '"'),
+            ("a\nb", 1, '\n'),
+            (r#"ab\x"#, 2, '\\'),
+            (r#"\ud800"#, 0, '\\'),
+            (r#"\u12"#, 0, '\\'),
+            ("\\", 0, '\\'),
+        ] {
+            let error = unescape_string(s).unwrap_err();
+            assert_eq!(
+                (error.offset, error.kind),
+                (offset, ErrorKind::UnexpectedChar(c)),
+                "{:?}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut records = parse_lines("{\"a\": 1}\n\n[1,\n  \n\"x\"\n");
+        assert_eq!(records.next(), Some(parse_document(r#"{"a": 1}"#)));
+        let error = records.next().unwrap().unwrap_err();
+        assert_eq!((error.offset, error.line, error.column), (10, 3, 1));
+        assert_eq!(records.next(), Some(Ok(JsonValue::Str("x".to_string()))));
+        assert_eq!(records.next(), None);
+
+        let expected = [
+            parse_document(r#"{"a": 1}"#),
+            Ok(JsonValue::Int(2)),
+            parse_document(r#"["x"]"#),
+        ];
+        for input in [
+            "{\"a\": 1}\r\n2\r\n[\"x\"]\r\n",
+            "{\"a\": 1}\r\n2\n[\"x\"]",
+            "{\"a\": 1}\r2\r\n\r\n[\"x\"]",
+        ] {
+            assert_eq!(
+                parse_lines(input).collect::<Vec<_>>(),
+                expected,
+                "{:?}",
+                input
+            );
+        }
+        let input = "1\r\n[2,\r\n3";
+        let results: Vec<_> = parse_lines(input).collect();
+        assert_eq!(results[0], Ok(JsonValue::Int(1)));
+        let error = results[1].as_ref().unwrap_err();
+        assert_eq!((error.offset, error.line, error.column), (3, 2, 1));
+        assert_eq!(results[2], Ok(JsonValue::Int(3)));
+    }
+
+    #[test]
+    fn test_comments() {
+        let input = "// config\n[1, /* two */ 2 // end\n, \"/* kept */ // too\"] /**/";
+        let options = ParseOptions {
+            allow_comments: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_with_options(input, &options),
+            Ok(JsonValue::Array(vec![
+                JsonValue::Int(1),
+                JsonValue::Int(2),
+                JsonValue::Str("/* kept */ // too".to_string()),
+            ]))
+        );
+        assert!(parse_with_options(r#"{/* a */"a"/* b */:/* c */1/* d */}"#, &options).is_ok());
+        for text in ["http://x", "/* not a comment */", "// nor this"] {
+            assert_eq!(
+                parse_with_options(&format!("[{:?}]", text), &options),
+                Ok(JsonValue::Array(vec![text.into()])),
+                "{:?}",
+                text
+            );
+        }
+        assert_eq!(
+            parse_with_options("[\"a\", // b\n \"c\"]", &options),
+            Ok(JsonValue::Array(vec!["a".into(), "c".into()]))
+        );
+        assert!(parse_with_options("[1] /* open", &options).is_err());
+        assert!(parse_complete(input).is_err());
+    }
+
+    #[test]
+    fn test_trailing_comma() {
+        let options = ParseOptions {
+            allow_trailing_comma: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_with_options("[1, 2, 3,]", &options),
+            parse_complete("[1, 2, 3]")
+        );
+        assert_eq!(
+            parse_with_options(r#"{"a": 1, }"#, &options),
+            parse_complete(r#"{"a": 1}"#)
+        );
+        assert!(parse_with_options("[1,,2]", &options).is_err());
+        assert!(parse_with_options("[1,,]", &options).is_err());
+        assert!(parse_with_options("[,]", &options).is_err());
+        assert!(parse_with_options("{,}", &options).is_err());
+        assert!(parse_complete("[1, 2, 3,]").is_err());
+        assert!(parse_complete(r#"{"a": 1,}"#).is_err());
+    }
+
+    #[test]
+    fn test_non_finite() {
+        let options = ParseOptions {
+            allow_nan: true,
+            ..Default::default()
+        };
+        let value = parse_with_options("[NaN, Infinity, -Infinity, -1]", &options).unwrap();
+        let items = value.as_array().unwrap();
+        assert!(matches!(items[0], JsonValue::Number(f) if f.is_nan()));
+        assert_eq!(items[1], JsonValue::Number(f64::INFINITY));
+        assert_eq!(items[2], JsonValue::Number(f64::NEG_INFINITY));
+        assert_eq!(items[3], JsonValue::Int(-1));
+        assert_ne!(items[0], items[0]);
+        assert!(parse_with_options("[Infinityx]", &options).is_err());
+
+        assert!(parse_complete("NaN").is_err());
+        assert!(parse_complete("Infinity").is_err());
+        assert!(parse_complete("-Infinity").is_err());
+    }
+
+    #[test]
+    fn test_raw_numbers() {
+        let input = "[100000000000000000000,1.0000000000000002,-0.50e+01,7]";
+        let options = ParseOptions {
+            raw_numbers: true,
+            ..Default::default()
+        };
+        let value = parse_with_options(input, &options).unwrap();
+        assert_eq!(
+            value[0],
+            JsonValue::RawNumber("100000000000000000000".to_string())
+        );
+        assert_eq!(value.to_string(), input);
+        assert_eq!(value[1].as_f64(), Some(1.0000000000000002));
+        assert_eq!(value[2].as_f64(), Some(-5.0));
+        assert_eq!(value[2].as_i64(), None);
+        assert_eq!(value[3].as_i64(), Some(7));
+        assert!(parse_with_options("[01]", &options).is_err());
+
+        assert_ne!(parse_complete(input).unwrap().to_string(), input);
+    }
+
+    #[test]
+    fn test_number_hook() {
+        let input = r#"{"price": 1.50, "n": [-0, 2e1], "s": "1"}"#;
+        let options = ParseOptions {
+            number_hook: Some(NumberHook::new(|token| {
+                Some(JsonValue::RawNumber(token.to_string()))
+            })),
+            ..Default::default()
+        };
+        let value = parse_with_options(input, &options).unwrap();
+        assert_eq!(value["price"], JsonValue::RawNumber("1.50".to_string()));
+        assert_eq!(value["n"][0], JsonValue::RawNumber("-0".to_string()));
+        assert_eq!(value["s"], JsonValue::Str("1".to_string()));
+        assert_eq!(value["n"].to_string(), "[-0,2e1]");
+        assert!(parse_with_options("[1.]", &options).is_err());
+
+        let options = ParseOptions {
+            number_hook: Some(NumberHook::new(|token| {
+                token.strip_suffix(".50").map(|_| JsonValue::Null)
+            })),
+            ..Default::default()
+        };
+        let value = parse_with_options(input, &options).unwrap();
+        assert_eq!(value["price"], JsonValue::Null);
+        assert_eq!(value["n"], parse_complete("[-0, 2e1]").unwrap());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_bigint() {
+        let digits = "-1234567890123456789012345678901234567890";
+        let value = parse_complete(&format!("[{}, 9223372036854775807, 1e40]", digits)).unwrap();
+        assert_eq!(value[0], JsonValue::BigInt(digits.parse().unwrap()));
+        assert_eq!(value[1], JsonValue::Int(i64::MAX));
+        assert_eq!(value[2], JsonValue::Number(1e40));
+        assert_eq!(value[0].as_f64(), Some(-1.2345678901234568e39));
+        assert_eq!(
+            value.to_string(),
+            format!("[{},9223372036854775807,1e40]", digits)
+        );
+        assert_eq!(parse_complete(&value.to_string()).unwrap(), value);
+        assert_eq!(
+            parse_complete("9223372036854775808").unwrap(),
+            JsonValue::BigInt(num_bigint::BigInt::from(i64::MAX) + 1)
+        );
+        assert!(
+            matches!(parse_borrowed("[1e2, 99999999999999999999]").unwrap().1,
+            JsonValueRef::Array(items) if items[1] == JsonValueRef::Number(1e20))
+        );
+    }
+
+    #[test]
+    fn test_whitespace() {
+        assert_eq!(
+            parse_complete(" [1,\t2\r\n]\n").unwrap(),
+            parse_complete("[1,2]").unwrap()
+        );
+        assert!(parse_complete("[1,\u{A0}2]").is_err());
+
+        let strict = ParseOptions {
+            whitespace: &[' ', '\n'],
+            ..Default::default()
+        };
+        assert!(parse_with_options("[1, 2]\n", &strict).is_ok());
+        assert!(parse_with_options("[1,\t2]", &strict).is_err());
+        assert!(parse_with_options("[1,\u{A0}2]", &strict).is_err());
+
+        let lenient = ParseOptions {
+            whitespace: &[' ', '\t', '\n', '\r', '\u{A0}'],
+            ..Default::default()
+        };
+        assert!(parse_with_options("[1,\u{A0}2]", &lenient).is_ok());
+    }
+
+    #[test]
+    fn test_whitespace_positions() {
+        let template = r#"_{_"a"_:_[_1_,_"x"_]_,_"b"_:_{_}_}_"#;
+        let expected = parse_complete(&template.replace('_', "")).unwrap();
+        let options = ParseOptions {
+            allow_comments: true,
+            ..Default::default()
+        };
+        for gap in [" ", "\r\n\t", "/* c */", "// c\n", " /**/ // c\n "] {
+            let input = template.replace('_', gap);
+            assert_eq!(
+                parse_with_options(&input, &options),
+                Ok(expected.clone()),
+                "{:?}",
+                input
+            );
+            let comments = gap.contains('/');
+            assert_eq!(parse_complete(&input).is_ok(), !comments, "{:?}", input);
+        }
+        // Each position alone accepts a comment too.
+        let pieces: Vec<_> = template.split('_').collect();
+        for i in 1..pieces.len() {
+            let input = [pieces[..i].concat(), pieces[i..].concat()].join("/**/");
+            assert_eq!(
+                parse_with_options(&input, &options),
+                Ok(expected.clone()),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_embedded_bom() {
+        let error = parse_document("[1,\n\u{FEFF}2]").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::UnexpectedChar('\u{FEFF}'));
+        assert_eq!((error.offset, error.line, error.column), (4, 2, 1));
+        assert!(error.to_string().contains("unexpected character U+FEFF"));
+        let error = parse_document("[1, \0 2]").unwrap_err();
+        assert_eq!(
+            (error.offset, error.kind),
+            (4, ErrorKind::UnexpectedChar('\0'))
+        );
+        assert!(parse_complete("[1]\u{FEFF}").is_err());
+        assert!(parse_many("1 \u{FEFF}2").any(|r| r.is_err()));
+
+        let options = ParseOptions {
+            strip_bom: true,
+            ..Default::default()
+        };
+        assert!(parse_with_options("\u{FEFF}[1, 2]", &options).is_ok());
+        assert!(parse_with_options("\u{FEFF}[1,\u{FEFF}2]", &options).is_err());
+        let options = ParseOptions {
+            whitespace: &[' ', '\u{FEFF}'],
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_with_options("[1,\u{FEFF}2]", &options),
+            parse_complete("[1, 2]")
+        );
+    }
+
+    struct Ignore;
+
+    impl Visitor for Ignore {}
+
+    /// Runs every entry point of the parser on `input`, which must not panic.
+    fn parse_everything(input: &str) {
+        let _ = parse(input);
+        let _ = parse_document(input);
+        let _ = parse_borrowed(input);
+        let _ = parse_lines(input).count();
+        let _ = parse_many(input).count();
+        let _ = parse_with_spans(input);
+        let _ = parse_events(input, &mut Ignore);
+        let options = ParseOptions {
+            allow_comments: true,
+            allow_trailing_comma: true,
+            allow_nan: true,
+            relaxed_numbers: true,
+            raw_numbers: true,
+            duplicate_keys: DuplicateKeyPolicy::Error,
+            strip_bom: true,
+            ..Default::default()
+        };
+        let _ = parse_with_options(input, &options);
+    }
+
+    #[test]
+    fn test_no_panic() {
+        let deep_array = "[".repeat(100_000);
+        let deep_object = r#"{"a":"#.repeat(100_000);
+        let inputs = [
+            "",
+            " ",
+            "[",
+            "]",
+            "{",
+            "}",
+            ":",
+            ",",
+            "\"",
+            "\"\\",
+            "\"\\u12",
+            "\"\\ud800\"",
+            "-",
+            "1e",
+            "0x1",
+            "[1,",
+            "{\"a\"",
+            "{\"a\":",
+            "{1: 2}",
+            "/*",
+            "//",
+            "\u{FEFF}",
+            &deep_array,
+            &deep_object,
+        ];
+        for input in inputs {
+            parse_everything(input);
+        }
+        let _ = parse_bytes(&[0xff, b'[', 0xc3]);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_no_panic_random(input in "\\PC*") {
+            parse_everything(&input);
+        }
+
+        #[test]
+        fn test_no_panic_json_like(input in r#"[\[\]{}:,"\\/*0-9eE.+\-truefalsnNIiyu \t\n]{0,64}"#) {
+            parse_everything(&input);
+        }
+
+        #[test]
+        fn test_no_panic_bytes(input in proptest::collection::vec(proptest::num::u8::ANY, 0..64)) {
+            let _ = parse_bytes(&input);
+        }
+    }
+
+    #[test]
+    fn test_imprecise_integers() {
+        let options = ParseOptions {
+            reject_imprecise_integers: true,
+            ..Default::default()
+        };
+        for input in [
+            "9007199254740992",
+            "-9007199254740992",
+            "100000000000000000000",
+            "-0",
+            "9007199254740993.0",
+            "9007199254740993e0",
+        ] {
+            assert!(parse_with_options(input, &options).is_ok(), "{}", input);
+        }
+        assert_eq!(
+            parse_with_options("9007199254740992", &options),
+            Ok(JsonValue::Int(9007199254740992))
+        );
+        let (rest, kind) =
+            failure(parse_with_options("[1, 9007199254740993]", &options).unwrap_err());
+        assert_eq!(
+            (rest, kind),
+            ("9007199254740993]", ErrorKind::ImpreciseInteger)
+        );
+        for input in [
+            "-9007199254740993",
+            "9223372036854775807",
+            "1".repeat(400).as_str(),
+        ] {
+            assert!(parse_with_options(input, &options).is_err(), "{}", input);
+        }
+        assert_eq!(
+            parse_complete("9007199254740993"),
+            Ok(JsonValue::Int(9007199254740993))
+        );
+
+        let raw = ParseOptions {
+            raw_numbers: true,
+            ..options
+        };
+        assert!(parse_with_options("9007199254740993", &raw).is_err());
+        assert_eq!(
+            parse_with_options("[1.5]", &raw).unwrap()[0],
+            JsonValue::RawNumber("1.5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_container_root() {
+        let options = ParseOptions {
+            require_container_root: true,
+            ..Default::default()
+        };
+        assert_eq!(parse_complete("42"), Ok(JsonValue::Int(42)));
+        assert_eq!(parse_complete(r#""hi""#), Ok(JsonValue::from("hi")));
+        let (rest, kind) = failure(parse_with_options(" 42", &options).unwrap_err());
+        assert_eq!((rest, kind), ("42", ErrorKind::ContainerRootRequired));
+        assert!(parse_with_options("true", &options).is_err());
+        assert!(parse_with_options("", &options).is_err());
+        for input in ["[42]", " {} "] {
+            assert!(parse_complete(input).is_ok());
+            assert!(parse_with_options(input, &options).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_bom() {
+        let input = "\u{FEFF}{\"a\": [1]}";
+        assert!(parse_complete(input).is_err());
+        let options = ParseOptions {
+            strip_bom: true,
+            ..Default::default()
+        };
+        let value = parse_with_options(input, &options).unwrap();
+        assert_eq!(value, parse_complete(&input[3..]).unwrap());
+        assert!(parse_with_options("\u{FEFF}\u{FEFF}1", &options).is_err());
+        assert!(parse_with_options("[\u{FEFF}1]", &options).is_err());
     }
 }
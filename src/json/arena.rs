@@ -0,0 +1,243 @@
+use alloc::{
+    borrow::Cow,
+    vec::{self, Vec},
+};
+
+use bumpalo::Bump;
+
+use super::{
+    map_with_capacity, parse_document_with, Builder, JsonValue, MapImpl, ParseError, ParseOptions,
+};
+
+/// A parsed value allocated in a [`Bump`] arena, from [`parse_in`].
+///
+/// Strings, elements and members all live in the arena and are freed
+/// together when it is dropped or reset; the value itself owns nothing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArenaValue<'b> {
+    Number(f64),
+    Int(i64),
+    Str(&'b str),
+    Bool(bool),
+    Null,
+    /// The members of an object, in the order their keys first appeared.
+    Object(&'b [(&'b str, ArenaValue<'b>)]),
+    Array(&'b [ArenaValue<'b>]),
+}
+
+impl<'b> ArenaValue<'b> {
+    /// Returns the member `key` of an object, or `None` when the key is
+    /// missing or the value is not an object.
+    ///
+    /// Members are kept in a slice, so this scans them in order.
+    pub fn get(&self, key: &str) -> Option<&ArenaValue<'b>> {
+        match self {
+            ArenaValue::Object(members) => members.iter().find(|(k, _)| *k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Copies the value out of the arena.
+    pub fn to_value(&self) -> JsonValue {
+        match *self {
+            ArenaValue::Number(f) => JsonValue::Number(f),
+            ArenaValue::Int(i) => JsonValue::Int(i),
+            ArenaValue::Str(s) => JsonValue::Str(s.into()),
+            ArenaValue::Bool(b) => JsonValue::Bool(b),
+            ArenaValue::Null => JsonValue::Null,
+            ArenaValue::Object(members) => JsonValue::Object(
+                members
+                    .iter()
+                    .map(|(k, v)| ((*k).into(), v.to_value()))
+                    .collect(),
+            ),
+            ArenaValue::Array(items) => {
+                JsonValue::Array(items.iter().map(ArenaValue::to_value).collect())
+            }
+        }
+    }
+}
+
+/// The number of members up to which an object being built finds duplicate
+/// keys by scanning them rather than through an index.
+const SCANNED_MEMBERS: usize = 16;
+
+/// The members of an object being built, gathered in the arena.
+///
+/// Only an object with more than [`SCANNED_MEMBERS`] members also has an
+/// index of their positions by key, so that duplicate keys are found as
+/// quickly as for a [`JsonValue`]; it is the one part allocated outside the
+/// arena.
+struct ArenaObject<'b> {
+    members: bumpalo::collections::Vec<'b, (&'b str, ArenaValue<'b>)>,
+    index: Option<MapImpl<&'b str, usize>>,
+}
+
+impl<'b> ArenaObject<'b> {
+    /// Returns the position of the member `key`, if there is one.
+    fn position(&self, key: &str) -> Option<usize> {
+        match &self.index {
+            Some(index) => index.get(key).copied(),
+            None => self.members.iter().position(|(k, _)| *k == key),
+        }
+    }
+}
+
+/// Builds an [`ArenaValue`] in `arena`.
+///
+/// Strings containing escapes are decoded into a buffer reused for the whole
+/// document before being copied into the arena, and elements and members are
+/// moved there straight from the parser's scratch stacks.
+struct ArenaBuilder<'b> {
+    arena: &'b Bump,
+}
+
+impl<'a, 'b: 'a> Builder<'a> for ArenaBuilder<'b> {
+    type Value = ArenaValue<'b>;
+    type Object = ArenaObject<'b>;
+
+    fn null(&mut self) -> ArenaValue<'b> {
+        ArenaValue::Null
+    }
+
+    fn bool(&mut self, b: bool) -> ArenaValue<'b> {
+        ArenaValue::Bool(b)
+    }
+
+    fn int(&mut self, i: i64) -> ArenaValue<'b> {
+        ArenaValue::Int(i)
+    }
+
+    fn float(&mut self, f: f64) -> ArenaValue<'b> {
+        ArenaValue::Number(f)
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) -> ArenaValue<'b> {
+        ArenaValue::Str(self.arena.alloc_str(&s))
+    }
+
+    fn decoded_string(&mut self, s: &str) -> ArenaValue<'b> {
+        ArenaValue::Str(self.arena.alloc_str(s))
+    }
+
+    fn decoded_key(&mut self, key: &str) -> Cow<'a, str> {
+        // Decoded into the arena, which outlives the input, rather than into
+        // a string of its own; it is copied once more on insertion, as keys
+        // borrowed from the input are.
+        Cow::Borrowed(self.arena.alloc_str(key))
+    }
+
+    fn array(&mut self, items: Vec<ArenaValue<'b>>) -> ArenaValue<'b> {
+        ArenaValue::Array(self.arena.alloc_slice_copy(&items))
+    }
+
+    fn array_from(&mut self, items: vec::Drain<'_, ArenaValue<'b>>) -> ArenaValue<'b> {
+        ArenaValue::Array(self.arena.alloc_slice_fill_iter(items))
+    }
+
+    fn new_object(&mut self, capacity: usize) -> Self::Object {
+        ArenaObject {
+            members: bumpalo::collections::Vec::with_capacity_in(capacity, self.arena),
+            index: (capacity > SCANNED_MEMBERS).then(|| map_with_capacity(capacity)),
+        }
+    }
+
+    fn contains_key(&self, object: &Self::Object, key: &str) -> bool {
+        object.position(key).is_some()
+    }
+
+    fn insert(&mut self, object: &mut Self::Object, key: Cow<'a, str>, value: ArenaValue<'b>) {
+        match object.position(&key) {
+            Some(i) => object.members[i].1 = value,
+            None => {
+                let key = self.arena.alloc_str(&key);
+                if let Some(index) = &mut object.index {
+                    index.insert(key, object.members.len());
+                }
+                object.members.push((key, value));
+            }
+        }
+    }
+
+    fn object(&mut self, object: Self::Object) -> ArenaValue<'b> {
+        ArenaValue::Object(object.members.into_bump_slice())
+    }
+}
+
+/// Parses `input` as a whole JSON document like
+/// [`parse_document`](super::parse_document), allocating the value in `arena`.
+///
+/// For parsing many documents in a loop, resetting one arena between them
+/// reuses its memory instead of allocating and freeing every string, array
+/// and object separately. Nothing is freed until the arena is dropped or
+/// reset, however, so even a string replaced by a later duplicate key keeps
+/// its memory until then.
+pub fn parse_in<'b>(input: &str, arena: &'b Bump) -> Result<&'b ArenaValue<'b>, ParseError> {
+    let mut builder = ArenaBuilder { arena };
+    match parse_document_with(input, &ParseOptions::default(), &mut builder) {
+        Ok(value) => Ok(arena.alloc(value)),
+        Err(e) => Err(ParseError::new(input, e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{parse_complete, ErrorKind};
+    use super::*;
+
+    #[test]
+    fn test_parse_in() {
+        let arena = Bump::new();
+        let input = r#"{"name": "a\nb", "tags": ["x", 1, 2.5, null], "ok": true, "name": "c"}"#;
+        let value = parse_in(input, &arena).unwrap();
+        assert_eq!(value.to_value(), parse_complete(input).unwrap());
+        assert_eq!(value.get("name"), Some(&ArenaValue::Str("c")));
+        match value.get("tags") {
+            Some(ArenaValue::Array(items)) => {
+                assert_eq!(items.len(), 4);
+                assert_eq!(items[2], ArenaValue::Number(2.5));
+            }
+            other => panic!("expected an array, got {:?}", other),
+        }
+        assert_eq!(value.get("missing"), None);
+        assert_eq!(ArenaValue::Null.get("name"), None);
+
+        let mut arena = Bump::new();
+        for _ in 0..3 {
+            let value = parse_in("[[1], {}]", &arena).unwrap();
+            assert_eq!(value.to_value(), parse_complete("[[1], {}]").unwrap());
+            arena.reset();
+        }
+        let error = parse_in("[1, 2", &arena).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Unclosed('['));
+    }
+
+    #[test]
+    fn test_parse_in_members() {
+        let arena = Bump::new();
+        let input = r#"{"b": 1, "a\u0062": 2, "a": 3, "b": 4, "\u0062": 5}"#;
+        let value = parse_in(input, &arena).unwrap();
+        let members = [
+            ("b", ArenaValue::Int(5)),
+            ("ab", ArenaValue::Int(2)),
+            ("a", ArenaValue::Int(3)),
+        ];
+        assert_eq!(*value, ArenaValue::Object(&members));
+
+        // Past `SCANNED_MEMBERS`, keys are found through an index instead.
+        let keys: Vec<_> = (0..40)
+            .map(|i| format!(r#""k{}": {}"#, i % 30, i))
+            .collect();
+        let input = format!("{{{}}}", keys.join(", "));
+        let value = parse_in(&input, &arena).unwrap();
+        assert_eq!(value.to_value(), parse_complete(&input).unwrap());
+        match value {
+            ArenaValue::Object(members) => {
+                assert_eq!(members.len(), 30);
+                assert_eq!(members[0], ("k0", ArenaValue::Int(30)));
+                assert_eq!(members[29], ("k29", ArenaValue::Int(29)));
+            }
+            other => panic!("expected an object, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use nom::Err;
+
+use super::{
+    parse_value, skip_ws, Error, ErrorKind, JsonValue, ParseError, ParseOptions, ValueBuilder,
+    VALUE_TOKENS, VALUE_TOKENS_OR_CLOSE,
+};
+
+/// Where an [`ArrayStream`] is in the array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// Before the opening bracket.
+    Start,
+    /// After a comma, before the next element.
+    Next,
+    /// After the closing bracket.
+    End,
+}
+
+/// The elements of a top-level array, parsed one at a time as they are
+/// iterated, from [`ArrayStream::new`].
+///
+/// Only the element being parsed is held in memory, so a huge array, such as
+/// a memory-mapped file, can be processed element by element, and iteration
+/// can stop early without parsing the rest. An error in an element is only
+/// found when the iteration reaches it; once an element or the array itself
+/// is malformed its error is returned and no further elements are.
+#[derive(Debug, Clone)]
+pub struct ArrayStream<'a> {
+    input: &'a str,
+    /// The input following the last token parsed, or `None` once the array
+    /// has ended or failed.
+    rest: Option<&'a str>,
+    position: Position,
+    options: ParseOptions,
+}
+
+impl<'a> ArrayStream<'a> {
+    /// Iterates the elements of the array `input` holds.
+    pub fn new(input: &'a str) -> Self {
+        ArrayStream::with_options(input, ParseOptions::default())
+    }
+
+    /// Iterates the elements of the array `input` holds, accepting what
+    /// [`parse_with_options`](super::parse_with_options) does with `options`.
+    pub fn with_options(input: &'a str, options: ParseOptions) -> Self {
+        ArrayStream {
+            input,
+            rest: Some(input),
+            position: Position::Start,
+            options,
+        }
+    }
+
+    /// Parses the next element of the array, or checks that only whitespace
+    /// follows its end.
+    fn element(&mut self, input: &'a str) -> Result<Option<(&'a str, JsonValue)>, Err<Error<'a>>> {
+        let options = &self.options;
+        let (mut rest, _) = skip_ws(input, options)?;
+        let expected = match self.position {
+            Position::Start => {
+                rest = match rest.strip_prefix('[') {
+                    Some(rest) => skip_ws(rest, options)?.0,
+                    None => {
+                        let mut error = Error::new(rest, ErrorKind::Char('['));
+                        error.expected = &["array"];
+                        return Err(Err::Failure(error));
+                    }
+                };
+                if let Some(after) = rest.strip_prefix(']') {
+                    return self.end(after);
+                }
+                VALUE_TOKENS_OR_CLOSE
+            }
+            Position::Next => {
+                match rest.strip_prefix(']') {
+                    Some(after) if options.allow_trailing_comma => return self.end(after),
+                    _ if rest.starts_with([',', ']']) => {
+                        let mut error = Error::new(rest, ErrorKind::ExpectedValue);
+                        error.expected = VALUE_TOKENS;
+                        return Err(Err::Failure(error));
+                    }
+                    _ => {}
+                }
+                VALUE_TOKENS
+            }
+            Position::End => return self.end(rest),
+        };
+        if rest.is_empty() {
+            return Err(self.unclosed(expected));
+        }
+        let (rest, value) =
+            parse_value(rest, options, 1, &mut ValueBuilder).map_err(|e| match e {
+                Err::Error(e) => Err::Failure(e),
+                e => e,
+            })?;
+        self.position = match rest.chars().next() {
+            Some(',') => Position::Next,
+            Some(']') => Position::End,
+            Some(_) => {
+                let mut error = Error::new(rest, ErrorKind::ExpectedSeparator(']'));
+                error.expected = &[",", "]"];
+                return Err(Err::Failure(error));
+            }
+            None => return Err(self.unclosed(&[",", "]"])),
+        };
+        Ok(Some((&rest[1..], value)))
+    }
+
+    /// Checks that only whitespace follows the closing bracket.
+    fn end(&self, input: &'a str) -> Result<Option<(&'a str, JsonValue)>, Err<Error<'a>>> {
+        let (rest, _) = skip_ws(input, &self.options)?;
+        if !rest.is_empty() {
+            let kind = ErrorKind::Nom(nom::error::ErrorKind::Eof);
+            return Err(Err::Error(Error::new(rest, kind)));
+        }
+        Ok(None)
+    }
+
+    /// The error for input ending inside the array, located at its opener.
+    fn unclosed(&self, expected: &'static [&'static str]) -> Err<Error<'a>> {
+        let start = self.input.trim_start_matches(self.options.whitespace);
+        let mut error = Error::new(start, ErrorKind::Unclosed('['));
+        error.expected = expected;
+        Err::Failure(error)
+    }
+}
+
+impl Iterator for ArrayStream<'_> {
+    type Item = Result<JsonValue, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest.take()?;
+        match self.element(rest) {
+            Ok(Some((rest, value))) => {
+                self.rest = Some(rest);
+                Some(Ok(value))
+            }
+            Ok(None) => None,
+            Err(e) => Some(Err(ParseError::new(self.input, e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{string::String, vec::Vec};
+
+    use super::super::{parse_complete, parse_document};
+    use super::*;
+
+    #[test]
+    fn test_array_stream() {
+        let mut input = String::from("[");
+        for i in 0..100_000 {
+            input.push_str(&format!("{{\"id\": {}, \"tags\": [\"a\", \"b\"]}},\n", i));
+        }
+        input.push_str("1 x]");
+        let mut ids = Vec::new();
+        for element in ArrayStream::new(&input) {
+            ids.push(element.unwrap()["id"].clone());
+            if ids.len() == 2 {
+                break;
+            }
+        }
+        assert_eq!(ids, [JsonValue::Int(0), JsonValue::Int(1)]);
+
+        let input = " [1, {\"a\": [2]}, \"x\", [] ] ";
+        let elements: Vec<_> = ArrayStream::new(input).map(Result::unwrap).collect();
+        assert_eq!(JsonValue::Array(elements), parse_complete(input).unwrap());
+        assert_eq!(ArrayStream::new("[ ]").count(), 0);
+
+        let errors = |input: &str| -> ParseError {
+            let results: Vec<_> = ArrayStream::new(input).collect();
+            assert!(results[..results.len() - 1].iter().all(Result::is_ok));
+            results.last().unwrap().clone().unwrap_err()
+        };
+        for input in [
+            "[1, 2", "[1, 2 3]", "[1,]", "[1,,2]", "[1] x", "[1, tru]", " [1,",
+        ] {
+            let error = errors(input);
+            let expected = parse_document(input).unwrap_err();
+            assert_eq!(
+                (error.offset, &error.kind),
+                (expected.offset, &expected.kind),
+                "{:?}",
+                input
+            );
+        }
+        let options = ParseOptions {
+            allow_trailing_comma: true,
+            ..Default::default()
+        };
+        assert_eq!(ArrayStream::with_options("[1, 2,]", options).count(), 2);
+        let error = errors(" {\"a\": 1}");
+        assert_eq!((error.offset, error.kind), (1, ErrorKind::Char('[')));
+        assert!(ArrayStream::new("").next().unwrap().is_err());
+    }
+}
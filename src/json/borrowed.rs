@@ -0,0 +1,146 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use super::{map_with_capacity, parse_value, Builder, JsonValue, MapImpl, ParseOptions, Res};
+
+/// The map backing [`JsonValueRef::Object`], ordered like [`Map`](super::Map).
+pub type MapRef<'a> = MapImpl<Cow<'a, str>, JsonValueRef<'a>>;
+
+/// A parsed value that borrows its strings and object keys from the input.
+///
+/// Only strings and keys containing escapes need unescaping and are
+/// allocated; all others are slices of the input. An object with many short
+/// keys is parsed with far fewer allocations than as a [`JsonValue`], which
+/// owns a `String` for every key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValueRef<'a> {
+    Number(f64),
+    Int(i64),
+    Str(Cow<'a, str>),
+    Bool(bool),
+    Null,
+    Object(MapRef<'a>),
+    Array(Vec<JsonValueRef<'a>>),
+}
+
+impl<'a> JsonValueRef<'a> {
+    /// Copies the borrowed strings, detaching the value from the input.
+    pub fn into_owned(self) -> JsonValue {
+        match self {
+            JsonValueRef::Number(f) => JsonValue::Number(f),
+            JsonValueRef::Int(i) => JsonValue::Int(i),
+            JsonValueRef::Str(s) => JsonValue::Str(s.into_owned()),
+            JsonValueRef::Bool(b) => JsonValue::Bool(b),
+            JsonValueRef::Null => JsonValue::Null,
+            JsonValueRef::Object(map) => JsonValue::Object(
+                map.into_iter()
+                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                    .collect(),
+            ),
+            JsonValueRef::Array(items) => {
+                JsonValue::Array(items.into_iter().map(JsonValueRef::into_owned).collect())
+            }
+        }
+    }
+}
+
+/// Builds a [`JsonValueRef`].
+struct RefBuilder;
+
+impl<'a> Builder<'a> for RefBuilder {
+    type Value = JsonValueRef<'a>;
+    type Object = MapRef<'a>;
+
+    fn null(&mut self) -> JsonValueRef<'a> {
+        JsonValueRef::Null
+    }
+
+    fn bool(&mut self, b: bool) -> JsonValueRef<'a> {
+        JsonValueRef::Bool(b)
+    }
+
+    fn int(&mut self, i: i64) -> JsonValueRef<'a> {
+        JsonValueRef::Int(i)
+    }
+
+    fn float(&mut self, f: f64) -> JsonValueRef<'a> {
+        JsonValueRef::Number(f)
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) -> JsonValueRef<'a> {
+        JsonValueRef::Str(s)
+    }
+
+    fn array(&mut self, items: Vec<JsonValueRef<'a>>) -> JsonValueRef<'a> {
+        JsonValueRef::Array(items)
+    }
+
+    fn new_object(&mut self, capacity: usize) -> MapRef<'a> {
+        map_with_capacity(capacity)
+    }
+
+    fn contains_key(&self, object: &MapRef<'a>, key: &str) -> bool {
+        object.contains_key(key)
+    }
+
+    fn insert(&mut self, object: &mut MapRef<'a>, key: Cow<'a, str>, value: JsonValueRef<'a>) {
+        object.insert(key, value);
+    }
+
+    fn object(&mut self, object: MapRef<'a>) -> JsonValueRef<'a> {
+        JsonValueRef::Object(object)
+    }
+}
+
+/// Parses a value like [`combinators::parse_value`](super::combinators::parse_value),
+/// borrowing strings and object keys from `input` wherever possible.
+pub fn parse_borrowed(input: &str) -> Res<'_, JsonValueRef<'_>> {
+    parse_value(input, &ParseOptions::default(), 0, &mut RefBuilder)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_borrowed() {
+        let input = r#"{"plain": "text", "esc\"aped": ["a\nb", 1, null]}"#;
+        let (rest, value) = parse_borrowed(input).unwrap();
+        assert_eq!(rest, "");
+        let map = match &value {
+            JsonValueRef::Object(map) => map,
+            _ => panic!("expected an object"),
+        };
+        let (key, plain) = map.get_key_value("plain").unwrap();
+        assert!(matches!(key, Cow::Borrowed("plain")));
+        assert!(matches!(plain, JsonValueRef::Str(Cow::Borrowed("text"))));
+
+        let (key, array) = map.get_key_value("esc\"aped").unwrap();
+        assert!(matches!(key, Cow::Owned(_)));
+        match array {
+            JsonValueRef::Array(items) => {
+                assert_eq!(items[0], JsonValueRef::Str(Cow::Owned("a\nb".to_string())));
+                assert!(matches!(items[0], JsonValueRef::Str(Cow::Owned(_))));
+            }
+            _ => panic!("expected an array"),
+        }
+
+        assert_eq!(value.into_owned(), parse_complete(input).unwrap());
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        let members: Vec<_> = (0..1000).map(|i| format!(r#""key{}": {}"#, i, i)).collect();
+        let input = format!(r#"{{{}, "tab\tkey": 0, "\u00e9": 1}}"#, members.join(","));
+        let map = match parse_borrowed(&input).unwrap().1 {
+            JsonValueRef::Object(map) => map,
+            _ => panic!("expected an object"),
+        };
+        assert_eq!(map.len(), 1002);
+        let borrowed = map.keys().filter(|k| matches!(k, Cow::Borrowed(_))).count();
+        assert_eq!(borrowed, 1000);
+        assert_eq!(map["key999"], JsonValueRef::Int(999));
+        assert_eq!(map["tab\tkey"], JsonValueRef::Int(0));
+        assert_eq!(map["é"], JsonValueRef::Int(1));
+    }
+}
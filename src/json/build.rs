@@ -0,0 +1,185 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use nom::combinator::all_consuming;
+
+use super::{parse_value, Builder, ParseError, ParseOptions};
+
+/// Builds values of a type of its own from a document parsed by
+/// [`parse_with`], in place of [`JsonValue`](super::JsonValue).
+///
+/// Containers are built once they are closed, from values already built for
+/// their contents. Strings and keys without escapes are borrowed from the
+/// input, which outlives `'a`.
+pub trait JsonBuilder<'a> {
+    type Value;
+    /// An object whose members are still being added.
+    type Object;
+
+    fn null(&mut self) -> Self::Value;
+    fn bool(&mut self, b: bool) -> Self::Value;
+    /// Builds a number written without a fraction or an exponent that fits
+    /// an `i64`.
+    fn int(&mut self, i: i64) -> Self::Value;
+    /// Builds any other number.
+    fn float(&mut self, f: f64) -> Self::Value;
+    fn string(&mut self, s: Cow<'a, str>) -> Self::Value;
+    fn array(&mut self, items: Vec<Self::Value>) -> Self::Value;
+    /// Starts an object that will have `capacity` members added, counting
+    /// repeated keys.
+    fn new_object(&mut self, capacity: usize) -> Self::Object;
+    /// Adds a member, in the order they appear. A key appearing more than
+    /// once is added each time, and the builder decides which value to keep.
+    fn insert(&mut self, object: &mut Self::Object, key: Cow<'a, str>, value: Self::Value);
+    fn object(&mut self, object: Self::Object) -> Self::Value;
+}
+
+/// Drives a [`JsonBuilder`] with the values of the parse.
+struct Built<'b, B>(&'b mut B);
+
+impl<'a, 'b, B: JsonBuilder<'a>> Builder<'a> for Built<'b, B> {
+    type Value = B::Value;
+    type Object = B::Object;
+
+    fn null(&mut self) -> B::Value {
+        self.0.null()
+    }
+
+    fn bool(&mut self, b: bool) -> B::Value {
+        self.0.bool(b)
+    }
+
+    fn int(&mut self, i: i64) -> B::Value {
+        self.0.int(i)
+    }
+
+    fn float(&mut self, f: f64) -> B::Value {
+        self.0.float(f)
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) -> B::Value {
+        self.0.string(s)
+    }
+
+    fn array(&mut self, items: Vec<B::Value>) -> B::Value {
+        self.0.array(items)
+    }
+
+    fn new_object(&mut self, capacity: usize) -> B::Object {
+        self.0.new_object(capacity)
+    }
+
+    fn contains_key(&self, _: &B::Object, _: &str) -> bool {
+        false
+    }
+
+    fn insert(&mut self, object: &mut B::Object, key: Cow<'a, str>, value: B::Value) {
+        self.0.insert(object, key, value);
+    }
+
+    fn object(&mut self, object: B::Object) -> B::Value {
+        self.0.object(object)
+    }
+}
+
+/// Parses `input` as a whole JSON document like [`parse`](super::parse),
+/// building its value with `builder` instead of as a
+/// [`JsonValue`](super::JsonValue).
+pub fn parse_with<'a, B: JsonBuilder<'a>>(
+    input: &'a str,
+    builder: &mut B,
+) -> Result<B::Value, ParseError> {
+    let mut built = Built(builder);
+    let result = all_consuming(|i| parse_value(i, &ParseOptions::default(), 0, &mut built))(input);
+    result
+        .map(|(_, value)| value)
+        .map_err(|e| ParseError::new(input, e))
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+
+    use super::super::ErrorKind;
+    use super::*;
+
+    /// Counts the values in a document, containers included.
+    struct Count;
+
+    impl<'a> JsonBuilder<'a> for Count {
+        type Value = usize;
+        type Object = usize;
+
+        fn null(&mut self) -> usize {
+            1
+        }
+
+        fn bool(&mut self, _b: bool) -> usize {
+            1
+        }
+
+        fn int(&mut self, _i: i64) -> usize {
+            1
+        }
+
+        fn float(&mut self, _f: f64) -> usize {
+            1
+        }
+
+        fn string(&mut self, _s: Cow<'a, str>) -> usize {
+            1
+        }
+
+        fn array(&mut self, items: Vec<usize>) -> usize {
+            1 + items.iter().sum::<usize>()
+        }
+
+        fn new_object(&mut self, _capacity: usize) -> usize {
+            1
+        }
+
+        fn insert(&mut self, object: &mut usize, _key: Cow<'a, str>, value: usize) {
+            *object += value;
+        }
+
+        fn object(&mut self, object: usize) -> usize {
+            object
+        }
+    }
+
+    /// Collects the keys of every object, borrowing those without escapes.
+    struct Keys<'a>(Vec<Cow<'a, str>>);
+
+    impl<'a> JsonBuilder<'a> for Keys<'a> {
+        type Value = ();
+        type Object = ();
+
+        fn null(&mut self) {}
+        fn bool(&mut self, _b: bool) {}
+        fn int(&mut self, _i: i64) {}
+        fn float(&mut self, _f: f64) {}
+        fn string(&mut self, _s: Cow<'a, str>) {}
+        fn array(&mut self, _items: Vec<()>) {}
+        fn new_object(&mut self, _capacity: usize) {}
+
+        fn insert(&mut self, _object: &mut (), key: Cow<'a, str>, _value: ()) {
+            self.0.push(key);
+        }
+
+        fn object(&mut self, _object: ()) {}
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let input = r#"{"a": [1, 2.5, "s", null], "b": {"c": true, "d": []}}"#;
+        assert_eq!(parse_with(input, &mut Count), Ok(9));
+        assert_eq!(parse_with("0", &mut Count), Ok(1));
+        let error = parse_with("[1, 2", &mut Count).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Unclosed('['));
+
+        let input = String::from(r#"{"x": {"y\n": 1, "x": 2}}"#);
+        let mut keys = Keys(Vec::new());
+        parse_with(&input, &mut keys).unwrap();
+        assert_eq!(keys.0, ["y\n", "x", "x"]);
+        assert!(matches!(keys.0[1], Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,48 @@
+//! The parser as `nom` combinators, for embedding JSON values in a larger
+//! `nom` grammar.
+//!
+//! These expose `nom`'s types and so change with its version; prefer
+//! [`parse`](super::parse) and the other functions of [`json`](super) where
+//! a whole document is parsed.
+
+use nom::IResult;
+
+use super::{Error, JsonValue, ParseOptions, ValueBuilder};
+
+/// Parses the JSON value at the start of `input`, along with any whitespace
+/// around it, returning it with the input that follows.
+pub fn parse_value(input: &str) -> IResult<&str, JsonValue, Error<'_>> {
+    super::parse_value(input, &ParseOptions::default(), 0, &mut ValueBuilder)
+}
+
+/// Parses a value like [`parse_value`] with the extensions and restrictions
+/// selected by `options`, except for `max_input_bytes`, `strip_bom` and
+/// `require_container_root`, which apply to whole documents only.
+pub fn parse_value_with_options<'a>(
+    input: &'a str,
+    options: &ParseOptions,
+) -> IResult<&'a str, JsonValue, Error<'a>> {
+    super::parse_value(input, options, 0, &mut ValueBuilder)
+}
+
+#[cfg(test)]
+mod test {
+    use nom::{character::complete::char, multi::separated_list1};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_value() {
+        let (rest, value) = parse_value(" [1, 2] tail").unwrap();
+        assert_eq!((rest, value.to_string().as_str()), ("tail", "[1,2]"));
+        assert!(parse_value("").is_err());
+
+        let (rest, values) = separated_list1(char(';'), parse_value)("1; \"a\" ;null").unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(values, [1.into(), "a".into(), JsonValue::Null]);
+
+        let options = ParseOptions::json5();
+        let (_, value) = parse_value_with_options("{a: NaN,}", &options).unwrap();
+        assert!(matches!(&value["a"], JsonValue::Number(f) if f.is_nan()));
+    }
+}
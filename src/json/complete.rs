@@ -0,0 +1,109 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+use super::{parse_value, skip_element, Builder, Error, ParseOptions};
+
+/// Tracks the keys of the objects open during a parse, building nothing.
+struct KeysBuilder {
+    /// The keys of each open object so far, innermost last.
+    open: Vec<Vec<String>>,
+    /// The keys of the innermost object open when parsing ran out of input.
+    scope: Option<Vec<String>>,
+}
+
+impl<'a> Builder<'a> for KeysBuilder {
+    type Value = ();
+    type Object = ();
+
+    fn null(&mut self) {}
+    fn bool(&mut self, _b: bool) {}
+    fn int(&mut self, _i: i64) {}
+    fn float(&mut self, _f: f64) {}
+    fn string(&mut self, _s: Cow<'a, str>) {}
+    fn array(&mut self, _items: Vec<()>) {}
+
+    fn begin_object(&mut self) {
+        self.open.push(Vec::new());
+    }
+
+    fn key(&mut self, key: &str) {
+        if let Some(keys) = self.open.last_mut() {
+            if !keys.iter().any(|k| k == key) {
+                keys.push(key.into());
+            }
+        }
+    }
+
+    fn new_object(&mut self, _capacity: usize) {}
+
+    fn contains_key(&self, _object: &(), _key: &str) -> bool {
+        false
+    }
+
+    fn insert(&mut self, _object: &mut (), _key: Cow<'a, str>, _value: ()) {}
+
+    fn object(&mut self, _object: ()) {
+        self.open.pop();
+    }
+
+    fn recover(&mut self, error: Error<'a>) -> Result<(), Error<'a>> {
+        // The first malformed element reaching the end of the input is the
+        // one the cursor is in; the containers around it are all unclosed.
+        if self.scope.is_none() && skip_element(error.input).is_empty() {
+            self.scope = Some(self.open.last().cloned().unwrap_or_default());
+        }
+        Ok(())
+    }
+}
+
+/// Returns the keys of the innermost object open at byte `offset` of `input`,
+/// in the order they are written before it, for completing a document being
+/// edited.
+///
+/// Only the input before `offset` is parsed, tolerating malformed and missing
+/// values as [`parse_recovering`](super::parse_recovering) does, so a key
+/// whose value is still being typed is included. Returns no keys if `offset`
+/// is outside any object. An `offset` past the end of `input` or inside a
+/// character is moved back to the nearest character boundary.
+pub fn collect_keys_at(input: &str, offset: usize) -> Vec<String> {
+    let mut end = offset.min(input.len());
+    while !input.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut builder = KeysBuilder {
+        open: Vec::new(),
+        scope: None,
+    };
+    if let Err(nom::Err::Error(e) | nom::Err::Failure(e)) =
+        parse_value(&input[..end], &ParseOptions::default(), 0, &mut builder)
+    {
+        let _ = builder.recover(e);
+    }
+    builder.scope.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_collect_keys_at() {
+        let input = r#"{"name": "x", "tags": [1, 2], "owner": , "id": 3}"#;
+        let cursor = input.find(", \"id\"").unwrap();
+        assert_eq!(collect_keys_at(input, cursor), ["name", "tags", "owner"]);
+        let cursor = input.find("\"tags\"").unwrap();
+        assert_eq!(collect_keys_at(input, cursor), ["name"]);
+
+        let input = r#"{"a": 1, "b": {"c": tr"#;
+        assert_eq!(collect_keys_at(input, input.len()), ["c"]);
+        let input = r#"{"a": 1, "b": [{"c": 1}, 2, "#;
+        assert_eq!(collect_keys_at(input, input.len()), ["a", "b"]);
+        let input = r#"{"a": {"b": 1}, "na"#;
+        assert_eq!(collect_keys_at(input, input.len()), ["a"]);
+        assert_eq!(collect_keys_at(input, 100), ["a"]);
+        assert_eq!(collect_keys_at(r#"{"a": "é"}"#, 8), ["a"]);
+
+        assert!(collect_keys_at(r#"{"a": 1}"#, 8).is_empty());
+        assert!(collect_keys_at("[1, ", 4).is_empty());
+        assert!(collect_keys_at("", 0).is_empty());
+    }
+}
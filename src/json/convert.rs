@@ -0,0 +1,163 @@
+use alloc::string::String;
+use core::{convert::TryFrom, fmt};
+
+use super::JsonValue;
+
+/// Why a [`JsonValue`] could not be converted to a Rust value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConversionError {
+    /// The value was not of the expected kind, named here.
+    UnexpectedType(&'static str),
+    /// The number had a fractional part, but an integer was expected.
+    NotAnInteger,
+    /// The number did not fit the range of the target type.
+    OutOfRange,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionError::UnexpectedType(expected) => write!(f, "expected {}", expected),
+            ConversionError::NotAnInteger => f.write_str("number is not an integer"),
+            ConversionError::OutOfRange => f.write_str("number out of range"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConversionError {}
+
+/// Converts any number, like [`JsonValue::as_f64`].
+impl TryFrom<&JsonValue> for f64 {
+    type Error = ConversionError;
+
+    fn try_from(value: &JsonValue) -> Result<f64, ConversionError> {
+        value
+            .as_f64()
+            .ok_or(ConversionError::UnexpectedType("a number"))
+    }
+}
+
+/// Converts an integer, or a float with no fractional part that fits.
+impl TryFrom<&JsonValue> for i64 {
+    type Error = ConversionError;
+
+    fn try_from(value: &JsonValue) -> Result<i64, ConversionError> {
+        if let Some(i) = value.as_i64() {
+            return Ok(i);
+        }
+        let f = f64::try_from(value)?;
+        if f.fract() != 0. {
+            Err(ConversionError::NotAnInteger)
+        } else if (-9.223_372_036_854_776e18..9.223_372_036_854_776e18).contains(&f) {
+            // The bounds are -2^63 and 2^63, and every float between them with
+            // no fractional part is a valid `i64`.
+            Ok(f as i64)
+        } else {
+            Err(ConversionError::OutOfRange)
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for bool {
+    type Error = ConversionError;
+
+    fn try_from(value: &JsonValue) -> Result<bool, ConversionError> {
+        value
+            .as_bool()
+            .ok_or(ConversionError::UnexpectedType("a boolean"))
+    }
+}
+
+impl TryFrom<&JsonValue> for String {
+    type Error = ConversionError;
+
+    fn try_from(value: &JsonValue) -> Result<String, ConversionError> {
+        match value {
+            JsonValue::Str(s) => Ok(s.clone()),
+            _ => Err(ConversionError::UnexpectedType("a string")),
+        }
+    }
+}
+
+impl TryFrom<JsonValue> for f64 {
+    type Error = ConversionError;
+
+    fn try_from(value: JsonValue) -> Result<f64, ConversionError> {
+        f64::try_from(&value)
+    }
+}
+
+impl TryFrom<JsonValue> for i64 {
+    type Error = ConversionError;
+
+    fn try_from(value: JsonValue) -> Result<i64, ConversionError> {
+        i64::try_from(&value)
+    }
+}
+
+impl TryFrom<JsonValue> for bool {
+    type Error = ConversionError;
+
+    fn try_from(value: JsonValue) -> Result<bool, ConversionError> {
+        bool::try_from(&value)
+    }
+}
+
+impl TryFrom<JsonValue> for String {
+    type Error = ConversionError;
+
+    fn try_from(value: JsonValue) -> Result<String, ConversionError> {
+        match value {
+            JsonValue::Str(s) => Ok(s),
+            _ => Err(ConversionError::UnexpectedType("a string")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(f64::try_from(JsonValue::Int(2)), Ok(2.));
+        assert_eq!(f64::try_from(&JsonValue::Number(1.5)), Ok(1.5));
+        assert_eq!(i64::try_from(JsonValue::Int(-3)), Ok(-3));
+        assert_eq!(i64::try_from(JsonValue::Number(5.0)), Ok(5));
+        assert_eq!(
+            i64::try_from(JsonValue::Number(-9.223_372_036_854_776e18)),
+            Ok(i64::MIN)
+        );
+        assert_eq!(bool::try_from(&JsonValue::Bool(true)), Ok(true));
+        assert_eq!(String::try_from(JsonValue::from("s")), Ok("s".to_string()));
+
+        assert_eq!(
+            i64::try_from(JsonValue::Number(1e300)),
+            Err(ConversionError::OutOfRange)
+        );
+        assert_eq!(
+            i64::try_from(JsonValue::Number(9.223_372_036_854_776e18)),
+            Err(ConversionError::OutOfRange)
+        );
+        assert_eq!(
+            i64::try_from(JsonValue::Number(f64::NAN)),
+            Err(ConversionError::NotAnInteger)
+        );
+        assert_eq!(
+            i64::try_from(&JsonValue::Number(0.5)),
+            Err(ConversionError::NotAnInteger)
+        );
+        assert_eq!(
+            f64::try_from(JsonValue::Null),
+            Err(ConversionError::UnexpectedType("a number"))
+        );
+        assert_eq!(
+            String::try_from(&JsonValue::Int(1))
+                .unwrap_err()
+                .to_string(),
+            "expected a string"
+        );
+        assert!(bool::try_from(JsonValue::from("true")).is_err());
+    }
+}
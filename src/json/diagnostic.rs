@@ -0,0 +1,324 @@
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use super::{
+    error::line_column, parse_document_with, Builder, JsonValue, Map, ParseError, ParseOptions,
+    ValueBuilder,
+};
+
+/// What non-standard input a [`Diagnostic`] notes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// A comma after the last element of an array or member of an object.
+    TrailingComma,
+    /// A `//` or `/* */` comment.
+    Comment,
+    /// A member with the same key as an earlier one in its object.
+    DuplicateKey(String),
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticKind::TrailingComma => f.write_str("trailing comma"),
+            DiagnosticKind::Comment => f.write_str("comment"),
+            DiagnosticKind::DuplicateKey(key) => write!(f, "duplicate key {:?}", key),
+        }
+    }
+}
+
+/// Non-standard input that lenient [`ParseOptions`] accepted, from
+/// [`parse_with_diagnostics`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The byte offset of the start of what is noted.
+    pub offset: usize,
+    /// The 1-based line of the start of what is noted.
+    pub line: usize,
+    /// The 1-based column of the start of what is noted, counted in
+    /// characters.
+    pub column: usize,
+    pub kind: DiagnosticKind,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.line, self.column
+        )
+    }
+}
+
+/// Builds a [`JsonValue`] like [`ValueBuilder`], noting where the input
+/// deviates from standard JSON.
+#[derive(Default)]
+struct DiagnosticBuilder<'a> {
+    found: Vec<(&'a str, DiagnosticKind)>,
+    /// The comments, each as the input starting with it and its length.
+    comments: Vec<(&'a str, usize)>,
+}
+
+impl<'a> DiagnosticBuilder<'a> {
+    /// The byte offset of `at` in `input`, with its line and column.
+    fn locate(input: &str, at: &str) -> (usize, usize, usize) {
+        let offset = input.len() - at.len();
+        let (line, column) = line_column(input, offset);
+        (offset, line, column)
+    }
+}
+
+impl<'a> Builder<'a> for DiagnosticBuilder<'a> {
+    type Value = JsonValue;
+    type Object = Map;
+
+    fn null(&mut self) -> JsonValue {
+        ValueBuilder.null()
+    }
+
+    fn bool(&mut self, b: bool) -> JsonValue {
+        ValueBuilder.bool(b)
+    }
+
+    fn int(&mut self, i: i64) -> JsonValue {
+        ValueBuilder.int(i)
+    }
+
+    fn float(&mut self, f: f64) -> JsonValue {
+        ValueBuilder.float(f)
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) -> JsonValue {
+        ValueBuilder.string(s)
+    }
+
+    fn raw_number(&mut self, token: &'a str) -> JsonValue {
+        ValueBuilder.raw_number(token)
+    }
+
+    #[cfg(feature = "bigint")]
+    fn big_int(&mut self, token: &'a str) -> JsonValue {
+        ValueBuilder.big_int(token)
+    }
+
+    fn array(&mut self, items: Vec<JsonValue>) -> JsonValue {
+        ValueBuilder.array(items)
+    }
+
+    fn new_object(&mut self, capacity: usize) -> Map {
+        ValueBuilder.new_object(capacity)
+    }
+
+    fn contains_key(&self, object: &Map, key: &str) -> bool {
+        ValueBuilder.contains_key(object, key)
+    }
+
+    fn insert(&mut self, object: &mut Map, key: Cow<'a, str>, value: JsonValue) {
+        ValueBuilder.insert(object, key, value);
+    }
+
+    fn object(&mut self, object: Map) -> JsonValue {
+        ValueBuilder.object(object)
+    }
+
+    fn hooked_number(&mut self, value: JsonValue) -> Option<JsonValue> {
+        ValueBuilder.hooked_number(value)
+    }
+
+    fn duplicate_key(&mut self, at: &'a str, key: &str) {
+        self.found
+            .push((at, DiagnosticKind::DuplicateKey(key.into())));
+    }
+
+    fn trailing_comma(&mut self, at: &'a str) {
+        self.found.push((at, DiagnosticKind::TrailingComma));
+    }
+
+    fn comments(&mut self, at: &'a str, len: usize) {
+        // Outside comments the gap is whitespace, which never holds a `/`.
+        let mut start = 0;
+        while let Some(i) = at[start..len].find('/') {
+            let comment = &at[start + i..];
+            let end = if comment.starts_with("//") {
+                "\n"
+            } else {
+                "*/"
+            };
+            // A line comment ends before its newline, a block comment after
+            // its `*/`.
+            let length = match comment[2..len - start - i].find(end) {
+                Some(j) if end == "\n" => 2 + j,
+                Some(j) => 2 + j + end.len(),
+                None => len - start - i,
+            };
+            self.comments.push((comment, length));
+            start += i + length;
+        }
+    }
+}
+
+/// Parses `input` as a whole JSON document like
+/// [`parse_with_options`](super::parse_with_options), noting each trailing
+/// comma, comment and duplicate key that `options` let through, in the order
+/// they appear in the input.
+///
+/// Duplicate keys are noted under every [`DuplicateKeyPolicy`] that accepts
+/// them, so a document without diagnostics is also valid with the default
+/// options and means the same to every reader.
+///
+/// [`DuplicateKeyPolicy`]: super::DuplicateKeyPolicy
+pub fn parse_with_diagnostics(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<(JsonValue, Vec<Diagnostic>), ParseError> {
+    let mut builder = DiagnosticBuilder::default();
+    let value =
+        parse_document_with(input, options, &mut builder).map_err(|e| ParseError::new(input, e))?;
+    let comments = builder
+        .comments
+        .iter()
+        .map(|&(at, _)| (at, DiagnosticKind::Comment));
+    let mut diagnostics: Vec<_> = builder
+        .found
+        .into_iter()
+        .chain(comments)
+        .map(|(at, kind)| {
+            let (offset, line, column) = DiagnosticBuilder::locate(input, at);
+            Diagnostic {
+                offset,
+                line,
+                column,
+                kind,
+            }
+        })
+        .collect();
+    diagnostics.sort_by_key(|d| d.offset);
+    Ok((value, diagnostics))
+}
+
+/// Parses `input` as a whole JSON document like
+/// [`parse_with_options`](super::parse_with_options), also returning the text
+/// of each comment with its byte offset, in the order they appear.
+///
+/// Comments are only accepted with
+/// [`allow_comments`](ParseOptions::allow_comments). Their text includes the
+/// `//` or the `/*` and `*/`, but not the newline ending a line comment, so
+/// that an editor can place them back where they were.
+pub fn parse_with_comments(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<(JsonValue, Vec<(usize, String)>), ParseError> {
+    let mut builder = DiagnosticBuilder::default();
+    let value =
+        parse_document_with(input, options, &mut builder).map_err(|e| ParseError::new(input, e))?;
+    let comments = builder
+        .comments
+        .into_iter()
+        .map(|(at, len)| {
+            (
+                DiagnosticBuilder::locate(input, at).0,
+                at[..len].to_string(),
+            )
+        })
+        .collect();
+    Ok((value, comments))
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{parse_complete, DuplicateKeyPolicy};
+    use super::*;
+
+    #[test]
+    fn test_diagnostics() {
+        let input = "{\n  \"a\": 1,\n  \"b\": [1, 2,],\n  \"a\": 3\n}";
+        let options = ParseOptions {
+            allow_trailing_comma: true,
+            ..Default::default()
+        };
+        let (value, diagnostics) = parse_with_diagnostics(input, &options).unwrap();
+        assert_eq!(value, parse_complete(r#"{"a": 3, "b": [1, 2]}"#).unwrap());
+        assert_eq!(
+            diagnostics,
+            [
+                Diagnostic {
+                    offset: input.find(",]").unwrap(),
+                    line: 3,
+                    column: 13,
+                    kind: DiagnosticKind::TrailingComma,
+                },
+                Diagnostic {
+                    offset: input.rfind("\"a\"").unwrap(),
+                    line: 4,
+                    column: 3,
+                    kind: DiagnosticKind::DuplicateKey("a".into()),
+                },
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "trailing comma at line 3, column 13"
+        );
+        assert_eq!(
+            diagnostics[1].to_string(),
+            "duplicate key \"a\" at line 4, column 3"
+        );
+
+        let input = "// head\n[1, /* a // b */ 2 /**/, {\"k\": 1, \"k\": 2} ] // tail";
+        let options = ParseOptions {
+            allow_comments: true,
+            duplicate_keys: DuplicateKeyPolicy::Collect,
+            ..Default::default()
+        };
+        let (_, diagnostics) = parse_with_diagnostics(input, &options).unwrap();
+        let found: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (&input[d.offset..d.offset + 2], d.kind.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("//", DiagnosticKind::Comment),
+                ("/*", DiagnosticKind::Comment),
+                ("/*", DiagnosticKind::Comment),
+                ("\"k", DiagnosticKind::DuplicateKey("k".into())),
+                ("//", DiagnosticKind::Comment),
+            ]
+        );
+        assert_eq!(diagnostics[3].offset, input.rfind("\"k\"").unwrap());
+
+        let (_, diagnostics) = parse_with_diagnostics("[1, 2]", &options).unwrap();
+        assert!(diagnostics.is_empty());
+        assert!(parse_with_diagnostics("[1,]", &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_comments() {
+        let input = "{\n  // the name\n  \"name\": \"x\", /* inline */\n  \"n\": 1 /**/}// end";
+        let options = ParseOptions {
+            allow_comments: true,
+            ..Default::default()
+        };
+        let (value, comments) = parse_with_comments(input, &options).unwrap();
+        assert_eq!(value, parse_complete(r#"{"name": "x", "n": 1}"#).unwrap());
+        let texts: Vec<_> = comments.iter().map(|(_, text)| text.as_str()).collect();
+        assert_eq!(texts, ["// the name", "/* inline */", "/**/", "// end"]);
+        for (offset, text) in &comments {
+            assert!(input[*offset..].starts_with(text.as_str()));
+        }
+        assert_eq!(comments[0].0, 4);
+        assert_eq!(
+            &input[comments[0].0 + comments[0].1.len()..][..9],
+            "\n  \"name\""
+        );
+
+        assert!(parse_with_comments("[1] // c", &ParseOptions::default()).is_err());
+        let (_, comments) = parse_with_comments("[1, 2]", &options).unwrap();
+        assert!(comments.is_empty());
+    }
+}
@@ -0,0 +1,262 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt;
+
+use nom::error::{ContextError, FromExternalError};
+
+use super::ExtractError;
+
+/// What went wrong while parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    /// A nom combinator failed.
+    Nom(nom::error::ErrorKind),
+    /// An expected character was missing.
+    Char(char),
+    /// An object contained the same key twice.
+    DuplicateKey(String),
+    /// Arrays and objects were nested deeper than the configured limit.
+    DepthLimitExceeded,
+    /// The input bytes were not valid UTF-8.
+    InvalidUtf8,
+    /// The document was longer than the configured limit.
+    InputTooLarge,
+    /// The document had more elements and members than the configured limit.
+    TooManyElements,
+    /// A string or key was longer than the configured limit.
+    StringTooLong,
+    /// An integer could not be represented exactly as an `f64`.
+    ImpreciseInteger,
+    /// The document was not an array or an object, but one was required.
+    ContainerRootRequired,
+    /// The input was empty or held only whitespace.
+    EmptyInput,
+    /// A comma in an array was followed by another comma or the end of the
+    /// array instead of an element.
+    ExpectedValue,
+    /// A number was malformed, as the text given, which runs to the first
+    /// character that cannot be part of a number.
+    InvalidNumber(String),
+    /// A number was too large in magnitude for an `f64`.
+    NumberOutOfRange,
+    /// An element or member was followed by something other than a comma or
+    /// the given closing bracket of its container.
+    ExpectedSeparator(char),
+    /// The input ended inside the array or object opened by the given
+    /// bracket, where the error is located.
+    Unclosed(char),
+    /// A byte order mark or NUL appeared between tokens, as happens in
+    /// corrupted or badly concatenated files.
+    UnexpectedChar(char),
+    /// A JSON Pointer given to [`parse_subtree`](super::parse_subtree) does
+    /// not refer to a value in the document.
+    PointerNotFound,
+    /// A row read by [`parse_rows`](super::parse_rows) did not have the given
+    /// number of elements.
+    RowArity(usize),
+    /// A row read by [`parse_rows`](super::parse_rows) could not be
+    /// converted, for the reason given, located by the error's path.
+    InvalidRow(Box<ExtractError>),
+    /// The input ended partway through a value, which more input could
+    /// complete, as reported by [`parse_streaming`](super::parse_streaming).
+    /// `at` is the byte offset of the innermost string, number, keyword,
+    /// array or object left unfinished.
+    NeedMoreInput { at: usize },
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Nom(nom::error::ErrorKind::Eof) => f.write_str("unexpected trailing input"),
+            ErrorKind::Nom(_) => f.write_str("unexpected input"),
+            ErrorKind::Char(c) => write!(f, "expected '{}'", c),
+            ErrorKind::DuplicateKey(key) => write!(f, "duplicate key {:?}", key),
+            ErrorKind::DepthLimitExceeded => f.write_str("maximum nesting depth exceeded"),
+            ErrorKind::InvalidUtf8 => f.write_str("invalid UTF-8"),
+            ErrorKind::InputTooLarge => f.write_str("input too large"),
+            ErrorKind::TooManyElements => f.write_str("too many elements"),
+            ErrorKind::StringTooLong => f.write_str("string too long"),
+            ErrorKind::ImpreciseInteger => f.write_str("integer not exactly representable"),
+            ErrorKind::ContainerRootRequired => f.write_str("expected an array or object"),
+            ErrorKind::EmptyInput => f.write_str("unexpected end of input: expected a JSON value"),
+            ErrorKind::InvalidNumber(token) => write!(f, "invalid number literal '{}'", token),
+            ErrorKind::NumberOutOfRange => f.write_str("number out of range"),
+            ErrorKind::ExpectedValue => f.write_str("expected value after ','"),
+            ErrorKind::ExpectedSeparator(close) => write!(f, "expected ',' or '{}'", close),
+            ErrorKind::Unclosed('[') => f.write_str("unclosed array"),
+            ErrorKind::Unclosed(_) => f.write_str("unclosed object"),
+            ErrorKind::UnexpectedChar(c) => write!(f, "unexpected character U+{:04X}", *c as u32),
+            ErrorKind::PointerNotFound => f.write_str("no value at the JSON Pointer"),
+            ErrorKind::RowArity(len) => write!(f, "expected a row of {} elements", len),
+            ErrorKind::InvalidRow(error) => match &**error {
+                ExtractError::MissingKey { key, .. } => {
+                    write!(f, "invalid row: missing key {:?}", key)
+                }
+                ExtractError::Invalid { error, .. } => write!(f, "invalid row: {}", error),
+            },
+            ErrorKind::NeedMoreInput { at } => {
+                write!(f, "unexpected end of input in the value at byte {}", at)
+            }
+        }
+    }
+}
+
+/// The error type of the parsers in this module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error<'a> {
+    /// The remaining input at the point of failure.
+    pub input: &'a str,
+    pub kind: ErrorKind,
+    /// The contexts the failure occurred in, innermost first.
+    pub context: Vec<&'static str>,
+    /// The JSON Pointer of the value the failure occurred in, such as
+    /// `/users/3/email`, or empty at the top level.
+    pub path: String,
+    /// What would have been accepted instead, as for
+    /// [`ParseError::expected`].
+    pub expected: &'static [&'static str],
+}
+
+impl<'a> Error<'a> {
+    pub fn new(input: &'a str, kind: ErrorKind) -> Self {
+        Error {
+            input,
+            kind,
+            context: Vec::new(),
+            path: String::new(),
+            expected: &[],
+        }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: nom::error::ErrorKind) -> Self {
+        Error::new(input, ErrorKind::Nom(kind))
+    }
+
+    fn append(_: &'a str, _: nom::error::ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        Error::new(input, ErrorKind::Char(c))
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    fn add_context(_: &'a str, context: &'static str, mut other: Self) -> Self {
+        other.context.push(context);
+        other
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(input: &'a str, kind: nom::error::ErrorKind, _: E) -> Self {
+        Error::new(input, ErrorKind::Nom(kind))
+    }
+}
+
+/// A parse failure located in the original input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// The byte offset of the failure.
+    pub offset: usize,
+    /// The 1-based line of the failure.
+    pub line: usize,
+    /// The 1-based column of the failure, counted in characters.
+    pub column: usize,
+    pub kind: ErrorKind,
+    /// The contexts the failure occurred in, innermost first.
+    pub context: Vec<&'static str>,
+    /// The JSON Pointer of the value the failure occurred in, or empty at the
+    /// top level.
+    pub path: String,
+    /// The tokens that would have been accepted at the failure, for
+    /// suggesting fixes: punctuation as itself, such as `,` and `}`, and
+    /// values by their kind, as `string`, `number`, `object`, `array`, `bool`
+    /// and `null`. Empty when the parser cannot tell. For
+    /// [`ErrorKind::Unclosed`], these are what would have been accepted at
+    /// the end of the input rather than at the opener.
+    pub expected: &'static [&'static str],
+}
+
+impl ParseError {
+    /// Locates `error`, produced while parsing `input`, within `input`.
+    pub fn new(input: &str, error: nom::Err<Error<'_>>) -> Self {
+        let error = match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            nom::Err::Incomplete(_) => {
+                Error::new("", ErrorKind::Nom(nom::error::ErrorKind::Complete))
+            }
+        };
+        let expected = match error.kind {
+            ErrorKind::Char(c) if error.expected.is_empty() => char_token(c),
+            _ => error.expected,
+        };
+        ParseError {
+            context: error.context,
+            path: error.path,
+            expected,
+            ..ParseError::at(input, input.len() - error.input.len(), error.kind)
+        }
+    }
+
+    /// An error of `kind` at byte `offset` of `input`.
+    pub(super) fn at(input: &str, offset: usize, kind: ErrorKind) -> Self {
+        let (line, column) = line_column(input, offset);
+        ParseError {
+            offset,
+            line,
+            column,
+            kind,
+            context: Vec::new(),
+            path: String::new(),
+            expected: &[],
+        }
+    }
+}
+
+/// The 1-based line and column, counted in characters, of byte `offset` of
+/// `input`.
+pub(super) fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    let consumed = &input[..offset];
+    let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+    (
+        consumed.matches('\n').count() + 1,
+        consumed[line_start..].chars().count() + 1,
+    )
+}
+
+/// Names the punctuation `c` as [`ParseError::expected`] does.
+fn char_token(c: char) -> &'static [&'static str] {
+    match c {
+        '"' => &["\""],
+        ':' => &[":"],
+        ',' => &[","],
+        '[' => &["["],
+        ']' => &["]"],
+        '{' => &["{"],
+        '}' => &["}"],
+        _ => &[],
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "parse error at line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )?;
+        match self.context.first() {
+            Some(&context) if context != "parse" => write!(f, " in {}", context)?,
+            _ => {}
+        }
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
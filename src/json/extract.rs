@@ -0,0 +1,332 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{convert::TryFrom, fmt};
+
+use super::{pointer::escape, ConversionError, JsonValue};
+
+/// Why [`FromJson`] could not extract a value, located by the JSON Pointer of
+/// the value relative to the one extraction started from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtractError {
+    /// The object lacks a key that is not optional.
+    MissingKey { path: String, key: String },
+    /// The value could not be converted to the expected type.
+    Invalid {
+        path: String,
+        error: ConversionError,
+    },
+}
+
+impl ExtractError {
+    /// The JSON Pointer of the object lacking the key or the invalid value.
+    pub fn path(&self) -> &str {
+        match self {
+            ExtractError::MissingKey { path, .. } | ExtractError::Invalid { path, .. } => path,
+        }
+    }
+
+    /// Locates the error inside the member or element `segment` of the value
+    /// its path is relative to.
+    pub(super) fn within(mut self, segment: &str) -> Self {
+        match &mut self {
+            ExtractError::MissingKey { path, .. } | ExtractError::Invalid { path, .. } => {
+                path.insert_str(0, &escape(segment));
+                path.insert(0, '/');
+            }
+        }
+        self
+    }
+}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtractError::MissingKey { path, key } => {
+                write!(f, "missing key {:?} at {:?}", key, path)
+            }
+            ExtractError::Invalid { path, error } => write!(f, "{} at {:?}", error, path),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ExtractError {}
+
+impl From<ConversionError> for ExtractError {
+    fn from(error: ConversionError) -> Self {
+        ExtractError::Invalid {
+            path: String::new(),
+            error,
+        }
+    }
+}
+
+/// A type that can be extracted from a [`JsonValue`] without serde.
+///
+/// Implementing it for a struct usually takes one
+/// [`extract`](JsonValue::extract) per field:
+///
+/// ```
+/// use parser::json::{parse_complete, ExtractError, FromJson, JsonValue};
+///
+/// struct User {
+///     name: String,
+///     tags: Vec<String>,
+/// }
+///
+/// impl FromJson for User {
+///     fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+///         Ok(User {
+///             name: value.extract("name")?,
+///             tags: value.extract("tags")?,
+///         })
+///     }
+/// }
+///
+/// let value = parse_complete(r#"{"name": "a", "tags": ["x"]}"#).unwrap();
+/// let user = User::from_json(&value).unwrap();
+/// assert_eq!((user.name.as_str(), user.tags.len()), ("a", 1));
+/// ```
+pub trait FromJson: Sized {
+    fn from_json(value: &JsonValue) -> Result<Self, ExtractError>;
+
+    /// The value to extract for an absent member, or `None` if the member is
+    /// required. Only `Option` gives one.
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+impl FromJson for JsonValue {
+    fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+        Ok(bool::try_from(value)?)
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+        Ok(String::try_from(value)?)
+    }
+}
+
+impl FromJson for f64 {
+    fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+        Ok(f64::try_from(value)?)
+    }
+}
+
+/// Converts any number, rounding it to the nearest `f32`.
+impl FromJson for f32 {
+    fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+        Ok(f64::try_from(value)? as f32)
+    }
+}
+
+/// Implements [`FromJson`] for integer types, through the `i64`
+/// conversion.
+macro_rules! from_json_int {
+    ($($t:ty),*) => {
+        $(
+            impl FromJson for $t {
+                fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+                    let i = i64::try_from(value)?;
+                    Ok(<$t>::try_from(i).map_err(|_| ConversionError::OutOfRange)?)
+                }
+            }
+        )*
+    };
+}
+
+from_json_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Extracts `None` from `null` or an absent member.
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+        match value {
+            JsonValue::Null => Ok(None),
+            _ => T::from_json(value).map(Some),
+        }
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+/// A member of an object that may be absent, `null` or have a value, from
+/// [`JsonValue::field`], for patch semantics where leaving a field out and
+/// setting it to `null` mean different things.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field<T> {
+    /// The object has no member with the key.
+    Absent,
+    /// The member is `null`.
+    Null,
+    /// The member has a value other than `null`.
+    Present(T),
+}
+
+impl<T> Field<T> {
+    /// Converts to `None` if absent, `Some(None)` if `null` and
+    /// `Some(Some(value))` otherwise.
+    pub fn into_option(self) -> Option<Option<T>> {
+        match self {
+            Field::Absent => None,
+            Field::Null => Some(None),
+            Field::Present(value) => Some(Some(value)),
+        }
+    }
+}
+
+/// Extracts [`Field::Null`] from `null` and [`Field::Absent`] from an absent
+/// member.
+impl<T: FromJson> FromJson for Field<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+        match value {
+            JsonValue::Null => Ok(Field::Null),
+            _ => T::from_json(value).map(Field::Present),
+        }
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(Field::Absent)
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+        let items = value
+            .as_array()
+            .ok_or(ConversionError::UnexpectedType("an array"))?;
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| T::from_json(item).map_err(|e| e.within(&i.to_string())))
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: FromJson> FromJson for std::collections::HashMap<String, T> {
+    fn from_json(value: &JsonValue) -> Result<Self, ExtractError> {
+        let object = value
+            .as_object()
+            .ok_or(ConversionError::UnexpectedType("an object"))?;
+        object
+            .iter()
+            .map(|(k, v)| match T::from_json(v) {
+                Ok(v) => Ok((k.clone(), v)),
+                Err(e) => Err(e.within(k)),
+            })
+            .collect()
+    }
+}
+
+impl JsonValue {
+    /// Extracts the member `key` of an object as a `T`, for implementing
+    /// [`FromJson`].
+    ///
+    /// An absent member is an error unless `T` is an `Option`, and errors
+    /// inside the member are located relative to this object.
+    pub fn extract<T: FromJson>(&self, key: &str) -> Result<T, ExtractError> {
+        let object = self
+            .as_object()
+            .ok_or(ConversionError::UnexpectedType("an object"))?;
+        match object.get(key) {
+            Some(value) => T::from_json(value).map_err(|e| e.within(key)),
+            None => T::from_missing().ok_or_else(|| ExtractError::MissingKey {
+                path: String::new(),
+                key: key.into(),
+            }),
+        }
+    }
+
+    /// Extracts the member `key` of an object like
+    /// [`extract`](JsonValue::extract), telling an absent member apart from
+    /// one that is `null`, which `Option` does not.
+    pub fn field<T: FromJson>(&self, key: &str) -> Result<Field<T>, ExtractError> {
+        self.extract(key)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let value =
+            parse_complete(r#"{"grid": [[1, 2], [3], []], "name": "a~b", "n": null}"#).unwrap();
+        let grid: Vec<Vec<i64>> = value.extract("grid").unwrap();
+        assert_eq!(grid, [vec![1, 2], vec![3], vec![]]);
+        assert_eq!(value.extract::<String>("name").unwrap(), "a~b");
+        assert_eq!(value.extract::<Option<String>>("missing"), Ok(None));
+        assert_eq!(value.extract::<Option<String>>("n"), Ok(None));
+        assert_eq!(
+            value.extract::<Option<String>>("name"),
+            Ok(Some("a~b".into()))
+        );
+        assert_eq!(value.extract::<u8>("grid").unwrap_err().path(), "/grid");
+
+        assert_eq!(
+            value.extract::<String>("missing"),
+            Err(ExtractError::MissingKey {
+                path: String::new(),
+                key: "missing".into(),
+            })
+        );
+        let error = value.extract::<Vec<Vec<bool>>>("grid").unwrap_err();
+        assert_eq!(
+            error,
+            ExtractError::Invalid {
+                path: "/grid/0/0".into(),
+                error: ConversionError::UnexpectedType("a boolean"),
+            }
+        );
+        assert_eq!(error.to_string(), "expected a boolean at \"/grid/0/0\"");
+        assert_eq!(i32::from_json(&JsonValue::Int(-5)), Ok(-5));
+        assert!(JsonValue::Int(1).extract::<i64>("a").is_err());
+
+        #[cfg(feature = "std")]
+        {
+            let value = parse_complete(r#"{"a/b": {"k": 300}}"#).unwrap();
+            let error = value
+                .extract::<std::collections::HashMap<String, u8>>("a/b")
+                .unwrap_err();
+            assert_eq!(error.path(), "/a~1b/k");
+        }
+    }
+
+    #[test]
+    fn test_field() {
+        let value = parse_complete(r#"{"name": "a", "email": null, "age": "x"}"#).unwrap();
+        assert_eq!(value.field("name"), Ok(Field::Present("a".to_string())));
+        assert_eq!(value.field::<String>("email"), Ok(Field::Null));
+        assert_eq!(value.field::<String>("phone"), Ok(Field::Absent));
+        assert_eq!(value.field::<u8>("age").unwrap_err().path(), "/age");
+        assert!(JsonValue::Null.field::<String>("name").is_err());
+
+        assert_eq!(value.field::<String>("phone").unwrap().into_option(), None);
+        assert_eq!(
+            value.field::<String>("email").unwrap().into_option(),
+            Some(None)
+        );
+        assert_eq!(
+            value.field::<String>("name").unwrap().into_option(),
+            Some(Some("a".to_string()))
+        );
+        let items = Vec::<Field<i64>>::from_json(&parse_complete("[1, null]").unwrap());
+        assert_eq!(items, Ok(vec![Field::Present(1), Field::Null]));
+    }
+}
@@ -0,0 +1,138 @@
+use alloc::{format, string::ToString};
+
+use super::{JsonValue, Map};
+
+/// Adds the leaves of `value`, which is found at the flattened `key`, to
+/// `flat`.
+fn flatten_into(flat: &mut Map, key: &str, value: &JsonValue, sep: char) {
+    match value {
+        JsonValue::Object(map) if !map.is_empty() => {
+            for (k, v) in map {
+                flatten_into(flat, &format!("{}{}{}", key, sep, k), v, sep);
+            }
+        }
+        JsonValue::Array(items) if !items.is_empty() => {
+            for (i, v) in items.iter().enumerate() {
+                flatten_into(flat, &format!("{}{}{}", key, sep, i), v, sep);
+            }
+        }
+        leaf => {
+            flat.insert(key.to_string(), leaf.clone());
+        }
+    }
+}
+
+impl JsonValue {
+    /// Flattens nested objects and arrays into a single object whose keys
+    /// join the path to each leaf with `sep`, so that
+    /// `{"a": {"b": [1, 2]}}` becomes `{"a.b.0": 1, "a.b.1": 2}` with `.`.
+    ///
+    /// Empty objects and arrays are kept as leaves. Keys that already contain
+    /// `sep` are joined as they are, so [`unflatten`](JsonValue::unflatten)
+    /// splits them too. Other values are returned unchanged.
+    pub fn flatten(&self, sep: char) -> JsonValue {
+        let mut flat = Map::default();
+        match self {
+            JsonValue::Object(map) => {
+                for (k, v) in map {
+                    flatten_into(&mut flat, k, v, sep);
+                }
+            }
+            JsonValue::Array(items) => {
+                for (i, v) in items.iter().enumerate() {
+                    flatten_into(&mut flat, &i.to_string(), v, sep);
+                }
+            }
+            other => return other.clone(),
+        }
+        JsonValue::Object(flat)
+    }
+
+    /// Expands an object with keys joined by `sep` into nested objects, the
+    /// inverse of [`flatten`](JsonValue::flatten) for documents without
+    /// arrays: `{"a.b.c": 1, "a.b.d": 2}` becomes `{"a": {"b": {"c": 1, "d": 2}}}`.
+    ///
+    /// Array indices come back as object keys, since `"0"` may as well have
+    /// been one. When a key is both a leaf and the prefix of other keys, as
+    /// `a` is in `{"a": 1, "a.b": 2}`, the key that comes later wins. Other
+    /// values are returned unchanged.
+    pub fn unflatten(&self, sep: char) -> JsonValue {
+        let map = match self {
+            JsonValue::Object(map) => map,
+            other => return other.clone(),
+        };
+        let mut root = JsonValue::Object(Map::default());
+        for (key, value) in map {
+            let mut node = &mut root;
+            let mut parts = key.split(sep);
+            let mut part = parts.next().unwrap_or_default();
+            for next in parts {
+                node = object_mut(node)
+                    .entry(part.to_string())
+                    .or_insert(JsonValue::Null);
+                part = next;
+            }
+            object_mut(node).insert(part.to_string(), value.clone());
+        }
+        root
+    }
+}
+
+/// Returns the members of `node`, replacing it with an empty object first if
+/// it is not one.
+fn object_mut(node: &mut JsonValue) -> &mut Map {
+    if !matches!(node, JsonValue::Object(_)) {
+        *node = JsonValue::Object(Map::default());
+    }
+    match node {
+        JsonValue::Object(map) => map,
+        _ => unreachable!("the node was just made an object"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_flatten() {
+        let nested =
+            parse_complete(r#"{"a": {"b": 1, "c": {"d": "x"}}, "e": true, "f": {}}"#).unwrap();
+        let flat = nested.flatten('.');
+        assert_eq!(
+            flat,
+            parse_complete(r#"{"a.b": 1, "a.c.d": "x", "e": true, "f": {}}"#).unwrap()
+        );
+        assert_eq!(flat.unflatten('.'), nested);
+
+        let value = parse_complete(r#"{"a": [{"b": 1}, [2]], "c": []}"#).unwrap();
+        assert_eq!(
+            value.flatten('/'),
+            parse_complete(r#"{"a/0/b": 1, "a/1/0": 2, "c": []}"#).unwrap()
+        );
+        assert_eq!(
+            value.flatten('/').unflatten('/'),
+            parse_complete(r#"{"a": {"0": {"b": 1}, "1": {"0": 2}}, "c": []}"#).unwrap()
+        );
+        assert_eq!(
+            parse_complete("[1, [2]]").unwrap().flatten('.'),
+            parse_complete(r#"{"0": 1, "1.0": 2}"#).unwrap()
+        );
+        assert_eq!(JsonValue::Int(1).flatten('.'), JsonValue::Int(1));
+    }
+
+    #[cfg(all(
+        feature = "preserve_order",
+        not(feature = "map-btree"),
+        not(feature = "map-hash")
+    ))]
+    #[test]
+    fn test_unflatten_conflict() {
+        let flat = parse_complete(r#"{"a": 1, "a.b": 2, "c.d": 3, "c": 4}"#).unwrap();
+        assert_eq!(
+            flat.unflatten('.'),
+            parse_complete(r#"{"a": {"b": 2}, "c": 4}"#).unwrap()
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::mem;
+
+use super::{
+    map_with_capacity, parse_document_with, Builder, JsonValue, MapImpl, ParseError, ParseOptions,
+};
+
+/// The map backing [`IntKeyedValue::Object`], ordered like [`Map`](super::Map).
+pub type IntKeyedMap = MapImpl<String, IntKeyedValue>;
+
+/// The map backing [`IntKeyedValue::IntObject`], ordered like
+/// [`Map`](super::Map).
+pub type IntMap = MapImpl<u32, IntKeyedValue>;
+
+/// A parsed value whose objects with only integer keys, such as `{"1": ..,
+/// "20": ..}`, are keyed by `u32` instead of by string, from
+/// [`parse_int_keyed`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntKeyedValue {
+    Number(f64),
+    Int(i64),
+    Str(String),
+    Bool(bool),
+    Null,
+    /// An object with a key that is not an integer key.
+    Object(IntKeyedMap),
+    /// An object whose every key is a `u32` written in decimal without a
+    /// sign or leading zeros, so that it is written back the same way.
+    IntObject(IntMap),
+    Array(Vec<IntKeyedValue>),
+}
+
+/// Reads `key` as the key of an [`IntKeyedValue::IntObject`].
+fn int_key(key: &str) -> Option<u32> {
+    if key.starts_with('+') || (key.len() > 1 && key.starts_with('0')) {
+        return None;
+    }
+    key.parse().ok()
+}
+
+impl IntKeyedValue {
+    /// Returns the member `key` of an object, whichever way it is keyed, or
+    /// `None` when the key is missing or the value is not an object.
+    pub fn get(&self, key: &str) -> Option<&IntKeyedValue> {
+        match self {
+            IntKeyedValue::Object(map) => map.get(key),
+            IntKeyedValue::IntObject(map) => map.get(&int_key(key)?),
+            _ => None,
+        }
+    }
+
+    /// Returns the member with the integer key `key` of an object, like
+    /// [`get`](IntKeyedValue::get) with the key written in decimal.
+    pub fn get_int(&self, key: u32) -> Option<&IntKeyedValue> {
+        match self {
+            IntKeyedValue::Object(map) => map.get(key.to_string().as_str()),
+            IntKeyedValue::IntObject(map) => map.get(&key),
+            _ => None,
+        }
+    }
+
+    /// Converts the value, writing integer keys back as strings.
+    pub fn into_owned(self) -> JsonValue {
+        match self {
+            IntKeyedValue::Number(f) => JsonValue::Number(f),
+            IntKeyedValue::Int(i) => JsonValue::Int(i),
+            IntKeyedValue::Str(s) => JsonValue::Str(s),
+            IntKeyedValue::Bool(b) => JsonValue::Bool(b),
+            IntKeyedValue::Null => JsonValue::Null,
+            IntKeyedValue::Object(map) => {
+                JsonValue::Object(map.into_iter().map(|(k, v)| (k, v.into_owned())).collect())
+            }
+            IntKeyedValue::IntObject(map) => JsonValue::Object(
+                map.into_iter()
+                    .map(|(k, v)| (k.to_string(), v.into_owned()))
+                    .collect(),
+            ),
+            IntKeyedValue::Array(items) => {
+                JsonValue::Array(items.into_iter().map(IntKeyedValue::into_owned).collect())
+            }
+        }
+    }
+}
+
+/// The members of an object being built, keyed by integer until a key that
+/// is not one is found.
+enum Members {
+    Int(IntMap),
+    Str(IntKeyedMap),
+}
+
+/// Builds an [`IntKeyedValue`], keeping integer keys as `u32`.
+struct IntKeyedBuilder;
+
+impl<'a> Builder<'a> for IntKeyedBuilder {
+    type Value = IntKeyedValue;
+    type Object = Members;
+
+    fn null(&mut self) -> IntKeyedValue {
+        IntKeyedValue::Null
+    }
+
+    fn bool(&mut self, b: bool) -> IntKeyedValue {
+        IntKeyedValue::Bool(b)
+    }
+
+    fn int(&mut self, i: i64) -> IntKeyedValue {
+        IntKeyedValue::Int(i)
+    }
+
+    fn float(&mut self, f: f64) -> IntKeyedValue {
+        IntKeyedValue::Number(f)
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) -> IntKeyedValue {
+        IntKeyedValue::Str(s.into_owned())
+    }
+
+    fn array(&mut self, items: Vec<IntKeyedValue>) -> IntKeyedValue {
+        IntKeyedValue::Array(items)
+    }
+
+    fn new_object(&mut self, capacity: usize) -> Members {
+        Members::Int(map_with_capacity(capacity))
+    }
+
+    fn contains_key(&self, object: &Members, key: &str) -> bool {
+        match object {
+            Members::Int(map) => int_key(key).is_some_and(|key| map.contains_key(&key)),
+            Members::Str(map) => map.contains_key(key),
+        }
+    }
+
+    fn insert(&mut self, object: &mut Members, key: Cow<'a, str>, value: IntKeyedValue) {
+        if let Members::Int(map) = object {
+            match int_key(&key) {
+                Some(key) => {
+                    map.insert(key, value);
+                    return;
+                }
+                None => {
+                    let mut members = map_with_capacity(map.len() + 1);
+                    members.extend(mem::take(map).into_iter().map(|(k, v)| (k.to_string(), v)));
+                    *object = Members::Str(members);
+                }
+            }
+        }
+        if let Members::Str(map) = object {
+            map.insert(key.into_owned(), value);
+        }
+    }
+
+    fn object(&mut self, object: Members) -> IntKeyedValue {
+        match object {
+            Members::Int(map) => IntKeyedValue::IntObject(map),
+            Members::Str(map) => IntKeyedValue::Object(map),
+        }
+    }
+}
+
+/// Parses `input` as a whole JSON document like
+/// [`parse_document`](super::parse_document), keying each object whose keys
+/// are all integers by `u32`.
+///
+/// Such objects, common in telemetry indexed by id, then need no string
+/// allocated or hashed per key. An object is keyed by integer until its
+/// first other key, when its members so far are moved to a string-keyed map.
+pub fn parse_int_keyed(input: &str) -> Result<IntKeyedValue, ParseError> {
+    parse_document_with(input, &ParseOptions::default(), &mut IntKeyedBuilder)
+        .map_err(|e| ParseError::new(input, e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_int_keyed() {
+        let input = r#"{"7": "a", "10": {"0": 1, "2": [{"01": true}]}, "x": {"3": null, "y": 2}}"#;
+        let value = parse_int_keyed(input).unwrap();
+        assert!(matches!(value, IntKeyedValue::Object(_)));
+        let ints = value.get("10").unwrap();
+        assert!(matches!(ints, IntKeyedValue::IntObject(_)));
+        assert_eq!(ints.get_int(0), Some(&IntKeyedValue::Int(1)));
+        assert_eq!(ints.get("0"), ints.get_int(0));
+        assert_eq!(ints.get("00"), None);
+        assert_eq!(value.get_int(7), Some(&IntKeyedValue::Str("a".into())));
+        let mixed = value.get("x").unwrap();
+        assert!(matches!(mixed, IntKeyedValue::Object(_)));
+        assert_eq!(mixed.get_int(3), Some(&IntKeyedValue::Null));
+        assert_eq!(mixed.get("y"), Some(&IntKeyedValue::Int(2)));
+        match ints.get_int(2) {
+            Some(IntKeyedValue::Array(items)) => {
+                assert!(matches!(items[0], IntKeyedValue::Object(_)));
+                assert_eq!(items[0].get("01"), Some(&IntKeyedValue::Bool(true)));
+            }
+            other => panic!("expected an array, got {:?}", other),
+        }
+        assert_eq!(value.into_owned(), parse_complete(input).unwrap());
+
+        assert_eq!(int_key("4294967295"), Some(u32::MAX));
+        for key in ["4294967296", "-1", "+1", "01", "", "1.0"] {
+            assert_eq!(int_key(key), None, "{:?}", key);
+        }
+        assert!(parse_int_keyed(r#"{"1": 1,}"#).is_err());
+    }
+}
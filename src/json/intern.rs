@@ -0,0 +1,159 @@
+use alloc::{borrow::Cow, collections::BTreeSet, string::ToString, sync::Arc, vec::Vec};
+
+use nom::combinator::all_consuming;
+
+use super::{
+    map_with_capacity, parse_value, Builder, JsonValue, MapImpl, ParseError, ParseOptions,
+};
+
+/// The map backing [`InternedValue::Object`], ordered like [`Map`](super::Map).
+pub type InternedMap = MapImpl<Arc<str>, InternedValue>;
+
+/// A parsed value whose equal strings and object keys share one allocation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InternedValue {
+    Number(f64),
+    Int(i64),
+    Str(Arc<str>),
+    Bool(bool),
+    Null,
+    Object(InternedMap),
+    Array(Vec<InternedValue>),
+}
+
+impl InternedValue {
+    /// Copies the shared strings, giving each its own allocation.
+    pub fn into_owned(self) -> JsonValue {
+        match self {
+            InternedValue::Number(f) => JsonValue::Number(f),
+            InternedValue::Int(i) => JsonValue::Int(i),
+            InternedValue::Str(s) => JsonValue::Str(s.to_string()),
+            InternedValue::Bool(b) => JsonValue::Bool(b),
+            InternedValue::Null => JsonValue::Null,
+            InternedValue::Object(map) => JsonValue::Object(
+                map.into_iter()
+                    .map(|(k, v)| (k.to_string(), v.into_owned()))
+                    .collect(),
+            ),
+            InternedValue::Array(items) => {
+                JsonValue::Array(items.into_iter().map(InternedValue::into_owned).collect())
+            }
+        }
+    }
+}
+
+/// Builds an [`InternedValue`], allocating each distinct string once.
+struct InternBuilder {
+    strings: BTreeSet<Arc<str>>,
+}
+
+impl InternBuilder {
+    fn intern(&mut self, s: &str) -> Arc<str> {
+        match self.strings.get(s) {
+            Some(shared) => shared.clone(),
+            None => {
+                let shared = Arc::<str>::from(s);
+                self.strings.insert(shared.clone());
+                shared
+            }
+        }
+    }
+}
+
+impl<'a> Builder<'a> for InternBuilder {
+    type Value = InternedValue;
+    type Object = InternedMap;
+
+    fn null(&mut self) -> InternedValue {
+        InternedValue::Null
+    }
+
+    fn bool(&mut self, b: bool) -> InternedValue {
+        InternedValue::Bool(b)
+    }
+
+    fn int(&mut self, i: i64) -> InternedValue {
+        InternedValue::Int(i)
+    }
+
+    fn float(&mut self, f: f64) -> InternedValue {
+        InternedValue::Number(f)
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) -> InternedValue {
+        InternedValue::Str(self.intern(&s))
+    }
+
+    fn array(&mut self, items: Vec<InternedValue>) -> InternedValue {
+        InternedValue::Array(items)
+    }
+
+    fn new_object(&mut self, capacity: usize) -> InternedMap {
+        map_with_capacity(capacity)
+    }
+
+    fn contains_key(&self, object: &InternedMap, key: &str) -> bool {
+        object.contains_key(key)
+    }
+
+    fn insert(&mut self, object: &mut InternedMap, key: Cow<'a, str>, value: InternedValue) {
+        let key = self.intern(&key);
+        object.insert(key, value);
+    }
+
+    fn object(&mut self, object: InternedMap) -> InternedValue {
+        InternedValue::Object(object)
+    }
+}
+
+/// Parses `input` as a whole JSON document like
+/// [`parse_document`](super::parse_document), sharing one allocation between
+/// all equal strings and object keys.
+///
+/// This saves memory on documents repeating the same values many times, such
+/// as exported tables, at the cost of a lookup for every string parsed.
+pub fn parse_interned(input: &str) -> Result<InternedValue, ParseError> {
+    let mut builder = InternBuilder {
+        strings: BTreeSet::new(),
+    };
+    let result =
+        all_consuming(|i| parse_value(i, &ParseOptions::default(), 0, &mut builder))(input);
+    match result {
+        Ok((_, value)) => Ok(value),
+        Err(e) => Err(ParseError::new(input, e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_interned() {
+        let input = r#"[{"status": "active", "id": 1}, {"status": "active", "id": 2}]"#;
+        let value = parse_interned(input).unwrap();
+        let rows = match &value {
+            InternedValue::Array(rows) => rows,
+            _ => panic!("expected an array"),
+        };
+        let (key, status) = match (&rows[0], &rows[1]) {
+            (InternedValue::Object(a), InternedValue::Object(b)) => {
+                let a = a.get_key_value("status").unwrap();
+                let b = b.get_key_value("status").unwrap();
+                ([a.0, b.0], [a.1, b.1])
+            }
+            _ => panic!("expected objects"),
+        };
+        match status {
+            [InternedValue::Str(a), InternedValue::Str(b)] => {
+                assert_eq!(&**a, "active");
+                assert!(Arc::ptr_eq(a, b));
+            }
+            _ => panic!("expected strings"),
+        }
+        assert!(Arc::ptr_eq(key[0], key[1]));
+        assert_eq!(value.into_owned(), parse_complete(input).unwrap());
+        assert!(parse_interned(r#"["a", "a""#).is_err());
+    }
+}
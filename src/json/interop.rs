@@ -0,0 +1,74 @@
+use super::JsonValue;
+
+/// Integers that fit an `i64` become `Int`; all other numbers become `Number`.
+impl From<serde_json::Value> for JsonValue {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => JsonValue::Null,
+            serde_json::Value::Bool(b) => JsonValue::Bool(b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => JsonValue::Int(i),
+                None => JsonValue::Number(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(s) => JsonValue::Str(s),
+            serde_json::Value::Array(items) => {
+                JsonValue::Array(items.into_iter().map(JsonValue::from).collect())
+            }
+            serde_json::Value::Object(map) => {
+                JsonValue::Object(map.into_iter().map(|(k, v)| (k, v.into())).collect())
+            }
+        }
+    }
+}
+
+/// NaN and the infinities, which `serde_json` cannot represent, become `Null`.
+impl From<JsonValue> for serde_json::Value {
+    fn from(value: JsonValue) -> Self {
+        match value {
+            JsonValue::Number(f) => serde_json::Number::from_f64(f)
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            JsonValue::Int(i) => serde_json::Value::Number(i.into()),
+            JsonValue::RawNumber(token) => token
+                .parse()
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            #[cfg(feature = "bigint")]
+            JsonValue::BigInt(b) => b
+                .to_string()
+                .parse()
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            JsonValue::Str(s) => serde_json::Value::String(s),
+            JsonValue::Bool(b) => serde_json::Value::Bool(b),
+            JsonValue::Null => serde_json::Value::Null,
+            JsonValue::Object(map) => {
+                serde_json::Value::Object(map.into_iter().map(|(k, v)| (k, v.into())).collect())
+            }
+            JsonValue::Array(items) => {
+                serde_json::Value::Array(items.into_iter().map(serde_json::Value::from).collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_serde_json_round_trip() {
+        let text = r#"{"a": [1, -2, 2.5, 1e300], "b": {"c": null, "d": true},
+            "e": "str", "f": []}"#;
+        let value = parse_complete(text).unwrap();
+        let converted = serde_json::Value::from(value.clone());
+        assert_eq!(
+            converted,
+            serde_json::from_str::<serde_json::Value>(text).unwrap()
+        );
+        assert_eq!(converted["a"][0], serde_json::json!(1));
+        assert_eq!(JsonValue::from(converted), value);
+        assert_eq!(
+            serde_json::Value::from(JsonValue::Number(f64::NAN)),
+            serde_json::Value::Null
+        );
+    }
+}
@@ -0,0 +1,146 @@
+/// Builds a [`JsonValue`](crate::json::JsonValue) from JSON-like syntax, as
+/// in `json!({"name": "x", "nums": [1, 2.5, null], "roles": roles})`.
+///
+/// Anything that is not `null`, `true`, `false`, an array or an object is
+/// interpolated as a Rust expression and converted with `JsonValue::from`.
+/// Object keys are expressions converted with `Into<String>`.
+#[macro_export]
+macro_rules! json {
+    ($($json:tt)+) => {
+        $crate::json_internal!($($json)+)
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! json_internal {
+    // Arrays are parsed by accumulating the converted elements.
+    (@array [$($elems:expr,)*]) => {
+        $crate::__private::vec![$($elems,)*]
+    };
+    (@array [$($elems:expr),*]) => {
+        $crate::__private::vec![$($elems),*]
+    };
+    (@array [$($elems:expr,)*] null $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!(null)] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] true $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!(true)] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] false $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!(false)] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] [$($array:tt)*] $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!([$($array)*])] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] {$($object:tt)*} $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!({$($object)*})] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!($next),] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] $last:expr) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!($last)])
+    };
+    (@array [$($elems:expr),*] , $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)*] $($rest)*)
+    };
+
+    // Objects are parsed by munching the tokens of one key at a time into
+    // `($($key)*)`, then inserting it with its value into `$object`. The
+    // trailing copy of the remaining tokens is unused but keeps the rules
+    // unambiguous.
+    (@object $object:ident () () ()) => {};
+    (@object $object:ident [$($key:tt)+] ($value:expr) , $($rest:tt)*) => {
+        let _ = $object.insert(($($key)+).into(), $value);
+        $crate::json_internal!(@object $object () ($($rest)*) ($($rest)*));
+    };
+    (@object $object:ident [$($key:tt)+] ($value:expr)) => {
+        let _ = $object.insert(($($key)+).into(), $value);
+    };
+    (@object $object:ident ($($key:tt)+) (: null $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!(null)) $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: true $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!(true)) $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: false $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!(false)) $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!([$($array)*])) $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: {$($map:tt)*} $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!({$($map)*})) $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: $value:expr , $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!($value)) , $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: $value:expr) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!($value)));
+    };
+    (@object $object:ident () (($key:expr) : $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object ($key) (: $($rest)*) (: $($rest)*));
+    };
+    (@object $object:ident ($($key:tt)*) ($tt:tt $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object ($($key)* $tt) ($($rest)*) ($($rest)*));
+    };
+
+    (null) => {
+        $crate::json::JsonValue::Null
+    };
+    (true) => {
+        $crate::json::JsonValue::Bool(true)
+    };
+    (false) => {
+        $crate::json::JsonValue::Bool(false)
+    };
+    ([]) => {
+        $crate::json::JsonValue::Array($crate::__private::vec![])
+    };
+    ([ $($tt:tt)+ ]) => {
+        $crate::json::JsonValue::Array($crate::json_internal!(@array [] $($tt)+))
+    };
+    ({}) => {
+        $crate::json::JsonValue::Object($crate::json::Map::default())
+    };
+    ({ $($tt:tt)+ }) => {
+        $crate::json::JsonValue::Object({
+            let mut object = $crate::json::Map::default();
+            $crate::json_internal!(@object object () ($($tt)+) ($($tt)+));
+            object
+        })
+    };
+    ($other:expr) => {
+        $crate::json::JsonValue::from($other)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{parse_complete, JsonValue};
+
+    #[test]
+    fn test_json_macro() {
+        let name = "x".to_string();
+        let nested = json!([true, null]);
+        let value = json!({
+            "name": name,
+            "nums": [1, 2, 3, -4.5],
+            "ok": true,
+            "none": null,
+            "nested": {"inner": nested, "empty": {}, "list": []},
+            ("computed".to_string() + "_key"): 1 + 1,
+        });
+        let expected = parse_complete(
+            r#"{"name": "x", "nums": [1, 2, 3, -4.5], "ok": true, "none": null,
+                "nested": {"inner": [true, null], "empty": {}, "list": []},
+                "computed_key": 2}"#,
+        )
+        .unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(json!(null), JsonValue::Null);
+        assert_eq!(json!("s"), JsonValue::Str("s".to_string()));
+        assert_eq!(json!([[], [{}]]).to_string(), "[[],[{}]]");
+    }
+}
@@ -0,0 +1,231 @@
+use super::{remove_member, JsonValue, Map};
+
+/// How [`merge_deep`](JsonValue::merge_deep) combines two arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayMerge {
+    /// The array of the other value replaces the target's, as in a merge
+    /// patch.
+    Replace,
+    /// The elements of the other array are appended to the target's.
+    Concat,
+    /// Elements at the same index are merged deeply, and elements past the
+    /// end of the target's array are appended.
+    ByIndex,
+}
+
+impl JsonValue {
+    /// Applies an RFC 7386 JSON Merge Patch.
+    ///
+    /// An object patch is merged member by member, recursively, with a `null`
+    /// member removing the key from the target; a target that is not an
+    /// object is replaced by an empty one first. Any other patch, arrays
+    /// included, replaces the target wholesale.
+    pub fn merge(&mut self, patch: &JsonValue) {
+        let patch = match patch {
+            JsonValue::Object(patch) => patch,
+            _ => {
+                *self = patch.clone();
+                return;
+            }
+        };
+        if !matches!(self, JsonValue::Object(_)) {
+            *self = JsonValue::Object(Map::default());
+        }
+        if let JsonValue::Object(target) = self {
+            for (key, value) in patch {
+                if value.is_null() {
+                    remove_member(target, key);
+                } else {
+                    target
+                        .entry(key.clone())
+                        .or_insert(JsonValue::Null)
+                        .merge(value);
+                }
+            }
+        }
+    }
+
+    /// Merges `other` into `self` deeply, as when layering an override
+    /// configuration over a base one.
+    ///
+    /// Objects are merged member by member, recursively, and arrays are
+    /// combined by `array_strategy`. Any other value of `other`, `null`
+    /// included, replaces the target, so unlike [`merge`](JsonValue::merge)
+    /// this never removes a member.
+    pub fn merge_deep(&mut self, other: &JsonValue, array_strategy: ArrayMerge) {
+        match (self, other) {
+            (JsonValue::Object(target), JsonValue::Object(other)) => {
+                for (key, value) in other {
+                    match target.get_mut(key) {
+                        Some(existing) => existing.merge_deep(value, array_strategy),
+                        None => {
+                            target.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+            }
+            (JsonValue::Array(target), JsonValue::Array(other))
+                if array_strategy != ArrayMerge::Replace =>
+            {
+                let merged = match array_strategy {
+                    ArrayMerge::ByIndex => target.len().min(other.len()),
+                    _ => 0,
+                };
+                for (existing, value) in target.iter_mut().zip(&other[..merged]) {
+                    existing.merge_deep(value, array_strategy);
+                }
+                target.extend_from_slice(&other[merged..]);
+            }
+            (target, other) => *target = other.clone(),
+        }
+    }
+
+    /// Computes the RFC 7386 merge patch that turns `self` into `other` when
+    /// passed to [`merge`](JsonValue::merge).
+    ///
+    /// Members only in `self` become `null`, changed members are diffed
+    /// recursively if both sides are objects and otherwise given their new
+    /// value, and unchanged members are left out. A merge patch cannot set a
+    /// member to `null`, so such members of `other` are removed instead.
+    pub fn diff(&self, other: &JsonValue) -> JsonValue {
+        let (source, target) = match (self, other) {
+            (JsonValue::Object(source), JsonValue::Object(target)) => (source, target),
+            _ => return other.clone(),
+        };
+        let mut patch = Map::default();
+        for key in source.keys().filter(|&key| !target.contains_key(key)) {
+            patch.insert(key.clone(), JsonValue::Null);
+        }
+        for (key, value) in target {
+            match source.get(key) {
+                Some(old) if old == value => {}
+                Some(old) => {
+                    patch.insert(key.clone(), old.diff(value));
+                }
+                None => {
+                    patch.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        JsonValue::Object(patch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let mut target = parse_complete(
+            r#"{"title": "Goodbye!", "author": {"givenName": "John", "familyName": "Doe"},
+                "tags": ["example", "sample"], "content": "This will be unchanged"}"#,
+        )
+        .unwrap();
+        let patch = parse_complete(
+            r#"{"title": "Hello!", "phoneNumber": "+01-123-456-7890",
+                "author": {"familyName": null}, "tags": ["example"]}"#,
+        )
+        .unwrap();
+        target.merge(&patch);
+        assert_eq!(
+            target,
+            parse_complete(
+                r#"{"title": "Hello!", "author": {"givenName": "John"}, "tags": ["example"],
+                    "content": "This will be unchanged", "phoneNumber": "+01-123-456-7890"}"#
+            )
+            .unwrap()
+        );
+
+        let mut target = parse_complete(r#"{"a": [1, {"b": 2}], "c": 3}"#).unwrap();
+        target.merge(&parse_complete(r#"{"a": {"d": {"e": null, "f": 4}}, "c": null}"#).unwrap());
+        assert_eq!(target, parse_complete(r#"{"a": {"d": {"f": 4}}}"#).unwrap());
+
+        target.merge(&parse_complete("[1, 2]").unwrap());
+        assert_eq!(target, parse_complete("[1, 2]").unwrap());
+    }
+
+    #[test]
+    fn test_merge_deep() {
+        let base = parse_complete(
+            r#"{"name": "base", "plugins": ["a", "b"], "servers": [{"host": "x", "port": 1}, {"host": "y"}],
+                "log": {"level": "info", "targets": ["stderr"]}}"#,
+        )
+        .unwrap();
+        let overlay = parse_complete(
+            r#"{"plugins": ["c"], "servers": [{"port": 2}, "z", {"host": "w"}],
+                "log": {"targets": ["file"], "color": null}}"#,
+        )
+        .unwrap();
+
+        let mut merged = base.clone();
+        merged.merge_deep(&overlay, ArrayMerge::Replace);
+        assert_eq!(
+            merged,
+            parse_complete(
+                r#"{"name": "base", "plugins": ["c"], "servers": [{"port": 2}, "z", {"host": "w"}],
+                    "log": {"level": "info", "targets": ["file"], "color": null}}"#
+            )
+            .unwrap()
+        );
+
+        let mut merged = base.clone();
+        merged.merge_deep(&overlay, ArrayMerge::Concat);
+        assert_eq!(
+            merged,
+            parse_complete(
+                r#"{"name": "base", "plugins": ["a", "b", "c"],
+                    "servers": [{"host": "x", "port": 1}, {"host": "y"}, {"port": 2}, "z", {"host": "w"}],
+                    "log": {"level": "info", "targets": ["stderr", "file"], "color": null}}"#
+            )
+            .unwrap()
+        );
+
+        let mut merged = base.clone();
+        merged.merge_deep(&overlay, ArrayMerge::ByIndex);
+        assert_eq!(
+            merged,
+            parse_complete(
+                r#"{"name": "base", "plugins": ["c", "b"],
+                    "servers": [{"host": "x", "port": 2}, "z", {"host": "w"}],
+                    "log": {"level": "info", "targets": ["file"], "color": null}}"#
+            )
+            .unwrap()
+        );
+
+        let mut merged = base.clone();
+        merged.merge_deep(&parse_complete("[1]").unwrap(), ArrayMerge::Concat);
+        assert_eq!(merged, parse_complete("[1]").unwrap());
+    }
+
+    #[test]
+    fn test_diff() {
+        let source = parse_complete(
+            r#"{"a": 1, "b": {"c": [1, 2], "d": "x", "e": {"f": true}}, "g": "gone"}"#,
+        )
+        .unwrap();
+        let target =
+            parse_complete(r#"{"a": 1, "b": {"c": [1], "d": "x", "e": 5}, "h": {"i": null}}"#)
+                .unwrap();
+        let patch = source.diff(&target);
+        assert_eq!(
+            patch,
+            parse_complete(r#"{"g": null, "b": {"c": [1], "e": 5}, "h": {"i": null}}"#).unwrap()
+        );
+
+        let mut merged = source.clone();
+        merged.merge(&patch);
+        assert_eq!(
+            merged,
+            parse_complete(r#"{"a": 1, "b": {"c": [1], "d": "x", "e": 5}, "h": {}}"#).unwrap()
+        );
+
+        let target = parse_complete(r#"{"a": 2, "b": {"c": [1, 2], "d": "y"}}"#).unwrap();
+        let mut merged = source.clone();
+        merged.merge(&source.diff(&target));
+        assert_eq!(merged, target);
+        assert_eq!(source.diff(&source), parse_complete("{}").unwrap());
+        assert_eq!(source.diff(&JsonValue::Int(1)), JsonValue::Int(1));
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(feature = "bigint")]
+use alloc::string::ToString;
+use core::convert::TryFrom;
+
+use super::JsonValue;
+
+/// A number, from [`JsonValue::as_number`], viewed as whichever of `i64`,
+/// `u64` and `f64` can hold it, whatever variant of [`JsonValue`] it came
+/// from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Number(N);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum N {
+    Int(i64),
+    /// An integer above `i64::MAX`.
+    UInt(u64),
+    Float(f64),
+}
+
+impl Number {
+    /// Whether the number is an integer that an `i64` or a `u64` holds
+    /// exactly. A number written with a fraction or an exponent is not one.
+    pub fn is_integer(&self) -> bool {
+        !matches!(self.0, N::Float(_))
+    }
+
+    /// Returns the number if it is an integer that fits an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.0 {
+            N::Int(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if it is an integer that fits a `u64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.0 {
+            N::Int(i) => u64::try_from(i).ok(),
+            N::UInt(u) => Some(u),
+            N::Float(_) => None,
+        }
+    }
+
+    /// Returns the number as an `f64`, rounding an integer too large for one
+    /// to the nearest `f64`.
+    pub fn as_f64(&self) -> f64 {
+        match self.0 {
+            N::Int(i) => i as f64,
+            N::UInt(u) => u as f64,
+            N::Float(f) => f,
+        }
+    }
+
+    /// Reads the text of a number, keeping an integer exact if it fits.
+    fn parse(token: &str) -> Option<Number> {
+        let n = match token.parse() {
+            Ok(i) => N::Int(i),
+            Err(_) => match token.parse() {
+                Ok(u) => N::UInt(u),
+                Err(_) => N::Float(token.parse().ok()?),
+            },
+        };
+        Some(Number(n))
+    }
+}
+
+impl JsonValue {
+    /// Returns a number of any of the variants holding one, or `None` for
+    /// other values.
+    ///
+    /// Unlike matching on the variants, this keeps working as numbers gain
+    /// representations: an integer is viewed the same way whether it is an
+    /// `Int`, a `RawNumber` or a `BigInt`.
+    pub fn as_number(&self) -> Option<Number> {
+        match self {
+            JsonValue::Number(f) => Some(Number(N::Float(*f))),
+            JsonValue::Int(i) => Some(Number(N::Int(*i))),
+            JsonValue::RawNumber(token) => Number::parse(token),
+            #[cfg(feature = "bigint")]
+            JsonValue::BigInt(b) => Number::parse(&b.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use super::super::{parse_complete, parse_with_options, ParseOptions};
+    use super::*;
+
+    #[test]
+    fn test_as_number() {
+        let value = parse_complete(r#"[-7, 2.5, 1e2, "1"]"#).unwrap();
+        let int = value[0].as_number().unwrap();
+        assert!(int.is_integer());
+        assert_eq!(
+            (int.as_i64(), int.as_u64(), int.as_f64()),
+            (Some(-7), None, -7.)
+        );
+        let float = value[1].as_number().unwrap();
+        assert!(!float.is_integer());
+        assert_eq!(
+            (float.as_i64(), float.as_u64(), float.as_f64()),
+            (None, None, 2.5)
+        );
+        assert_eq!(value[2].as_number().unwrap().as_i64(), None);
+        assert_eq!(value[3].as_number(), None);
+        assert_eq!(JsonValue::Null.as_number(), None);
+
+        let raw = ParseOptions {
+            raw_numbers: true,
+            ..Default::default()
+        };
+        let value = parse_with_options("[7, 18446744073709551615, 1.5e3, 1e400]", &raw).unwrap();
+        let numbers: Vec<_> = value.elements().map(|v| v.as_number().unwrap()).collect();
+        assert_eq!(numbers[0], JsonValue::Int(7).as_number().unwrap());
+        assert_eq!(
+            (numbers[1].as_i64(), numbers[1].as_u64()),
+            (None, Some(u64::MAX))
+        );
+        assert!(numbers[1].is_integer());
+        assert_eq!(numbers[2].as_f64(), 1500.);
+        assert_eq!(numbers[3].as_f64(), f64::INFINITY);
+    }
+}
@@ -0,0 +1,203 @@
+use alloc::sync::Arc;
+use core::fmt;
+
+use super::JsonValue;
+
+/// The characters JSON allows between tokens: space, tab, line feed and
+/// carriage return.
+pub const JSON_WHITESPACE: &[char] = &[' ', '\t', '\n', '\r'];
+
+/// How [`parse_with_options`](super::parse_with_options) handles an object
+/// containing the same key more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// Keep the value of the last member with the key.
+    LastWins,
+    /// Keep the value of the first member with the key.
+    FirstWins,
+    /// Fail with [`ErrorKind::DuplicateKey`](super::ErrorKind::DuplicateKey).
+    Error,
+    /// Keep an array of the values of every member with the key, in order. A
+    /// key appearing only once keeps its value as it is.
+    Collect,
+}
+
+/// How [`parse_with_options`](super::parse_with_options) handles a `\u`
+/// escape of a lone surrogate, which stands for no character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurrogatePolicy {
+    /// Fail, as the escape is invalid.
+    Error,
+    /// Decode the escape as U+FFFD REPLACEMENT CHARACTER, as lossy UTF-16
+    /// decoding does.
+    Replace,
+}
+
+/// A function given the text of each number, from
+/// [`ParseOptions::number_hook`].
+///
+/// It returns the value to build for the number, or `None` to build it as
+/// usual. Cloning the hook shares the function.
+#[derive(Clone)]
+pub struct NumberHook(Arc<HookFn>);
+
+type HookFn = dyn Fn(&str) -> Option<JsonValue> + Send + Sync;
+
+impl NumberHook {
+    pub fn new(hook: impl Fn(&str) -> Option<JsonValue> + Send + Sync + 'static) -> Self {
+        NumberHook(Arc::new(hook))
+    }
+
+    pub(super) fn call(&self, token: &str) -> Option<JsonValue> {
+        (self.0)(token)
+    }
+}
+
+impl fmt::Debug for NumberHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NumberHook")
+    }
+}
+
+/// Options controlling what [`parse_with_options`](super::parse_with_options)
+/// accepts. The default accepts exactly what [`parse`](super::parse) does.
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// What to do with an object containing the same key twice,
+    /// [`DuplicateKeyPolicy::LastWins`] by default.
+    pub duplicate_keys: DuplicateKeyPolicy,
+    /// The deepest nesting of arrays and objects accepted, 128 by default.
+    pub max_depth: usize,
+    /// The longest document accepted, in bytes. Unlimited by default.
+    pub max_input_bytes: usize,
+    /// The most array elements and object members accepted in the whole
+    /// document. Unlimited by default.
+    pub max_total_elements: usize,
+    /// The longest string or object key accepted, in bytes after unescaping.
+    /// Unlimited by default.
+    pub max_string_length: usize,
+    /// Skip `//` line comments and `/* */` block comments wherever whitespace
+    /// is allowed.
+    pub allow_comments: bool,
+    /// Accept a single comma after the last element of an array or member of
+    /// an object.
+    pub allow_trailing_comma: bool,
+    /// Accept `NaN`, `Infinity` and `-Infinity` as [`JsonValue::Number`].
+    ///
+    /// As with `f64`, a `NaN` number is unequal to everything including
+    /// itself, so a value containing one never compares equal with `==`.
+    ///
+    /// [`JsonValue::Number`]: super::JsonValue::Number
+    pub allow_nan: bool,
+    /// Accept integers written in hexadecimal with a `0x` or `0X` prefix,
+    /// such as `0xFF` or `-0x10`, as [`JsonValue::Int`].
+    ///
+    /// [`JsonValue::Int`]: super::JsonValue::Int
+    pub allow_hex_numbers: bool,
+    /// Accept numbers with a leading `+`, and with a decimal point that has
+    /// no digits before or after it, such as `+1.5`, `.5` and `5.`, as data
+    /// written by scientific tools often has. They are read as the number
+    /// they stand for, so `+1` is [`JsonValue::Int`] `1` and `5.` is
+    /// [`JsonValue::Number`] `5.0`, even with
+    /// [`raw_numbers`](ParseOptions::raw_numbers), as their text is not
+    /// JSON.
+    ///
+    /// [`JsonValue::Int`]: super::JsonValue::Int
+    /// [`JsonValue::Number`]: super::JsonValue::Number
+    pub relaxed_numbers: bool,
+    /// Keep numbers as [`JsonValue::RawNumber`] holding their text, so they
+    /// are written back exactly as they were read.
+    ///
+    /// [`JsonValue::RawNumber`]: super::JsonValue::RawNumber
+    pub raw_numbers: bool,
+    /// Fail on a number written as an integer whose value an `f64` cannot
+    /// hold exactly, such as `9007199254740993`, instead of accepting a value
+    /// that consumers storing numbers as doubles would silently round.
+    pub reject_imprecise_integers: bool,
+    /// Skip a UTF-8 byte order mark (`U+FEFF`) at the very start of the
+    /// document, as written by some Windows tools.
+    pub strip_bom: bool,
+    /// Accept a backslash before any character other than `u` in strings and
+    /// keys, standing for that character, so that `\'` is read as `'`.
+    pub lenient_escapes: bool,
+    /// What to do with a string or key escaping a high surrogate not followed
+    /// by an escaped low surrogate, or a low surrogate on its own,
+    /// [`SurrogatePolicy::Error`] by default.
+    pub on_invalid_surrogate: SurrogatePolicy,
+    /// Accept strings and keys delimited by single quotes, in which `\'`
+    /// stands for a single quote and a double quote needs no escape.
+    pub allow_single_quotes: bool,
+    /// Accept object keys written without quotes as identifiers, as JSON5
+    /// does: a letter, `_` or `$` followed by letters, digits, `_` and `$`.
+    pub allow_unquoted_keys: bool,
+    /// Fail on a document that is a bare scalar such as `42`, as RFC 4627
+    /// required, instead of accepting any value as RFC 8259 does.
+    pub require_container_root: bool,
+    /// The characters accepted between tokens, [`JSON_WHITESPACE`] by
+    /// default. A subset makes parsing stricter; a superset accepts, say,
+    /// non-breaking spaces.
+    pub whitespace: &'static [char],
+    /// A function deciding the value of each number from its text, such as
+    /// a decimal type kept as a [`JsonValue::RawNumber`]. The grammar of
+    /// numbers is unchanged: the hook only sees valid JSON numbers, not the
+    /// `NaN` or hexadecimal extensions. `None` by default.
+    ///
+    /// The hook applies to the functions building a [`JsonValue`] with
+    /// options, such as [`parse_with_options`](super::parse_with_options).
+    ///
+    /// [`JsonValue::RawNumber`]: super::JsonValue::RawNumber
+    pub number_hook: Option<NumberHook>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            duplicate_keys: DuplicateKeyPolicy::LastWins,
+            max_depth: 128,
+            max_input_bytes: usize::MAX,
+            max_total_elements: usize::MAX,
+            max_string_length: usize::MAX,
+            allow_comments: false,
+            allow_trailing_comma: false,
+            allow_nan: false,
+            allow_hex_numbers: false,
+            relaxed_numbers: false,
+            raw_numbers: false,
+            reject_imprecise_integers: false,
+            strip_bom: false,
+            lenient_escapes: false,
+            on_invalid_surrogate: SurrogatePolicy::Error,
+            allow_single_quotes: false,
+            allow_unquoted_keys: false,
+            require_container_root: false,
+            whitespace: JSON_WHITESPACE,
+            number_hook: None,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Options accepting exactly what RFC 8259 allows, the same as
+    /// [`ParseOptions::default`].
+    pub fn strict() -> Self {
+        ParseOptions::default()
+    }
+
+    /// Options accepting the relaxed syntax most JSON5 documents use:
+    /// comments, trailing commas, unquoted keys, single-quoted strings and
+    /// `NaN` and `Infinity`.
+    ///
+    /// The rest of JSON5, such as hexadecimal numbers and strings continued
+    /// across lines, is still rejected; see
+    /// [`allow_hex_numbers`](ParseOptions::allow_hex_numbers) for the former.
+    pub fn json5() -> Self {
+        ParseOptions {
+            allow_comments: true,
+            allow_trailing_comma: true,
+            allow_nan: true,
+            allow_single_quotes: true,
+            allow_unquoted_keys: true,
+            ..ParseOptions::default()
+        }
+    }
+}
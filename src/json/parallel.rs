@@ -0,0 +1,113 @@
+use alloc::vec::Vec;
+
+use rayon::prelude::*;
+
+use super::{
+    parse_document, parse_with_options, skip_element, ErrorKind, JsonValue, ParseError,
+    ParseOptions, JSON_WHITESPACE,
+};
+
+/// Splits the contents of an array, which follow its `[` in `input`, into
+/// the text of its elements without parsing them. Returns `None` if the array
+/// is not closed or is followed by more than whitespace.
+fn split_elements(input: &str) -> Option<Vec<&str>> {
+    let mut elements = Vec::new();
+    let mut rest = input.trim_start_matches(JSON_WHITESPACE);
+    if let Some(after) = rest.strip_prefix(']') {
+        rest = after;
+    } else {
+        rest = input;
+        loop {
+            let end = skip_element(rest);
+            elements.push(&rest[..rest.len() - end.len()]);
+            match end.chars().next() {
+                Some(',') => rest = &end[1..],
+                Some(']') => {
+                    rest = &end[1..];
+                    break;
+                }
+                _ => return None,
+            }
+        }
+    }
+    if rest.trim_start_matches(JSON_WHITESPACE).is_empty() {
+        Some(elements)
+    } else {
+        None
+    }
+}
+
+/// Parses `input`, a whole JSON document holding an array, into its
+/// elements, parsing them in parallel.
+///
+/// The elements are first found by scanning for the commas between them,
+/// which is much cheaper than parsing them, so this pays off for arrays of
+/// many large elements. The result is the same as from parsing serially with
+/// [`parse_document`]; if the document is malformed, it is parsed serially to
+/// report the same error.
+pub fn parse_array_parallel(input: &str) -> Result<Vec<JsonValue>, ParseError> {
+    let rest = input.trim_start_matches(JSON_WHITESPACE);
+    let contents = match rest.strip_prefix('[') {
+        Some(contents) => contents,
+        None => {
+            let offset = input.len() - rest.len();
+            return Err(ParseError::at(input, offset, ErrorKind::Char('[')));
+        }
+    };
+    // Each element is parsed as a document of its own, one level less deep.
+    let options = ParseOptions {
+        max_depth: ParseOptions::default().max_depth - 1,
+        ..Default::default()
+    };
+    let values = split_elements(contents).and_then(|elements| {
+        elements
+            .par_iter()
+            .map(|element| parse_with_options(element, &options).ok())
+            .collect::<Option<Vec<_>>>()
+    });
+    match values {
+        Some(values) => Ok(values),
+        None => match parse_document(input)? {
+            JsonValue::Array(values) => Ok(values),
+            _ => unreachable!("the document starts with '['"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parallel() {
+        let mut input = String::from("[");
+        for i in 0..1000 {
+            if i > 0 {
+                input.push_str(", ");
+            }
+            input.push_str(&format!(
+                r#"{{"id": {}, "tags": ["a,b", "]"], "nested": {{"s": "q\"}}", "n": [[{}]]}}}}"#,
+                i, i
+            ));
+        }
+        input.push_str("]\n");
+        let serial = match parse_document(&input).unwrap() {
+            JsonValue::Array(values) => values,
+            _ => panic!("expected an array"),
+        };
+        assert_eq!(parse_array_parallel(&input).unwrap(), serial);
+        assert_eq!(parse_array_parallel(" [ ] ").unwrap(), []);
+        assert_eq!(parse_array_parallel("[1]").unwrap(), [JsonValue::Int(1)]);
+
+        for input in ["[1,,2]", "[1, 2", r#"[1, "a]"#, "[1, {]", "[1]]", "[[1, 2]"] {
+            assert_eq!(
+                parse_array_parallel(input).unwrap_err(),
+                parse_document(input).unwrap_err(),
+                "{}",
+                input
+            );
+        }
+        let error = parse_array_parallel(" {}").unwrap_err();
+        assert_eq!((error.offset, error.kind), (1, ErrorKind::Char('[')));
+    }
+}
@@ -0,0 +1,299 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use super::{
+    pointer::{parse_index, tokens},
+    remove_member, JsonValue,
+};
+
+/// Why a JSON Patch could not be applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchError {
+    /// The operation at this index of the patch is malformed: it is not an
+    /// object, has an unknown `op`, lacks a member the `op` needs, or moves a
+    /// value into itself.
+    InvalidOperation(usize),
+    /// A path or `from` pointer does not refer to an existing location.
+    PathNotFound(String),
+    /// A `test` operation found a different value at this path.
+    TestFailed(String),
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::InvalidOperation(i) => write!(f, "invalid patch operation at index {}", i),
+            PatchError::PathNotFound(path) => write!(f, "path {:?} does not exist", path),
+            PatchError::TestFailed(path) => write!(f, "test failed at path {:?}", path),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PatchError {}
+
+/// Applies an RFC 6902 JSON Patch, an array of `add`, `remove`, `replace`,
+/// `move`, `copy` and `test` operations, to `doc`.
+///
+/// The operations are applied in order, and either all of them succeed or
+/// `doc` is left unchanged.
+pub fn apply_patch(doc: &mut JsonValue, patch: &JsonValue) -> Result<(), PatchError> {
+    let operations = match patch {
+        JsonValue::Array(operations) => operations,
+        _ => return Err(PatchError::InvalidOperation(0)),
+    };
+    let mut patched = doc.clone();
+    for (i, operation) in operations.iter().enumerate() {
+        apply_operation(&mut patched, operation)
+            .map_err(|e| e.unwrap_or(PatchError::InvalidOperation(i)))?;
+    }
+    *doc = patched;
+    Ok(())
+}
+
+/// Applies one operation, failing with `None` if it is malformed.
+fn apply_operation(doc: &mut JsonValue, operation: &JsonValue) -> Result<(), Option<PatchError>> {
+    let path = operation["path"].as_str().ok_or(None)?;
+    let from = || operation["from"].as_str().ok_or(None);
+    let value = || match operation.as_object().and_then(|o| o.get("value")) {
+        Some(value) => Ok(value.clone()),
+        None => Err(None),
+    };
+    match operation["op"].as_str().ok_or(None)? {
+        "add" => add(doc, path, value()?),
+        "remove" => remove(doc, path).map(drop),
+        "replace" => {
+            let target = doc.pointer_mut(path).ok_or_else(|| not_found(path))?;
+            *target = value()?;
+            Ok(())
+        }
+        "move" => {
+            let from = from()?;
+            if path.starts_with(from) && path[from.len()..].starts_with('/') {
+                return Err(None);
+            }
+            let moved = remove(doc, from)?;
+            add(doc, path, moved)
+        }
+        "copy" => {
+            let from = from()?;
+            let copied = doc.pointer(from).ok_or_else(|| not_found(from))?.clone();
+            add(doc, path, copied)
+        }
+        "test" => match doc.pointer(path) {
+            Some(target) if equal(target, &value()?) => Ok(()),
+            Some(_) => Err(Some(PatchError::TestFailed(path.to_string()))),
+            None => Err(not_found(path)),
+        },
+        _ => Err(None),
+    }
+}
+
+fn not_found(path: &str) -> Option<PatchError> {
+    Some(PatchError::PathNotFound(path.to_string()))
+}
+
+/// Splits `path` into the container it points into and the last token.
+fn parent_mut<'v>(doc: &'v mut JsonValue, path: &str) -> Option<(&'v mut JsonValue, String)> {
+    let split = path.rfind('/')?;
+    let last = tokens(&path[split..])?.next()?;
+    Some((doc.pointer_mut(&path[..split])?, last))
+}
+
+fn add(doc: &mut JsonValue, path: &str, value: JsonValue) -> Result<(), Option<PatchError>> {
+    if path.is_empty() {
+        *doc = value;
+        return Ok(());
+    }
+    match parent_mut(doc, path) {
+        Some((JsonValue::Object(map), key)) => {
+            map.insert(key, value);
+            Ok(())
+        }
+        Some((JsonValue::Array(items), token)) => {
+            let index = match token.as_str() {
+                "-" => items.len(),
+                token => parse_index(token)
+                    .filter(|&i| i <= items.len())
+                    .ok_or_else(|| not_found(path))?,
+            };
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(not_found(path)),
+    }
+}
+
+fn remove(doc: &mut JsonValue, path: &str) -> Result<JsonValue, Option<PatchError>> {
+    let removed = match parent_mut(doc, path) {
+        Some((JsonValue::Object(map), key)) => remove_member(map, &key),
+        Some((JsonValue::Array(items), token)) => match parse_index(&token) {
+            Some(i) if i < items.len() => Some(items.remove(i)),
+            _ => None,
+        },
+        _ => None,
+    };
+    removed.ok_or_else(|| not_found(path))
+}
+
+/// Compares values as the `test` operation does, with numbers equal when
+/// their values are, whether written as integers or not.
+///
+/// Integers are compared exactly, and only a comparison involving a float is
+/// made between `f64`s, so two integers do not pass for equal just because
+/// they round to the same `f64`.
+fn equal(a: &JsonValue, b: &JsonValue) -> bool {
+    match (a, b) {
+        (JsonValue::Object(a), JsonValue::Object(b)) => {
+            a.len() == b.len() && a.iter().all(|(k, v)| b.get(k).is_some_and(|w| equal(v, w)))
+        }
+        (JsonValue::Array(a), JsonValue::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(v, w)| equal(v, w))
+        }
+        // Too large for a `u64`, these would only be compared as `f64`s.
+        #[cfg(feature = "bigint")]
+        (JsonValue::BigInt(a), JsonValue::BigInt(b)) => a == b,
+        _ => match (a.as_number(), b.as_number()) {
+            (Some(x), Some(y)) if x.is_integer() && y.is_integer() => {
+                (x.as_i64(), x.as_u64()) == (y.as_i64(), y.as_u64())
+            }
+            (Some(x), Some(y)) => x.as_f64() == y.as_f64(),
+            _ => a == b,
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    fn patched(doc: &str, patch: &str) -> Result<JsonValue, PatchError> {
+        let mut doc = parse_complete(doc).unwrap();
+        apply_patch(&mut doc, &parse_complete(patch).unwrap())?;
+        Ok(doc)
+    }
+
+    #[test]
+    fn test_operations() {
+        let doc = r#"{"a": {"b": [1, 2]}, "c": "x"}"#;
+        let cases = [
+            (
+                r#"{"op": "add", "path": "/a/b/1", "value": 9}"#,
+                r#"{"a": {"b": [1, 9, 2]}, "c": "x"}"#,
+            ),
+            (
+                r#"{"op": "add", "path": "/a/b/-", "value": 3}"#,
+                r#"{"a": {"b": [1, 2, 3]}, "c": "x"}"#,
+            ),
+            (
+                r#"{"op": "add", "path": "/d", "value": null}"#,
+                r#"{"a": {"b": [1, 2]}, "c": "x", "d": null}"#,
+            ),
+            (
+                r#"{"op": "remove", "path": "/a/b/0"}"#,
+                r#"{"a": {"b": [2]}, "c": "x"}"#,
+            ),
+            (
+                r#"{"op": "remove", "path": "/c"}"#,
+                r#"{"a": {"b": [1, 2]}}"#,
+            ),
+            (
+                r#"{"op": "replace", "path": "/c", "value": [true]}"#,
+                r#"{"a": {"b": [1, 2]}, "c": [true]}"#,
+            ),
+            (
+                r#"{"op": "move", "from": "/a/b", "path": "/b"}"#,
+                r#"{"a": {}, "c": "x", "b": [1, 2]}"#,
+            ),
+            (
+                r#"{"op": "copy", "from": "/a/b/1", "path": "/a/e"}"#,
+                r#"{"a": {"b": [1, 2], "e": 2}, "c": "x"}"#,
+            ),
+            (r#"{"op": "test", "path": "/a/b", "value": [1, 2.0]}"#, doc),
+            (r#"{"op": "replace", "path": "", "value": 1}"#, "1"),
+        ];
+        for (operation, expected) in cases.iter() {
+            assert_eq!(
+                patched(doc, &format!("[{}]", operation)),
+                Ok(parse_complete(expected).unwrap()),
+                "{}",
+                operation
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let doc = r#"{"a": [1], "b": 2}"#;
+        let mut value = parse_complete(doc).unwrap();
+        let patch = parse_complete(
+            r#"[{"op": "remove", "path": "/b"}, {"op": "test", "path": "/a/0", "value": 2}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            apply_patch(&mut value, &patch),
+            Err(PatchError::TestFailed("/a/0".to_string()))
+        );
+        assert_eq!(value, parse_complete(doc).unwrap());
+
+        let error = |operation: &str| patched(doc, &format!("[{}]", operation)).unwrap_err();
+        assert_eq!(
+            error(r#"{"op": "remove", "path": "/x"}"#),
+            PatchError::PathNotFound("/x".to_string())
+        );
+        assert_eq!(
+            error(r#"{"op": "add", "path": "/a/2", "value": 1}"#),
+            PatchError::PathNotFound("/a/2".to_string())
+        );
+        assert_eq!(
+            error(r#"{"op": "replace", "path": "/x/y", "value": 1}"#),
+            PatchError::PathNotFound("/x/y".to_string())
+        );
+        assert_eq!(
+            error(r#"{"op": "move", "from": "/a", "path": "/a/0"}"#),
+            PatchError::InvalidOperation(0)
+        );
+        assert_eq!(
+            error(r#"{"op": "add", "path": "/c"}"#),
+            PatchError::InvalidOperation(0)
+        );
+        assert_eq!(
+            error(r#"{"op": "frobnicate", "path": ""}"#),
+            PatchError::InvalidOperation(0)
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        let test = |doc: &str, value: &str| {
+            let operation = format!(r#"[{{"op": "test", "path": "/a", "value": {}}}]"#, value);
+            patched(&format!(r#"{{"a": {}}}"#, doc), &operation)
+        };
+        assert!(test("1", "1.0").is_ok());
+        assert!(test("-3", "-3").is_ok());
+        assert!(test("18446744073709551615", "18446744073709551615").is_ok());
+        // Both round to the same `f64`, 2^53.
+        assert_eq!(
+            test("9007199254740992", "9007199254740993"),
+            Err(PatchError::TestFailed("/a".to_string()))
+        );
+        assert!(test("9007199254740992", "9007199254740992.0").is_ok());
+
+        let mut doc = JsonValue::Array(alloc::vec![JsonValue::RawNumber("12".to_string())]);
+        let patch = parse_complete(r#"[{"op": "test", "path": "/0", "value": 12}]"#).unwrap();
+        assert_eq!(apply_patch(&mut doc, &patch), Ok(()));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_big_int_numbers() {
+        let big = JsonValue::BigInt(u64::MAX.into());
+        assert!(equal(&big, &JsonValue::RawNumber(u64::MAX.to_string())));
+        assert!(equal(&big, &JsonValue::Number(u64::MAX as f64)));
+        assert!(!equal(&big, &JsonValue::Int(i64::MAX)));
+        let huge = JsonValue::BigInt(num_bigint::BigInt::from(u64::MAX) * 10);
+        assert!(equal(&huge, &huge.clone()));
+        assert!(!equal(&huge, &big));
+    }
+}
@@ -0,0 +1,289 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use super::{JsonValue, Map, PatchError};
+
+/// Splits `pointer` into its unescaped reference tokens, or `None` if it is
+/// neither empty nor starts with `/`.
+pub(super) fn tokens(pointer: &str) -> Option<impl Iterator<Item = String> + '_> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return None;
+    }
+    Some(
+        pointer
+            .split('/')
+            .skip(1)
+            .map(|token| token.replace("~1", "/").replace("~0", "~")),
+    )
+}
+
+/// Escapes `key` as a reference token, the reverse of [`tokens`].
+pub(super) fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Parses a reference token as an array index. Leading zeros are not allowed.
+pub(super) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || !token.bytes().all(|b| b.is_ascii_digit())
+        || (token.len() > 1 && token.starts_with('0'))
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
+impl JsonValue {
+    /// Looks up a value by JSON Pointer, e.g. `/foo/0/bar`.
+    ///
+    /// The empty pointer refers to the whole document, and `~1` and `~0`
+    /// decode to `/` and `~` within a key. Returns `None` when a token does not
+    /// resolve.
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonValue> {
+        tokens(pointer)?.try_fold(self, |target, token| match target {
+            JsonValue::Object(map) => map.get(&token),
+            JsonValue::Array(items) => items.get(parse_index(&token)?),
+            _ => None,
+        })
+    }
+
+    /// Returns a copy of the value at a JSON Pointer, cloning only that
+    /// subtree rather than the whole document, or `None` when the pointer
+    /// does not resolve, as for [`pointer`](JsonValue::pointer).
+    pub fn clone_at(&self, pointer: &str) -> Option<JsonValue> {
+        self.pointer(pointer).cloned()
+    }
+
+    /// Looks up a value by JSON Pointer like [`pointer`](JsonValue::pointer),
+    /// for modification.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut JsonValue> {
+        tokens(pointer)?.try_fold(self, |target, token| match target {
+            JsonValue::Object(map) => map.get_mut(&token),
+            JsonValue::Array(items) => items.get_mut(parse_index(&token)?),
+            _ => None,
+        })
+    }
+
+    /// Sets the value at a JSON Pointer, creating the arrays and objects on
+    /// the way to it that do not exist yet, so that `/a/b/0` set in an empty
+    /// object gives `{"a": {"b": [value]}}`.
+    ///
+    /// An existing value at the pointer is replaced, while an array index one
+    /// past the end, or `-`, appends to the array. A container created for a
+    /// token of `0` or `-` is an array and for any other token an object.
+    /// Fails with [`PatchError::PathNotFound`] if the pointer is malformed or
+    /// leads through a value that is not a container, or past the end of an
+    /// array.
+    pub fn set_pointer(&mut self, pointer: &str, value: JsonValue) -> Result<(), PatchError> {
+        let not_found = || PatchError::PathNotFound(pointer.to_string());
+        let tokens: Vec<_> = tokens(pointer).ok_or_else(not_found)?.collect();
+        let mut target = self;
+        for (i, token) in tokens.iter().enumerate() {
+            let rest = &tokens[i + 1..];
+            target = match target {
+                JsonValue::Object(map) => {
+                    if !map.contains_key(token) {
+                        map.insert(token.clone(), vivify(rest, value));
+                        return Ok(());
+                    }
+                    map.get_mut(token).unwrap()
+                }
+                JsonValue::Array(items) => {
+                    let index = match token.as_str() {
+                        "-" => items.len(),
+                        token => parse_index(token)
+                            .filter(|&i| i <= items.len())
+                            .ok_or_else(not_found)?,
+                    };
+                    if index == items.len() {
+                        items.push(vivify(rest, value));
+                        return Ok(());
+                    }
+                    &mut items[index]
+                }
+                _ => return Err(not_found()),
+            };
+        }
+        *target = value;
+        Ok(())
+    }
+
+    /// Returns the value at a JSON Pointer for modification, first inserting
+    /// the value `f` returns if there is none, along with the containers on
+    /// the way to it, as [`set_pointer`](JsonValue::set_pointer) does.
+    ///
+    /// A value that exists is returned unchanged, and `f` is not called. Fails
+    /// like `set_pointer` with [`PatchError::PathNotFound`].
+    pub fn pointer_or_insert_with(
+        &mut self,
+        pointer: &str,
+        f: impl FnOnce() -> JsonValue,
+    ) -> Result<&mut JsonValue, PatchError> {
+        let not_found = || PatchError::PathNotFound(pointer.to_string());
+        let tokens: Vec<_> = tokens(pointer).ok_or_else(not_found)?.collect();
+        let (last, path) = match tokens.split_last() {
+            Some(split) => split,
+            None => return Ok(self),
+        };
+        let mut target = self;
+        for (token, next) in path.iter().zip(&tokens[1..]) {
+            target =
+                child_or_insert(target, token, || container_for(next)).ok_or_else(not_found)?;
+        }
+        child_or_insert(target, last, f).ok_or_else(not_found)
+    }
+}
+
+/// Returns the member or element `token` of a container, first inserting the
+/// value `make` returns if an object lacks the member or the token is one past
+/// the end of an array, or `-`.
+fn child_or_insert<'v>(
+    target: &'v mut JsonValue,
+    token: &str,
+    make: impl FnOnce() -> JsonValue,
+) -> Option<&'v mut JsonValue> {
+    match target {
+        JsonValue::Object(map) => Some(map.entry(token.to_string()).or_insert_with(make)),
+        JsonValue::Array(items) => {
+            let index = match token {
+                "-" => items.len(),
+                token => parse_index(token).filter(|&i| i <= items.len())?,
+            };
+            if index == items.len() {
+                items.push(make());
+            }
+            Some(&mut items[index])
+        }
+        _ => None,
+    }
+}
+
+/// The empty container created for a missing value that `token` is looked
+/// up in: an array for a token of `0` or `-`, and an object for any other.
+fn container_for(token: &str) -> JsonValue {
+    if token == "0" || token == "-" {
+        JsonValue::Array(Vec::new())
+    } else {
+        JsonValue::Object(Map::default())
+    }
+}
+
+/// Builds the containers that `tokens` lead through to reach `value`.
+fn vivify(tokens: &[String], value: JsonValue) -> JsonValue {
+    tokens.iter().rev().fold(value, |value, token| {
+        if token == "0" || token == "-" {
+            JsonValue::Array(vec![value])
+        } else {
+            let mut map = Map::default();
+            map.insert(token.clone(), value);
+            JsonValue::Object(map)
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_pointer() {
+        let value = parse_complete(r#"{"a": [{"b": 1}, 2], "c/d": 3, "e~f": 4, "": 5}"#).unwrap();
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(value.pointer("/a/0/b"), Some(&JsonValue::Int(1)));
+        assert_eq!(value.pointer("/a/1"), Some(&JsonValue::Int(2)));
+        assert_eq!(value.pointer("/c~1d"), Some(&JsonValue::Int(3)));
+        assert_eq!(value.pointer("/e~0f"), Some(&JsonValue::Int(4)));
+        assert_eq!(value.pointer("/"), Some(&JsonValue::Int(5)));
+
+        assert_eq!(value.pointer("a"), None);
+        assert_eq!(value.pointer("/a/2"), None);
+        assert_eq!(value.pointer("/a/x"), None);
+        assert_eq!(value.pointer("/a/01"), None);
+        assert_eq!(value.pointer("/a/-"), None);
+        assert_eq!(value.pointer("/a/1/b"), None);
+        assert_eq!(value.pointer("/missing"), None);
+    }
+
+    #[test]
+    fn test_clone_at() {
+        let value = parse_complete(r#"{"a": {"b": {"c": [1, 2]}, "d": 3}, "e": [4]}"#).unwrap();
+        let mut b = value.clone_at("/a/b").unwrap();
+        assert_eq!(b, parse_complete(r#"{"c": [1, 2]}"#).unwrap());
+        b.set_pointer("/c/0", "x".into()).unwrap();
+        assert_eq!(value.pointer("/a/b/c/0"), Some(&JsonValue::Int(1)));
+        assert_eq!(value.clone_at("/e/0"), Some(JsonValue::Int(4)));
+        assert_eq!(value.clone_at(""), Some(value.clone()));
+        assert_eq!(value.clone_at("/a/x"), None);
+        assert_eq!(value.clone_at("a"), None);
+    }
+
+    #[test]
+    fn test_pointer_mut() {
+        let mut value = parse_complete(r#"{"a": [{"b": 1}, 2], "c": null}"#).unwrap();
+        *value.pointer_mut("/a/0/b").unwrap() = "x".into();
+        value.set_pointer("/a/1", JsonValue::Bool(true)).unwrap();
+        value.set_pointer("/a/-", JsonValue::Null).unwrap();
+        value.set_pointer("/a/0/d/e", 1.into()).unwrap();
+        value.set_pointer("/c", 2.into()).unwrap();
+        assert_eq!(
+            value,
+            parse_complete(r#"{"a": [{"b": "x", "d": {"e": 1}}, true, null], "c": 2}"#).unwrap()
+        );
+        assert_eq!(value.pointer_mut("/a/5"), None);
+
+        let mut doc = JsonValue::Object(Map::default());
+        doc.set_pointer("/a/b/0", "v".into()).unwrap();
+        doc.set_pointer("/a/c/-/d", 1.into()).unwrap();
+        doc.set_pointer("/a/b/1", "w".into()).unwrap();
+        assert_eq!(
+            doc,
+            parse_complete(r#"{"a": {"b": ["v", "w"], "c": [{"d": 1}]}}"#).unwrap()
+        );
+        doc.set_pointer("", JsonValue::Null).unwrap();
+        assert_eq!(doc, JsonValue::Null);
+
+        let mut value = parse_complete(r#"{"a": [1], "s": "x"}"#).unwrap();
+        let not_found = |path: &str| Err(PatchError::PathNotFound(path.to_string()));
+        assert_eq!(value.set_pointer("/a/2", 0.into()), not_found("/a/2"));
+        assert_eq!(value.set_pointer("/s/t", 0.into()), not_found("/s/t"));
+        assert_eq!(value.set_pointer("a", 0.into()), not_found("a"));
+        assert_eq!(value, parse_complete(r#"{"a": [1], "s": "x"}"#).unwrap());
+    }
+
+    #[test]
+    fn test_pointer_or_insert_with() {
+        let mut doc = parse_complete(r#"{"a": {"b": 1}}"#).unwrap();
+        *doc.pointer_or_insert_with("/a/c/0/d", || 0.into()).unwrap() = 2.into();
+        let d = doc.pointer_or_insert_with("/a/c/0/d", || unreachable!());
+        assert_eq!(*d.unwrap(), JsonValue::Int(2));
+        doc.pointer_or_insert_with("/a/c/-", JsonValue::default)
+            .unwrap();
+        assert_eq!(
+            *doc.pointer_or_insert_with("/a/b", || 0.into()).unwrap(),
+            1.into()
+        );
+        assert_eq!(
+            doc,
+            parse_complete(r#"{"a": {"b": 1, "c": [{"d": 2}, null]}}"#).unwrap()
+        );
+        assert_eq!(
+            doc.pointer_or_insert_with("", || 0.into()).map(|v| v.len()),
+            Ok(Some(1))
+        );
+
+        let not_found = |path: &str| Err(PatchError::PathNotFound(path.to_string()));
+        assert_eq!(
+            doc.pointer_or_insert_with("/a/b/c", || 0.into()),
+            not_found("/a/b/c")
+        );
+        assert_eq!(
+            doc.pointer_or_insert_with("/a/c/3", || 0.into()),
+            not_found("/a/c/3")
+        );
+        assert_eq!(doc.pointer_or_insert_with("a", || 0.into()), not_found("a"));
+    }
+}
@@ -0,0 +1,294 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::JsonValue;
+
+/// What a step of a path selects from each value it is applied to.
+#[derive(Debug, PartialEq)]
+enum Selector {
+    Key(String),
+    Index(usize),
+    /// Every element of an array or member value of an object.
+    Wildcard,
+}
+
+/// A step of a path, applied either to the current values or, after `..`,
+/// to them and all their descendants.
+#[derive(Debug, PartialEq)]
+struct Step {
+    recursive: bool,
+    selector: Selector,
+}
+
+/// Parses a bracketed selector, `['key']`, `["key"]`, `[index]` or `[*]`,
+/// following its `[`. Returns it with the rest of the path.
+fn parse_bracket(path: &str) -> Option<(Selector, &str)> {
+    if let Some(rest) = path.strip_prefix("*]") {
+        return Some((Selector::Wildcard, rest));
+    }
+    if let Some(quote) = path.chars().next().filter(|c| *c == '\'' || *c == '"') {
+        let mut key = String::new();
+        let mut chars = path[1..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => key.push(chars.next()?.1),
+                c if c == quote => {
+                    let rest = path[1 + i + 1..].strip_prefix(']')?;
+                    return Some((Selector::Key(key), rest));
+                }
+                c => key.push(c),
+            }
+        }
+        return None;
+    }
+    let end = path.find(']')?;
+    let index = &path[..end];
+    if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((Selector::Index(index.parse().ok()?), &path[end + 1..]))
+}
+
+/// Parses `path` into its steps, or `None` if it is not a valid path.
+fn parse_path(path: &str) -> Option<Vec<Step>> {
+    let mut rest = path.strip_prefix('$')?;
+    let mut steps = Vec::new();
+    while !rest.is_empty() {
+        let (recursive, after) = match rest.strip_prefix("..") {
+            Some(after) => (true, after),
+            None => (false, rest),
+        };
+        let (selector, after) = if let Some(bracket) = after.strip_prefix('[') {
+            parse_bracket(bracket)?
+        } else {
+            // A dot is optional before a bracket, but required before a name.
+            let name = if recursive {
+                after
+            } else {
+                after.strip_prefix('.')?
+            };
+            let end = name.find(['.', '[']).unwrap_or(name.len());
+            let selector = match &name[..end] {
+                "" => return None,
+                "*" => Selector::Wildcard,
+                key => Selector::Key(key.to_string()),
+            };
+            (selector, &name[end..])
+        };
+        steps.push(Step {
+            recursive,
+            selector,
+        });
+        rest = after;
+    }
+    Some(steps)
+}
+
+/// Calls `f` with each value `selector` selects from `value`, in document
+/// order.
+fn for_each_selected<'v>(
+    value: &'v JsonValue,
+    selector: &Selector,
+    f: &mut impl FnMut(&'v JsonValue),
+) {
+    match selector {
+        Selector::Key(key) => value.get(key).into_iter().for_each(f),
+        Selector::Index(index) => value.get_index(*index).into_iter().for_each(f),
+        Selector::Wildcard => match value {
+            JsonValue::Object(map) => map.values().for_each(f),
+            JsonValue::Array(items) => items.iter().for_each(f),
+            _ => {}
+        },
+    }
+}
+
+/// Adds what `selector` selects from `value` to `out`.
+fn select_from<'v>(value: &'v JsonValue, selector: &Selector, out: &mut Vec<&'v JsonValue>) {
+    for_each_selected(value, selector, &mut |v| out.push(v));
+}
+
+/// Adds what `selector` selects from `value` and each of its descendants to
+/// `out`, in document order.
+fn select_recursive<'v>(value: &'v JsonValue, selector: &Selector, out: &mut Vec<&'v JsonValue>) {
+    select_from(value, selector, out);
+    match value {
+        JsonValue::Object(map) => {
+            for v in map.values() {
+                select_recursive(v, selector, out);
+            }
+        }
+        JsonValue::Array(items) => {
+            for v in items {
+                select_recursive(v, selector, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Counts the values `steps` select from `value`, as many as
+/// [`JsonValue::select`] would return, visiting each match in turn.
+fn count_from(value: &JsonValue, steps: &[Step]) -> usize {
+    let (step, rest) = match steps.split_first() {
+        Some(split) => split,
+        None => return 1,
+    };
+    let mut count = 0;
+    for_each_selected(value, &step.selector, &mut |v| count += count_from(v, rest));
+    if step.recursive {
+        let mut descend = |v: &JsonValue| count += count_from(v, steps);
+        match value {
+            JsonValue::Object(map) => map.values().for_each(&mut descend),
+            JsonValue::Array(items) => items.iter().for_each(&mut descend),
+            _ => {}
+        }
+    }
+    count
+}
+
+impl JsonValue {
+    /// Selects the values matched by a JSONPath expression.
+    ///
+    /// The supported subset is the root `$`, followed by any of `.key` or
+    /// `['key']` for object members, `[index]` for array elements, `.*` or
+    /// `[*]` for every element or member, and `..` before any of these to
+    /// apply it at every depth, as in `$..price`. Matches are returned in
+    /// document order; a path that is invalid or matches nothing selects no
+    /// values.
+    pub fn select(&self, path: &str) -> Vec<&JsonValue> {
+        let steps = match parse_path(path) {
+            Some(steps) => steps,
+            None => return Vec::new(),
+        };
+        let mut current = alloc::vec![self];
+        for step in &steps {
+            let mut next = Vec::new();
+            for value in current {
+                if step.recursive {
+                    select_recursive(value, &step.selector, &mut next);
+                } else {
+                    select_from(value, &step.selector, &mut next);
+                }
+            }
+            current = next;
+        }
+        current
+    }
+
+    /// Counts the values a JSONPath expression matches, the length of what
+    /// [`select`](JsonValue::select) would return, without collecting them.
+    pub fn count_matches(&self, path: &str) -> usize {
+        parse_path(path).map_or(0, |steps| count_from(self, &steps))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(parse_path("$"), Some(vec![]));
+        assert_eq!(
+            parse_path(r#"$.a['b.c'][0]..*["d\"e"][*]"#),
+            Some(vec![
+                Step {
+                    recursive: false,
+                    selector: Selector::Key("a".to_string())
+                },
+                Step {
+                    recursive: false,
+                    selector: Selector::Key("b.c".to_string())
+                },
+                Step {
+                    recursive: false,
+                    selector: Selector::Index(0)
+                },
+                Step {
+                    recursive: true,
+                    selector: Selector::Wildcard
+                },
+                Step {
+                    recursive: false,
+                    selector: Selector::Key("d\"e".to_string())
+                },
+                Step {
+                    recursive: false,
+                    selector: Selector::Wildcard
+                },
+            ])
+        );
+        for path in [
+            "", "a", "$a", "$.", "$..", "$[", "$[-1]", "$['a]", "$[a]", "$.a[0",
+        ] {
+            assert_eq!(parse_path(path), None, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let value = parse_complete(
+            r#"{"store": {
+                "book": [{"title": "A", "price": 8}, {"title": "B", "price": 12}],
+                "bicycle": {"price": 20, "parts": [{"price": 1}]}
+            }}"#,
+        )
+        .unwrap();
+        let titles: Vec<_> = value
+            .select("$.store.book[*].title")
+            .into_iter()
+            .filter_map(JsonValue::as_str)
+            .collect();
+        assert_eq!(titles, ["A", "B"]);
+
+        let mut prices: Vec<_> = value
+            .select("$..price")
+            .into_iter()
+            .filter_map(JsonValue::as_i64)
+            .collect();
+        prices.sort_unstable();
+        assert_eq!(prices, [1, 8, 12, 20]);
+
+        assert_eq!(
+            value.select("$['store']['book'][1].price"),
+            [&JsonValue::Int(12)]
+        );
+        assert_eq!(value.select("$.store.bicycle.*").len(), 2);
+        assert_eq!(value.select("$"), [&value]);
+        assert!(value.select("$.store.book[2]").is_empty());
+        assert!(value.select("$..missing").is_empty());
+        assert!(value.select("$.store.book.title").is_empty());
+        assert!(value.select("store").is_empty());
+    }
+
+    #[test]
+    fn test_count_matches() {
+        let value = parse_complete(
+            r#"{"order": {"lines": [
+                {"sku": "a", "discount": 5, "parts": [{"discount": 1}]},
+                {"sku": "b"},
+                {"sku": "c", "discount": 0, "bundle": {"lines": [{"discount": 2}]}}
+            ]}}"#,
+        )
+        .unwrap();
+        assert_eq!(value.count_matches("$..discount"), 4);
+        assert_eq!(value.count_matches("$.order.lines[*].discount"), 2);
+        assert_eq!(value.count_matches("$..lines[*]"), 4);
+        assert_eq!(value.count_matches("$..*"), value.select("$..*").len());
+        assert_eq!(value.count_matches("$"), 1);
+        for path in ["$..missing", "$.order.lines[3]", "$.order.sku", "order"] {
+            assert_eq!(value.count_matches(path), 0, "{}", path);
+        }
+        for path in ["$..discount", "$..lines[*]", "$.order..*", "$..[0]"] {
+            assert_eq!(
+                value.count_matches(path),
+                value.select(path).len(),
+                "{}",
+                path
+            );
+        }
+    }
+}
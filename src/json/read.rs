@@ -0,0 +1,101 @@
+use std::{
+    fmt,
+    io::{self, BufReader, Read},
+};
+
+use super::{parse_bytes, JsonValue, ParseError};
+
+/// Why [`parse_reader`] failed.
+#[derive(Debug)]
+pub enum ReadError {
+    /// Reading the input failed.
+    Io(io::Error),
+    /// The input was read but is not valid JSON.
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "read error: {}", e),
+            ReadError::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(e) => Some(e),
+            ReadError::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(e: ParseError) -> Self {
+        ReadError::Parse(e)
+    }
+}
+
+/// Reads `reader` to its end and parses what it held as a whole JSON
+/// document, like [`parse_bytes`].
+///
+/// The reader is buffered, so an unbuffered one such as a `File` need not be
+/// wrapped first. The whole input is held in memory while it is parsed; see
+/// [`StreamParser`](super::StreamParser) to parse input as it arrives.
+pub fn parse_reader<R: Read>(reader: R) -> Result<JsonValue, ReadError> {
+    let mut input = Vec::new();
+    BufReader::new(reader).read_to_end(&mut input)?;
+    Ok(parse_bytes(&input)?)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::super::{parse_complete, ErrorKind};
+    use super::*;
+
+    /// A reader failing after the bytes it was given.
+    struct Failing<'a>(&'a [u8]);
+
+    impl Read for Failing<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::BrokenPipe, "gone"));
+            }
+            self.0.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_parse_reader() {
+        let input = br#"{"a": [1, "b"]}"#;
+        assert_eq!(
+            parse_reader(Cursor::new(&input[..])).unwrap(),
+            parse_complete(core::str::from_utf8(input).unwrap()).unwrap()
+        );
+
+        match parse_reader(Cursor::new(&b"[1, \xff]"[..])) {
+            Err(ReadError::Parse(e)) => {
+                assert_eq!((e.offset, e.kind), (4, ErrorKind::InvalidUtf8));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        match parse_reader(Failing(b"[1, ")) {
+            Err(ReadError::Io(e)) => assert_eq!(e.kind(), io::ErrorKind::BrokenPipe),
+            other => panic!("expected an I/O error, got {:?}", other),
+        }
+        let error = parse_reader(Cursor::new(&b"[1"[..])).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("parse error at line 1, column 1: unclosed array"));
+    }
+}
@@ -0,0 +1,248 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use nom::{error::make_error, Err};
+
+use super::{parse_value, Builder, Error, JsonValue, Map, ParseError, ParseOptions, ValueBuilder};
+
+/// Builds a [`JsonValue`] like [`ValueBuilder`], collecting the errors in
+/// malformed elements instead of stopping at the first.
+struct RecoveringBuilder<'a> {
+    errors: Vec<Error<'a>>,
+}
+
+impl<'a> RecoveringBuilder<'a> {
+    /// Records `error`, unless one was already recorded at the same position,
+    /// as happens when input ends inside several containers.
+    fn record(&mut self, error: Error<'a>) {
+        match self.errors.last() {
+            Some(last) if last.input.len() == error.input.len() => {}
+            _ => self.errors.push(error),
+        }
+    }
+}
+
+impl<'a> Builder<'a> for RecoveringBuilder<'a> {
+    type Value = JsonValue;
+    type Object = Map;
+
+    fn null(&mut self) -> JsonValue {
+        ValueBuilder.null()
+    }
+
+    fn bool(&mut self, b: bool) -> JsonValue {
+        ValueBuilder.bool(b)
+    }
+
+    fn int(&mut self, i: i64) -> JsonValue {
+        ValueBuilder.int(i)
+    }
+
+    fn float(&mut self, f: f64) -> JsonValue {
+        ValueBuilder.float(f)
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) -> JsonValue {
+        ValueBuilder.string(s)
+    }
+
+    fn raw_number(&mut self, token: &'a str) -> JsonValue {
+        ValueBuilder.raw_number(token)
+    }
+
+    #[cfg(feature = "bigint")]
+    fn big_int(&mut self, token: &'a str) -> JsonValue {
+        ValueBuilder.big_int(token)
+    }
+
+    fn array(&mut self, items: Vec<JsonValue>) -> JsonValue {
+        ValueBuilder.array(items)
+    }
+
+    fn new_object(&mut self, capacity: usize) -> Map {
+        ValueBuilder.new_object(capacity)
+    }
+
+    fn contains_key(&self, object: &Map, key: &str) -> bool {
+        ValueBuilder.contains_key(object, key)
+    }
+
+    fn insert(&mut self, object: &mut Map, key: Cow<'a, str>, value: JsonValue) {
+        ValueBuilder.insert(object, key, value);
+    }
+
+    fn object(&mut self, object: Map) -> JsonValue {
+        ValueBuilder.object(object)
+    }
+
+    fn recover(&mut self, error: Error<'a>) -> Result<(), Error<'a>> {
+        self.record(error);
+        Ok(())
+    }
+}
+
+/// Parses `input` as a whole JSON document, reporting every malformed element
+/// of an array or object instead of stopping at the first.
+///
+/// Each malformed element is skipped up to the next `,`, `]` or `}` and left
+/// out of the result, and a container that is not closed properly ends where
+/// its parent continues. Returns the value parsed, if any, with the errors in
+/// the order they occurred; the document is valid if there are none.
+pub fn parse_recovering(input: &str) -> (Option<JsonValue>, Vec<ParseError>) {
+    let mut builder = RecoveringBuilder { errors: Vec::new() };
+    let value = match parse_value(input, &ParseOptions::default(), 0, &mut builder) {
+        Ok((rest, value)) => {
+            if !rest.is_empty() {
+                builder.record(make_error(rest, nom::error::ErrorKind::Eof));
+            }
+            Some(value)
+        }
+        Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+            builder.record(e);
+            None
+        }
+        Err(Err::Incomplete(_)) => None,
+    };
+    let errors = builder
+        .errors
+        .into_iter()
+        .map(|e| ParseError::new(input, Err::Failure(e)))
+        .collect();
+    (value, errors)
+}
+
+/// Finds where the next value likely starts in `input`, which starts at a
+/// parse failure, such as the rest of a stream from a [`ParseError`]'s
+/// offset, so that parsing can resume there.
+///
+/// The scan balances brackets and skips strings, passing over the
+/// containers nested in the malformed record. The record ends at a closing
+/// bracket that the scan did not see opened, or else, if there is none
+/// further on, at the first comma or line break outside the brackets the
+/// scan saw opened: a failure within a container is followed by its closing
+/// bracket, while one in a top-level record, such as a malformed scalar, is
+/// not. Returns the offset in `input` of the first value after the end of
+/// the record, or `None` if none starts after it.
+///
+/// Telling the two apart may take scanning all of `input`.
+pub fn resync(input: &str) -> Option<usize> {
+    let mut depth = 0;
+    // Whether a closing bracket the scan did not see opened ended the record.
+    let mut ended = false;
+    // Whether a comma or line break outside any bracket has been passed.
+    let mut separated = false;
+    // Where the first value after such a separator starts, until a closing
+    // bracket shows that the separator was within a container.
+    let mut next = None;
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if depth == 0 && is_value_start(c) {
+            if ended {
+                return Some(i);
+            }
+            if separated && next.is_none() {
+                next = Some(i);
+            }
+        }
+        match c {
+            '"' => {
+                let mut escaped = false;
+                loop {
+                    match chars.next() {
+                        None => return next,
+                        Some(_) if escaped => escaped = false,
+                        Some((_, '\\')) => escaped = true,
+                        Some((_, '"')) => break,
+                        Some(_) => {}
+                    }
+                }
+            }
+            '[' | '{' => depth += 1,
+            ']' | '}' if depth == 0 => {
+                ended = true;
+                next = None;
+            }
+            ']' | '}' => depth -= 1,
+            ',' | '\n' | '\r' if depth == 0 => separated = true,
+            _ => {}
+        }
+    }
+    next
+}
+
+/// Whether `c` may start a JSON value.
+fn is_value_start(c: char) -> bool {
+    matches!(c, '"' | '[' | '{' | '-' | '0'..='9' | 't' | 'f' | 'n')
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{parse_complete, parse_document, parse_prefix, ErrorKind};
+    use super::*;
+
+    #[test]
+    fn test_recovering() {
+        let input = r#"{"a": [1, tru, 3], "b": {"c": "x", "d": nul}, "e": 5}"#;
+        let (value, errors) = parse_recovering(input);
+        assert_eq!(
+            value,
+            Some(parse_complete(r#"{"a": [1, 3], "b": {"c": "x"}, "e": 5}"#).unwrap())
+        );
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].offset, input.find("tru").unwrap());
+        assert_eq!(errors[0].path, "/a/1");
+        assert_eq!(errors[1].offset, input.find("nul}").unwrap());
+        assert_eq!(errors[1].path, "/b/d");
+
+        let input = r#"[1 2, {"k" 1, "l": [3}, "s, 4]"#;
+        let (value, errors) = parse_recovering(input);
+        assert_eq!(value, Some(parse_complete(r#"[1, {"l": [3]}]"#).unwrap()));
+        let kinds: Vec<_> = errors.iter().map(|e| e.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            [
+                ErrorKind::ExpectedSeparator(']'),
+                ErrorKind::Char(':'),
+                ErrorKind::ExpectedSeparator(']'),
+                ErrorKind::Char('"'),
+                ErrorKind::Unclosed('['),
+            ]
+        );
+
+        let (value, errors) = parse_recovering("[1,,2,]");
+        assert_eq!(value, Some(parse_complete("[1, 2]").unwrap()));
+        let kinds: Vec<_> = errors.iter().map(|e| (e.offset, e.kind.clone())).collect();
+        assert_eq!(
+            kinds,
+            [(3, ErrorKind::ExpectedValue), (6, ErrorKind::ExpectedValue)]
+        );
+
+        let (value, errors) = parse_recovering("[1]]");
+        assert_eq!(value, Some(parse_complete("[1]").unwrap()));
+        assert_eq!(errors[0].offset, 3);
+        assert_eq!(parse_recovering("tru").0, None);
+        assert_eq!(parse_recovering(r#"{"a": [1]}"#).1, []);
+    }
+
+    #[test]
+    fn test_resync() {
+        let input = r#"{"id": 1, "tags": [{"k": "}"}], "name": tru, "n": {"m": 2}}
+            {"id": 2}"#;
+        let error = parse_document(input).unwrap_err();
+        let at = error.offset + resync(&input[error.offset..]).unwrap();
+        assert_eq!(at, input.find(r#"{"id": 2}"#).unwrap());
+        assert_eq!(
+            parse_prefix(&input[at..]).unwrap().0,
+            parse_complete(r#"{"id": 2}"#).unwrap()
+        );
+
+        assert_eq!(resync(r#"1, "x": [2]]] [3]"#), Some(14));
+        assert_eq!(resync(r#"x "\"]{" }"#), None);
+        assert_eq!(resync(r#"x, {"a": 1}, [2]"#), Some(3));
+        assert_eq!(resync("tru\n5\n\"a\""), Some(4));
+        let nested = "tru,\n  \"b\": [2]\n}\n{\"c\": 3}";
+        assert_eq!(resync(nested), nested.find("{\"c\""));
+        assert_eq!(resync("x,"), None);
+        assert_eq!(resync(r#"x } {"#), Some(4));
+        assert_eq!(resync(""), None);
+    }
+}
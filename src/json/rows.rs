@@ -0,0 +1,228 @@
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+
+use super::{
+    parse_document, parse_value, ConversionError, ErrorKind, ExtractError, FromJson, JsonValue,
+    ParseError, ParseOptions, ValueBuilder, JSON_WHITESPACE,
+};
+
+/// A type that can be read from an array of a fixed number of elements by
+/// [`parse_rows`], such as a tuple of [`FromJson`] types.
+///
+/// Implementing it for a struct takes one [`FromJson`] conversion per
+/// element:
+///
+/// ```
+/// use parser::json::{parse_rows, ExtractError, FromJson, FromJsonRow, JsonValue};
+///
+/// struct Score {
+///     id: u32,
+///     score: f64,
+/// }
+///
+/// impl FromJsonRow for Score {
+///     const ARITY: usize = 2;
+///
+///     fn from_row(row: &[JsonValue]) -> Result<Self, ExtractError> {
+///         Ok(Score {
+///             id: u32::from_json(&row[0])?,
+///             score: f64::from_json(&row[1])?,
+///         })
+///     }
+/// }
+///
+/// let scores: Vec<Score> = parse_rows("[[1, 0.5], [2, 1.5]]").unwrap();
+/// assert_eq!((scores[1].id, scores[1].score), (2, 1.5));
+/// ```
+pub trait FromJsonRow: Sized {
+    /// The number of elements of a row.
+    const ARITY: usize;
+
+    /// Reads a row from its elements, of which there are
+    /// [`ARITY`](FromJsonRow::ARITY).
+    fn from_row(row: &[JsonValue]) -> Result<Self, ExtractError>;
+}
+
+/// Implements [`FromJsonRow`] for tuples of [`FromJson`] types, locating an
+/// error by the index of the element.
+macro_rules! from_json_row {
+    ($($len:literal => ($($t:ident $i:tt),+))*) => {
+        $(
+            impl<$($t: FromJson),+> FromJsonRow for ($($t,)+) {
+                const ARITY: usize = $len;
+
+                fn from_row(row: &[JsonValue]) -> Result<Self, ExtractError> {
+                    Ok(($(
+                        $t::from_json(&row[$i]).map_err(|e| e.within(stringify!($i)))?,
+                    )+))
+                }
+            }
+        )*
+    };
+}
+
+from_json_row! {
+    1 => (A 0)
+    2 => (A 0, B 1)
+    3 => (A 0, B 1, C 2)
+    4 => (A 0, B 1, C 2, D 3)
+    5 => (A 0, B 1, C 2, D 3, E 4)
+    6 => (A 0, B 1, C 2, D 3, E 4, F 5)
+    7 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    8 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+}
+
+/// Skips the JSON whitespace at the start of `input`.
+fn ws(input: &str) -> &str {
+    input.trim_start_matches(JSON_WHITESPACE)
+}
+
+/// Parses `input`, a whole JSON document that is an array of rows, as the
+/// `T` each row is read as.
+///
+/// Every row is an array of [`T::ARITY`](FromJsonRow::ARITY) elements,
+/// parsed into a buffer that all the rows share, so that no array is built
+/// per row as [`parse_document`] would. A row of another length fails with
+/// [`ErrorKind::RowArity`], and one that `T` cannot be read from, or an
+/// element that is not an array, with [`ErrorKind::InvalidRow`]; both are
+/// located at the row, with the error's path leading to the row or the
+/// element at fault. Malformed input fails as `parse_document` reports it.
+pub fn parse_rows<T: FromJsonRow>(input: &str) -> Result<Vec<T>, ParseError> {
+    let options = ParseOptions::default();
+    let malformed = || match parse_document(input) {
+        Err(error) => error,
+        Ok(_) => unreachable!("`parse_rows` rejected a valid document"),
+    };
+    let invalid = |at: &str, path, kind| ParseError {
+        path,
+        ..ParseError::at(input, input.len() - at.len(), kind)
+    };
+    let mut rows = Vec::new();
+    let mut row = Vec::with_capacity(T::ARITY);
+    let mut rest = match ws(input).strip_prefix('[') {
+        Some(rest) => ws(rest),
+        None => {
+            parse_document(input)?;
+            let error = ExtractError::from(ConversionError::UnexpectedType("an array"));
+            return Err(invalid(
+                ws(input),
+                String::new(),
+                ErrorKind::InvalidRow(Box::new(error)),
+            ));
+        }
+    };
+    if let Some(after) = rest.strip_prefix(']') {
+        return match ws(after) {
+            "" => Ok(rows),
+            _ => Err(malformed()),
+        };
+    }
+    loop {
+        let start = rest;
+        match rest.strip_prefix('[') {
+            Some(after) => {
+                rest = ws(after);
+                row.clear();
+                match rest.strip_prefix(']') {
+                    Some(after) => rest = after,
+                    None => loop {
+                        let (after, value) = parse_value(rest, &options, 2, &mut ValueBuilder)
+                            .map_err(|_| malformed())?;
+                        row.push(value);
+                        let after = ws(after);
+                        match after.chars().next() {
+                            Some(',') => rest = ws(&after[1..]),
+                            Some(']') => {
+                                rest = &after[1..];
+                                break;
+                            }
+                            _ => return Err(malformed()),
+                        }
+                    },
+                }
+                if row.len() != T::ARITY {
+                    return Err(invalid(
+                        start,
+                        format!("/{}", rows.len()),
+                        ErrorKind::RowArity(T::ARITY),
+                    ));
+                }
+                match T::from_row(&row) {
+                    Ok(value) => rows.push(value),
+                    Err(e) => {
+                        let path = format!("/{}{}", rows.len(), e.path());
+                        return Err(invalid(start, path, ErrorKind::InvalidRow(Box::new(e))));
+                    }
+                }
+            }
+            None => {
+                parse_value(rest, &options, 1, &mut ValueBuilder).map_err(|_| malformed())?;
+                let error = ExtractError::from(ConversionError::UnexpectedType("an array"));
+                let path = format!("/{}", rows.len());
+                return Err(invalid(start, path, ErrorKind::InvalidRow(Box::new(error))));
+            }
+        }
+        rest = ws(rest);
+        match rest.chars().next() {
+            Some(',') => rest = ws(&rest[1..]),
+            Some(']') if ws(&rest[1..]).is_empty() => return Ok(rows),
+            _ => return Err(malformed()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_rows() {
+        let input = r#" [[1, "ada", 9.5], [2, "lin", 7], [ 3 , "" , -1e2 ] ] "#;
+        let rows: Vec<(i64, String, f64)> = parse_rows(input).unwrap();
+        assert_eq!(
+            rows,
+            [
+                (1, "ada".to_string(), 9.5),
+                (2, "lin".to_string(), 7.0),
+                (3, String::new(), -100.0)
+            ]
+        );
+        assert_eq!(parse_rows::<(i64,)>("[]"), Ok(Vec::new()));
+        let nested: Vec<(Vec<u8>, Option<bool>)> = parse_rows("[[[1, 2], null]]").unwrap();
+        assert_eq!(nested, [(vec![1, 2], None)]);
+
+        let input = r#"[[1, "a", 1.5], [2, "b"]]"#;
+        let error = parse_rows::<(i64, String, f64)>(input).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::RowArity(3));
+        assert_eq!((error.offset, error.path.as_str()), (16, "/1"));
+        assert_eq!(error.kind.to_string(), "expected a row of 3 elements");
+
+        let input = r#"[[1, "a"], [2, 3]]"#;
+        let error = parse_rows::<(i64, String)>(input).unwrap_err();
+        assert_eq!((error.offset, error.path.as_str()), (11, "/1/1"));
+        assert_eq!(error.kind.to_string(), "invalid row: expected a string");
+        let error = parse_rows::<(Vec<u8>,)>("[[[1, -1]]]").unwrap_err();
+        assert_eq!(error.path, "/0/0/1");
+        let error = parse_rows::<(i64,)>(r#"[[1], {"a": 1}]"#).unwrap_err();
+        assert_eq!((error.offset, error.path.as_str()), (6, "/1"));
+        let error = parse_rows::<(i64,)>(" {}").unwrap_err();
+        assert_eq!((error.offset, error.path.as_str()), (1, ""));
+
+        for input in [
+            "[[1], [2]",
+            "[[1] [2]]",
+            "[[1,]]",
+            "[[1]] x",
+            "[[1], tru]",
+            "",
+        ] {
+            assert_eq!(
+                parse_rows::<(i64,)>(input),
+                Err(parse_document(input).unwrap_err()),
+                "{:?}",
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{convert::TryFrom, fmt};
+
+use super::{pointer::escape, ConversionError, JsonValue};
+
+/// A violation of a [`Schema`], located by the JSON Pointer of the value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    /// The value is not of the type the schema expects, named here.
+    WrongType {
+        path: String,
+        expected: &'static str,
+    },
+    /// The object lacks a required key.
+    MissingKey { path: String, key: String },
+    /// The number is outside the range the schema allows.
+    OutOfRange { path: String },
+    /// The string or array is shorter or longer than the schema allows.
+    WrongLength { path: String },
+}
+
+impl ValidationError {
+    /// The JSON Pointer of the value violating the schema.
+    pub fn path(&self) -> &str {
+        match self {
+            ValidationError::WrongType { path, .. }
+            | ValidationError::MissingKey { path, .. }
+            | ValidationError::OutOfRange { path }
+            | ValidationError::WrongLength { path } => path,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::WrongType { path, expected } => {
+                write!(f, "expected {} at {:?}", expected, path)
+            }
+            ValidationError::MissingKey { path, key } => {
+                write!(f, "missing key {:?} at {:?}", key, path)
+            }
+            ValidationError::OutOfRange { path } => write!(f, "number out of range at {:?}", path),
+            ValidationError::WrongLength { path } => write!(f, "wrong length at {:?}", path),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Any,
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array(Box<Schema>),
+    Object(Vec<Member>),
+}
+
+#[derive(Debug, Clone)]
+struct Member {
+    key: String,
+    schema: Schema,
+    required: bool,
+}
+
+/// The expected shape of a document, checked with [`Schema::validate`].
+///
+/// Schemas are built from the constructors for each type and narrowed with
+/// constraints, as in
+/// `Schema::object([("name", Schema::string()), ("age", Schema::integer().range(0, 150))])`.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    kind: Kind,
+    min: Option<f64>,
+    max: Option<f64>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+}
+
+impl Schema {
+    fn new(kind: Kind) -> Self {
+        Schema {
+            kind,
+            min: None,
+            max: None,
+            min_length: None,
+            max_length: None,
+        }
+    }
+
+    /// Accepts any value.
+    pub fn any() -> Self {
+        Schema::new(Kind::Any)
+    }
+
+    pub fn null() -> Self {
+        Schema::new(Kind::Null)
+    }
+
+    pub fn boolean() -> Self {
+        Schema::new(Kind::Boolean)
+    }
+
+    /// Accepts integers, including floats with no fractional part that fit an
+    /// `i64`.
+    pub fn integer() -> Self {
+        Schema::new(Kind::Integer)
+    }
+
+    pub fn number() -> Self {
+        Schema::new(Kind::Number)
+    }
+
+    pub fn string() -> Self {
+        Schema::new(Kind::String)
+    }
+
+    /// Accepts arrays whose elements all match `items`.
+    pub fn array(items: Schema) -> Self {
+        Schema::new(Kind::Array(Box::new(items)))
+    }
+
+    /// Accepts objects that have all of `members`, each matching its schema.
+    /// Other keys are allowed.
+    pub fn object<K: Into<String>>(members: impl IntoIterator<Item = (K, Schema)>) -> Self {
+        let members = members
+            .into_iter()
+            .map(|(key, schema)| Member {
+                key: key.into(),
+                schema,
+                required: true,
+            })
+            .collect();
+        Schema::new(Kind::Object(members))
+    }
+
+    /// Allows an object to have the member `key`, which must then match
+    /// `schema`. Has no effect on schemas for other types.
+    pub fn optional(mut self, key: impl Into<String>, schema: Schema) -> Self {
+        if let Kind::Object(members) = &mut self.kind {
+            members.push(Member {
+                key: key.into(),
+                schema,
+                required: false,
+            });
+        }
+        self
+    }
+
+    /// Restricts numbers to between `min` and `max` inclusive.
+    pub fn range(mut self, min: impl Into<f64>, max: impl Into<f64>) -> Self {
+        self.min = Some(min.into());
+        self.max = Some(max.into());
+        self
+    }
+
+    /// Restricts the characters of a string, or the elements of an array, to
+    /// between `min` and `max` inclusive.
+    pub fn length(mut self, min: usize, max: usize) -> Self {
+        self.min_length = Some(min);
+        self.max_length = Some(max);
+        self
+    }
+
+    /// Checks `value` against the schema, reporting every violation.
+    pub fn validate(&self, value: &JsonValue) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        self.check(value, &mut String::new(), &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Adds the violations by `value`, which is found at `path`, to `errors`.
+    fn check(&self, value: &JsonValue, path: &mut String, errors: &mut Vec<ValidationError>) {
+        let wrong_type = |expected| ValidationError::WrongType {
+            path: path.clone(),
+            expected,
+        };
+        match &self.kind {
+            Kind::Any => {}
+            Kind::Null if value.is_null() => {}
+            Kind::Null => errors.push(wrong_type("null")),
+            Kind::Boolean if value.as_bool().is_some() => {}
+            Kind::Boolean => errors.push(wrong_type("a boolean")),
+            Kind::Integer => match i64::try_from(value) {
+                Ok(i) => self.check_range(i as f64, path, errors),
+                Err(ConversionError::OutOfRange) => {
+                    errors.push(ValidationError::OutOfRange { path: path.clone() })
+                }
+                Err(_) => errors.push(wrong_type("an integer")),
+            },
+            Kind::Number => match value.as_f64() {
+                Some(f) => self.check_range(f, path, errors),
+                None => errors.push(wrong_type("a number")),
+            },
+            Kind::String => match value.as_str() {
+                Some(s) => self.check_length(s.chars().count(), path, errors),
+                None => errors.push(wrong_type("a string")),
+            },
+            Kind::Array(schema) => match value.as_array() {
+                Some(items) => {
+                    self.check_length(items.len(), path, errors);
+                    for (i, item) in items.iter().enumerate() {
+                        let len = path.len();
+                        path.push('/');
+                        path.push_str(&i.to_string());
+                        schema.check(item, path, errors);
+                        path.truncate(len);
+                    }
+                }
+                None => errors.push(wrong_type("an array")),
+            },
+            Kind::Object(members) => match value.as_object() {
+                Some(map) => {
+                    for member in members {
+                        match map.get(&member.key) {
+                            Some(value) => {
+                                let len = path.len();
+                                path.push('/');
+                                path.push_str(&escape(&member.key));
+                                member.schema.check(value, path, errors);
+                                path.truncate(len);
+                            }
+                            None if member.required => errors.push(ValidationError::MissingKey {
+                                path: path.clone(),
+                                key: member.key.clone(),
+                            }),
+                            None => {}
+                        }
+                    }
+                }
+                None => errors.push(wrong_type("an object")),
+            },
+        }
+    }
+
+    fn check_range(&self, f: f64, path: &str, errors: &mut Vec<ValidationError>) {
+        if self.min.is_some_and(|min| f < min) || self.max.is_some_and(|max| f > max) {
+            errors.push(ValidationError::OutOfRange {
+                path: path.to_string(),
+            });
+        }
+    }
+
+    fn check_length(&self, len: usize, path: &str, errors: &mut Vec<ValidationError>) {
+        if self.min_length.is_some_and(|min| len < min)
+            || self.max_length.is_some_and(|max| len > max)
+        {
+            errors.push(ValidationError::WrongLength {
+                path: path.to_string(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    fn person() -> Schema {
+        Schema::object([
+            ("name", Schema::string().length(1, 8)),
+            ("age", Schema::integer().range(0, 150)),
+        ])
+        .optional("tags", Schema::array(Schema::string()))
+    }
+
+    #[test]
+    fn test_validate() {
+        let schema = Schema::array(person());
+        let valid = parse_complete(
+            r#"[{"name": "Ann", "age": 30.0, "tags": ["x"]}, {"name": "Bo", "age": 0}]"#,
+        )
+        .unwrap();
+        assert_eq!(schema.validate(&valid), Ok(()));
+
+        let invalid =
+            parse_complete(r#"[{"name": "Ann", "age": 151}, {"name": "", "tags": [1]}]"#).unwrap();
+        let errors = schema.validate(&invalid).unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                ValidationError::OutOfRange {
+                    path: "/0/age".to_string()
+                },
+                ValidationError::WrongLength {
+                    path: "/1/name".to_string()
+                },
+                ValidationError::MissingKey {
+                    path: "/1".to_string(),
+                    key: "age".to_string()
+                },
+                ValidationError::WrongType {
+                    path: "/1/tags/0".to_string(),
+                    expected: "a string"
+                },
+            ]
+        );
+        assert_eq!(errors[3].path(), "/1/tags/0");
+        assert_eq!(errors[2].to_string(), r#"missing key "age" at "/1""#);
+
+        let errors = person().validate(&JsonValue::Int(1)).unwrap_err();
+        assert_eq!(
+            errors,
+            [ValidationError::WrongType {
+                path: String::new(),
+                expected: "an object"
+            }]
+        );
+        let age = parse_complete(r#"{"name": "x", "age": 1.5}"#).unwrap();
+        assert_eq!(person().validate(&age).unwrap_err()[0].path(), "/age");
+        assert_eq!(Schema::any().validate(&age), Ok(()));
+        assert!(Schema::number()
+            .range(0.5, 1)
+            .validate(&JsonValue::Number(1.5))
+            .is_err());
+    }
+}
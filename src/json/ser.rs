@@ -0,0 +1,849 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Write};
+#[cfg(feature = "std")]
+use std::io;
+
+use super::JsonValue;
+
+/// Writes `value` as JSON, compact when `indent` is `None` and otherwise with
+/// each element on its own line, indented by `indent` spaces per level.
+///
+/// Numbers are written from their value, not the text they were parsed from:
+/// an `Int` in decimal, and a float in the shortest form that reads back as
+/// the same `f64`, so `2.50` becomes `2.5`, `1E+2` becomes `100.0` and `-0`
+/// becomes `-0.0`. Floats always keep a `.` or an exponent so they re-parse
+/// as [`JsonValue::Number`] rather than [`JsonValue::Int`], using an exponent
+/// below `1e-5` and from `1e16` in magnitude. NaN and the infinities have no
+/// JSON representation and are written as `null`. Parsing with
+/// [`ParseOptions::raw_numbers`](super::ParseOptions::raw_numbers) keeps the
+/// text of each number instead, which is written back unchanged.
+pub(super) fn write_value<W: Write>(
+    w: &mut W,
+    value: &JsonValue,
+    indent: Option<usize>,
+) -> fmt::Result {
+    write_nested(w, value, indent, FloatFormat::Shortest, 0)
+}
+
+fn write_nested<W: Write>(
+    w: &mut W,
+    value: &JsonValue,
+    indent: Option<usize>,
+    float: FloatFormat,
+    depth: usize,
+) -> fmt::Result {
+    match value {
+        JsonValue::Number(f) => write_float_as(w, *f, float),
+        JsonValue::Int(i) => write!(w, "{}", i),
+        JsonValue::RawNumber(token) => w.write_str(token),
+        #[cfg(feature = "bigint")]
+        JsonValue::BigInt(b) => write!(w, "{}", b),
+        JsonValue::Str(s) => write_string(w, s),
+        JsonValue::Bool(b) => write!(w, "{}", b),
+        JsonValue::Null => w.write_str("null"),
+        JsonValue::Object(map) if map.is_empty() => w.write_str("{}"),
+        JsonValue::Object(map) => {
+            w.write_char('{')?;
+            for (i, (k, v)) in map.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_newline(w, indent, depth + 1)?;
+                write_string(w, k)?;
+                w.write_str(if indent.is_some() { ": " } else { ":" })?;
+                write_nested(w, v, indent, float, depth + 1)?;
+            }
+            write_newline(w, indent, depth)?;
+            w.write_char('}')
+        }
+        JsonValue::Array(items) if items.is_empty() => w.write_str("[]"),
+        JsonValue::Array(items) => {
+            w.write_char('[')?;
+            for (i, v) in items.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_newline(w, indent, depth + 1)?;
+                write_nested(w, v, indent, float, depth + 1)?;
+            }
+            write_newline(w, indent, depth)?;
+            w.write_char(']')
+        }
+    }
+}
+
+/// Counts the characters written to it, failing once there are more than
+/// `limit`.
+struct Measure {
+    len: usize,
+    limit: usize,
+}
+
+impl Write for Measure {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.len += s.chars().count();
+        if self.len > self.limit {
+            Err(fmt::Error)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Counts the bytes written to it.
+struct ByteCount(usize);
+
+impl Write for ByteCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// Whether `value` written by `write` takes at most `limit` characters.
+fn fits(
+    value: &JsonValue,
+    limit: usize,
+    write: fn(&mut Measure, &JsonValue) -> fmt::Result,
+) -> bool {
+    write(&mut Measure { len: 0, limit }, value).is_ok()
+}
+
+/// Writes `value` on a single line, with a space after each `,` and `:`.
+fn write_inline<W: Write>(w: &mut W, value: &JsonValue) -> fmt::Result {
+    match value {
+        JsonValue::Object(map) => {
+            w.write_char('{')?;
+            for (i, (k, v)) in map.iter().enumerate() {
+                if i > 0 {
+                    w.write_str(", ")?;
+                }
+                write_string(w, k)?;
+                w.write_str(": ")?;
+                write_inline(w, v)?;
+            }
+            w.write_char('}')
+        }
+        JsonValue::Array(items) => {
+            w.write_char('[')?;
+            for (i, v) in items.iter().enumerate() {
+                if i > 0 {
+                    w.write_str(", ")?;
+                }
+                write_inline(w, v)?;
+            }
+            w.write_char(']')
+        }
+        scalar => write_nested(w, scalar, None, FloatFormat::Shortest, 0),
+    }
+}
+
+/// Writes `value`, which starts at `column` and is followed by `trailing`
+/// more characters on its line, on that line if it fits within `width` and
+/// otherwise with each element on its own line, deciding the same for each.
+fn write_fitted<W: Write>(
+    w: &mut W,
+    value: &JsonValue,
+    indent: usize,
+    width: usize,
+    depth: usize,
+    column: usize,
+    trailing: usize,
+) -> fmt::Result {
+    let room = width.saturating_sub(column + trailing);
+    if fits(value, room, write_inline) {
+        return write_inline(w, value);
+    }
+    let inner = indent * (depth + 1);
+    match value {
+        JsonValue::Object(map) if !map.is_empty() => {
+            w.write_char('{')?;
+            for (i, (k, v)) in map.iter().enumerate() {
+                write_newline(w, Some(indent), depth + 1)?;
+                let mut key = Measure {
+                    len: 0,
+                    limit: usize::MAX,
+                };
+                write_string(&mut key, k)?;
+                write_string(w, k)?;
+                w.write_str(": ")?;
+                let trailing = usize::from(i + 1 < map.len());
+                write_fitted(
+                    w,
+                    v,
+                    indent,
+                    width,
+                    depth + 1,
+                    inner + key.len + 2,
+                    trailing,
+                )?;
+                if trailing > 0 {
+                    w.write_char(',')?;
+                }
+            }
+            write_newline(w, Some(indent), depth)?;
+            w.write_char('}')
+        }
+        JsonValue::Array(items) if !items.is_empty() => {
+            w.write_char('[')?;
+            for (i, v) in items.iter().enumerate() {
+                write_newline(w, Some(indent), depth + 1)?;
+                let trailing = usize::from(i + 1 < items.len());
+                write_fitted(w, v, indent, width, depth + 1, inner, trailing)?;
+                if trailing > 0 {
+                    w.write_char(',')?;
+                }
+            }
+            write_newline(w, Some(indent), depth)?;
+            w.write_char(']')
+        }
+        other => write_inline(w, other),
+    }
+}
+
+/// Writes `value` as compact JSON with the members of every object sorted by
+/// key, so that values which are [`deep_eq`](JsonValue::deep_eq) are written
+/// identically.
+fn write_canonical<W: Write>(w: &mut W, value: &JsonValue) -> fmt::Result {
+    match value {
+        JsonValue::Object(map) => {
+            let mut members: Vec<_> = map.iter().collect();
+            members.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            w.write_char('{')?;
+            for (i, (k, v)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_string(w, k)?;
+                w.write_char(':')?;
+                write_canonical(w, v)?;
+            }
+            w.write_char('}')
+        }
+        JsonValue::Array(items) => {
+            w.write_char('[')?;
+            for (i, v) in items.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_canonical(w, v)?;
+            }
+            w.write_char(']')
+        }
+        scalar => write_nested(w, scalar, None, FloatFormat::Shortest, 0),
+    }
+}
+
+fn write_newline<W: Write>(w: &mut W, indent: Option<usize>, depth: usize) -> fmt::Result {
+    if let Some(indent) = indent {
+        w.write_char('\n')?;
+        for _ in 0..indent * depth {
+            w.write_char(' ')?;
+        }
+    }
+    Ok(())
+}
+
+fn write_float<W: Write>(w: &mut W, f: f64) -> fmt::Result {
+    if !f.is_finite() {
+        return w.write_str("null");
+    }
+    let abs = f.abs();
+    if abs != 0. && !(1e-5..1e16).contains(&abs) {
+        write!(w, "{:e}", f)
+    } else if f.fract() == 0. {
+        write!(w, "{}.0", f)
+    } else {
+        write!(w, "{}", f)
+    }
+}
+
+/// Writes the finite `f` as `float` selects, or `null` if it is not finite.
+fn write_float_as<W: Write>(w: &mut W, f: f64, float: FloatFormat) -> fmt::Result {
+    match float {
+        _ if !f.is_finite() => w.write_str("null"),
+        FloatFormat::Shortest => write_float(w, f),
+        FloatFormat::Fixed(0) => write!(w, "{:.0}.0", f),
+        FloatFormat::Fixed(decimals) => write!(w, "{:.*}", usize::from(decimals), f),
+        FloatFormat::Significant(digits) => {
+            let precision = usize::from(digits.max(1)) - 1;
+            let rounded = format!("{:.*e}", precision, f).parse().unwrap_or(f);
+            write_float(w, rounded)
+        }
+    }
+}
+
+/// Writes `s` as a JSON string, escaping each character below U+0020 with its
+/// short escape if it has one and as `\uXXXX` otherwise. Being a `str`, `s`
+/// holds no lone surrogates, so none can be written.
+fn write_string<W: Write>(w: &mut W, s: &str) -> fmt::Result {
+    w.write_char('"')?;
+    write_escaped(w, s)?;
+    w.write_char('"')
+}
+
+/// Writes `s` escaped as [`write_string`] does, without the quotes.
+fn write_escaped<W: Write>(w: &mut W, s: &str) -> fmt::Result {
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        let escape = match c {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            '\u{08}' => "\\b",
+            '\u{0C}' => "\\f",
+            c if c < '\u{20}' => "",
+            _ => continue,
+        };
+        w.write_str(&s[start..i])?;
+        if escape.is_empty() {
+            write!(w, "\\u{:04x}", c as u32)?;
+        } else {
+            w.write_str(escape)?;
+        }
+        start = i + c.len_utf8();
+    }
+    w.write_str(&s[start..])
+}
+
+/// Escapes `s` for the inside of a JSON string, as strings are written when
+/// serializing a value, without adding the quotes around it.
+///
+/// [`unescape_string`](super::unescape_string) reverses this.
+pub fn escape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    write_escaped(&mut out, s).unwrap();
+    out
+}
+
+/// Options for [`JsonValue::to_string_with`]. The default escapes only what
+/// JSON requires and writes floats as [`to_string`](ToString::to_string) does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SerializeOptions {
+    /// Write `/` as `\/`.
+    pub escape_forward_slash: bool,
+    /// Write `<`, `>` and `&` as `\u003c`, `\u003e` and `\u0026`, so that the
+    /// output can be embedded in a `<script>` element of an HTML page.
+    pub escape_html: bool,
+    /// Write every character outside ASCII as a `\uXXXX` escape, a character
+    /// outside the Basic Multilingual Plane as the escapes of its UTF-16
+    /// surrogate pair, so that the output is ASCII. Like Python's
+    /// `ensure_ascii`; by default such characters are written as UTF-8.
+    pub ascii_only: bool,
+    /// How floats are written, [`FloatFormat::Shortest`] by default.
+    pub float: FloatFormat,
+}
+
+/// How [`JsonValue::to_string_with`] writes a [`JsonValue::Number`]. Whatever
+/// the format, NaN and the infinities are written as `null`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// The shortest form that reads back as the same `f64`, as
+    /// [`to_string`](ToString::to_string) writes it. A float with an integer
+    /// value keeps a `.0`, so that it reads back as a `Number` rather than an
+    /// `Int`.
+    #[default]
+    Shortest,
+    /// With this many digits after the decimal point and no exponent, so
+    /// `0.1` with 3 decimals is `0.100`. With none, the number is rounded to
+    /// an integer that still keeps a `.0`, as with `Shortest`, so `2.5` is
+    /// `2.0`.
+    Fixed(u8),
+    /// Rounded to this many significant digits, at least one, then written
+    /// in the shortest form, so `0.123456` with 3 digits is `0.123`.
+    Significant(u8),
+}
+
+/// Escapes the characters selected by `options` in what is written to `w`.
+///
+/// None of them can appear in JSON outside a string, so every one written is
+/// part of a string or key and escaping it keeps the output equivalent.
+struct Escaping<'w, W> {
+    w: &'w mut W,
+    options: SerializeOptions,
+}
+
+impl<W: Write> Write for Escaping<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            let escape = match c {
+                '/' if self.options.escape_forward_slash => "\\/",
+                '<' if self.options.escape_html => "\\u003c",
+                '>' if self.options.escape_html => "\\u003e",
+                '&' if self.options.escape_html => "\\u0026",
+                c if self.options.ascii_only && !c.is_ascii() => "",
+                _ => continue,
+            };
+            self.w.write_str(&s[start..i])?;
+            if escape.is_empty() {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    write!(self.w, "\\u{:04x}", unit)?;
+                }
+            } else {
+                self.w.write_str(escape)?;
+            }
+            start = i + c.len_utf8();
+        }
+        self.w.write_str(&s[start..])
+    }
+}
+
+/// Adapts an `io::Write` to `fmt::Write`, keeping the I/O error behind a
+/// `fmt::Error`.
+#[cfg(feature = "std")]
+struct IoWriter<'w, W> {
+    inner: &'w mut W,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+/// Writes `value` to `w` like [`write_value`], returning the I/O error that
+/// stopped it.
+#[cfg(feature = "std")]
+fn write_io<W: io::Write>(w: &mut W, value: &JsonValue, indent: Option<usize>) -> io::Result<()> {
+    let mut writer = IoWriter {
+        inner: w,
+        error: None,
+    };
+    write_value(&mut writer, value, indent).map_err(|_| {
+        writer
+            .error
+            .take()
+            .unwrap_or_else(|| io::Error::other("formatter error"))
+    })
+}
+
+/// Formats the value as compact JSON, which also provides
+/// `JsonValue::to_string`. The alternate form `{:#}` pretty-prints it with an
+/// indentation of two spaces.
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indent = if f.alternate() { Some(2) } else { None };
+        write_value(f, self, indent)
+    }
+}
+
+impl JsonValue {
+    /// Serializes the value as human-readable JSON, putting each array element
+    /// and object member on its own line indented by `indent` spaces per level.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        let mut out = String::new();
+        write_value(&mut out, self, Some(indent)).unwrap();
+        out
+    }
+
+    /// Serializes the value as human-readable JSON like
+    /// [`to_string_pretty`](JsonValue::to_string_pretty), but keeps each array
+    /// and object on a single line when it fits within `width` columns,
+    /// counting indentation, keys and a trailing comma.
+    ///
+    /// Containers are laid out from the outside in, so a short array inside a
+    /// long object stays inline while the object is expanded. A scalar too
+    /// long for the width is written on its line regardless.
+    pub fn to_string_pretty_width(&self, indent: usize, width: usize) -> String {
+        let mut out = String::new();
+        write_fitted(&mut out, self, indent, width, 0, 0, 0).unwrap();
+        out
+    }
+
+    /// Serializes the value as compact JSON like
+    /// [`to_string`](ToString::to_string), escaping the characters and
+    /// formatting the floats as `options` selects.
+    pub fn to_string_with(&self, options: &SerializeOptions) -> String {
+        let mut out = String::new();
+        let mut w = Escaping {
+            w: &mut out,
+            options: *options,
+        };
+        write_nested(&mut w, self, None, options.float, 0).unwrap();
+        out
+    }
+
+    /// Writes the value to `w` as compact JSON, like
+    /// [`to_string`](ToString::to_string) but without building a `String`.
+    ///
+    /// The value is written in many small pieces, so `w` should be buffered.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        write_io(w, self, None)
+    }
+
+    /// Writes the value to `w` as human-readable JSON, like
+    /// [`to_string_pretty`](JsonValue::to_string_pretty).
+    #[cfg(feature = "std")]
+    pub fn write_to_pretty<W: io::Write>(&self, w: &mut W, indent: usize) -> io::Result<()> {
+        write_io(w, self, Some(indent))
+    }
+
+    /// Returns the number of characters in a string as parsed, with escapes
+    /// decoded, or `None` for other values.
+    pub fn char_count(&self) -> Option<usize> {
+        self.as_str().map(|s| s.chars().count())
+    }
+
+    /// Returns the length in bytes of the value serialized as compact JSON, as
+    /// [`to_string`](ToString::to_string) writes it, without building it.
+    ///
+    /// For a string this counts its quotes and the escapes it needs, so
+    /// `"a\nb"` takes 6 bytes encoded for 3 characters decoded.
+    pub fn encoded_len(&self) -> usize {
+        let mut count = ByteCount(0);
+        write_value(&mut count, self, None).unwrap();
+        count.0
+    }
+
+    /// Serializes the value as compact JSON with object keys sorted by their
+    /// UTF-8 bytes, giving a deterministic form for hashing and signing.
+    ///
+    /// Floats are written in their shortest round-trip form, as by
+    /// [`to_string`](ToString::to_string), and a `RawNumber` verbatim.
+    pub fn to_string_canonical(&self) -> String {
+        let mut out = String::new();
+        write_canonical(&mut out, self).unwrap();
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::super::{
+        parse_borrowed, parse_complete, parse_int_keyed, parse_interned, parse_with_options, Map,
+        ParseOptions,
+    };
+    use super::*;
+
+    /// Generates values that survive a round trip: finite floats only, and no
+    /// `RawNumber`, which parsing produces only when asked to.
+    fn arb_value() -> impl Strategy<Value = JsonValue> {
+        let float = prop_oneof![
+            proptest::num::f64::NORMAL,
+            proptest::num::f64::SUBNORMAL,
+            proptest::num::f64::ZERO,
+        ];
+        let leaf = prop_oneof![
+            Just(JsonValue::Null),
+            any::<bool>().prop_map(JsonValue::Bool),
+            any::<i64>().prop_map(JsonValue::Int),
+            float.prop_map(JsonValue::Number),
+            any::<String>().prop_map(JsonValue::Str),
+        ];
+        leaf.prop_recursive(4, 64, 8, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..8).prop_map(JsonValue::Array),
+                prop::collection::vec((any::<String>(), inner), 0..8)
+                    .prop_map(|members| JsonValue::Object(members.into_iter().collect::<Map>())),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip_arbitrary(value in arb_value()) {
+            for text in [
+                value.to_string(),
+                value.to_string_pretty(2),
+                value.to_string_canonical(),
+            ] {
+                let parsed = parse_complete(&text).unwrap();
+                prop_assert!(parsed.deep_eq(&value), "{} parsed as {:?}", text, parsed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(JsonValue::Null.to_string(), "null");
+        assert_eq!(JsonValue::Bool(false).to_string(), "false");
+        assert_eq!(JsonValue::Int(-12).to_string(), "-12");
+        assert_eq!(JsonValue::Number(1.5).to_string(), "1.5");
+        assert_eq!(JsonValue::Number(2.).to_string(), "2.0");
+        assert_eq!(JsonValue::Number(1e20).to_string(), "1e20");
+        assert_eq!(JsonValue::Number(f64::NAN).to_string(), "null");
+    }
+
+    #[test]
+    fn test_number_text() {
+        let input = "[2.50,1.0,1e2,1E+2,-0,-0.0,100,0.000001,12345678901234567.0,1.5e300]";
+        assert_eq!(
+            parse_complete(input).unwrap().to_string(),
+            "[2.5,1.0,100.0,100.0,-0.0,-0.0,100,1e-6,1.2345678901234568e16,1.5e300]"
+        );
+        let options = ParseOptions {
+            raw_numbers: true,
+            ..Default::default()
+        };
+        let value = parse_with_options(input, &options).unwrap();
+        assert_eq!(value.to_string(), input);
+        assert_eq!(value[0].as_f64(), Some(2.5));
+        let pretty = parse_with_options("{\"price\": 2.50}", &options).unwrap();
+        assert_eq!(pretty.to_string_pretty(2), "{\n  \"price\": 2.50\n}");
+    }
+
+    #[test]
+    fn test_string_escape() {
+        assert_eq!(
+            JsonValue::Str("a\"b\\c\nd\u{1}é".to_string()).to_string(),
+            r#""a\"b\\c\nd\u0001é""#
+        );
+        assert_eq!(
+            JsonValue::Str("\0\u{1}".into()).to_string(),
+            r#""\u0000\u0001""#
+        );
+        assert_eq!(
+            JsonValue::Str("\u{8}\u{c}\r\t\u{1f} ".into()).to_string(),
+            r#""\b\f\r\t\u001f ""#
+        );
+        let controls: String = (0..0x20u8).map(char::from).collect();
+        let written = JsonValue::Str(controls.clone()).to_string();
+        assert!(written.chars().all(|c| c >= ' '));
+        assert_eq!(parse_complete(&written).unwrap(), JsonValue::Str(controls));
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        let value = JsonValue::Str("say \"hi\"\né".into());
+        assert_eq!(value.char_count(), Some(10));
+        assert_eq!(value.as_str().unwrap().len(), 11);
+        assert_eq!(value.encoded_len(), 16);
+        assert_eq!(value.encoded_len(), value.to_string().len());
+
+        let value = parse_complete(r#"{"a": [1, 2.5, null, "\u0001"]}"#).unwrap();
+        assert_eq!(value.encoded_len(), value.to_string().len());
+        assert_eq!(value.char_count(), None);
+        assert_eq!(JsonValue::Int(42).char_count(), None);
+    }
+
+    #[test]
+    fn test_pretty() {
+        let value = JsonValue::Array(vec![
+            JsonValue::Int(1),
+            JsonValue::Array(vec![JsonValue::Bool(true), JsonValue::Null]),
+            JsonValue::Array(vec![]),
+            JsonValue::Object(Default::default()),
+        ]);
+        assert_eq!(
+            value.to_string_pretty(2),
+            "[\n  1,\n  [\n    true,\n    null\n  ],\n  [],\n  {}\n]"
+        );
+        let value = parse_complete(r#"{"a": {"b": [1]}}"#).unwrap();
+        assert_eq!(
+            value.to_string_pretty(4),
+            "{\n    \"a\": {\n        \"b\": [\n            1\n        ]\n    }\n}"
+        );
+    }
+
+    #[test]
+    fn test_pretty_width() {
+        let value = parse_complete(
+            r#"{"rows": [[1, 2, 3], ["a long string value here", "another long string"], []]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value.to_string_pretty_width(2, 40),
+            r#"{
+  "rows": [
+    [1, 2, 3],
+    [
+      "a long string value here",
+      "another long string"
+    ],
+    []
+  ]
+}"#
+        );
+        let wide = value.to_string_pretty_width(2, 200);
+        assert!(!wide.contains('\n'));
+        assert_eq!(parse_complete(&wide).unwrap(), value);
+
+        let value = parse_complete(r#"{"a": [1, {"b": null}], "c": {}, "d": "x"}"#).unwrap();
+        assert_eq!(
+            value.to_string_pretty_width(2, 0),
+            value.to_string_pretty(2)
+        );
+        assert_eq!(JsonValue::Int(12345).to_string_pretty_width(2, 3), "12345");
+        // `[1, 2, 3],` ends exactly at the width, counting its comma.
+        let value = parse_complete(r#"[[1, 2, 3], 4]"#).unwrap();
+        assert_eq!(
+            value.to_string_pretty_width(2, 12),
+            "[\n  [1, 2, 3],\n  4\n]"
+        );
+        assert_eq!(
+            value.to_string_pretty_width(2, 11),
+            "[\n  [\n    1,\n    2,\n    3\n  ],\n  4\n]"
+        );
+    }
+
+    #[test]
+    fn test_serialize_options() {
+        let value = parse_complete(r#"{"html": "</script><b>&amp;", "url": "a/b"}"#).unwrap();
+        let html = SerializeOptions {
+            escape_html: true,
+            ..Default::default()
+        };
+        let safe = value.to_string_with(&html);
+        assert!(!safe.contains("</script>"));
+        assert!(safe.contains(r#""\u003c/script\u003e\u003cb\u003e\u0026amp;""#));
+        assert!(safe.contains(r#""a/b""#));
+        assert_eq!(parse_complete(&safe).unwrap(), value);
+
+        let slash = SerializeOptions {
+            escape_forward_slash: true,
+            ..Default::default()
+        };
+        let escaped = value.to_string_with(&slash);
+        assert!(escaped.contains(r#""<\/script><b>&amp;""#));
+        assert!(escaped.contains(r#""a\/b""#));
+        assert_eq!(parse_complete(&escaped).unwrap(), value);
+
+        let plain = value.to_string_with(&SerializeOptions::default());
+        assert_eq!(plain, value.to_string());
+        assert!(plain.contains("</script>"));
+
+        let value = parse_complete(r#"{"café": "é 😀 ok\u0001", "n": 1}"#).unwrap();
+        let ascii = SerializeOptions {
+            ascii_only: true,
+            ..Default::default()
+        };
+        let escaped = value.to_string_with(&ascii);
+        assert!(escaped.is_ascii());
+        assert!(escaped.contains(r#""caf\u00e9""#));
+        assert!(escaped.contains(r#""\u00e9 \ud83d\ude00 ok\u0001""#));
+        assert_eq!(parse_complete(&escaped).unwrap(), value);
+        assert!(value.to_string().contains("é 😀"));
+    }
+
+    #[test]
+    fn test_float_format() {
+        let format = |f: f64, float| {
+            let options = SerializeOptions {
+                float,
+                ..Default::default()
+            };
+            JsonValue::Number(f).to_string_with(&options)
+        };
+        assert_eq!(format(0.1, FloatFormat::Shortest), "0.1");
+        assert_eq!(format(100., FloatFormat::Shortest), "100.0");
+        assert_eq!(format(0.1, FloatFormat::Fixed(6)), "0.100000");
+        assert_eq!(format(100., FloatFormat::Fixed(6)), "100.000000");
+        assert_eq!(format(2.5, FloatFormat::Fixed(0)), "2.0");
+        assert_eq!(format(-0.4, FloatFormat::Fixed(0)), "-0.0");
+        let written = format(7.75, FloatFormat::Fixed(0));
+        assert_eq!(parse_complete(&written).unwrap(), JsonValue::Number(8.));
+        assert_eq!(format(0.1, FloatFormat::Significant(3)), "0.1");
+        assert_eq!(format(100., FloatFormat::Significant(3)), "100.0");
+        assert_eq!(format(2. / 3., FloatFormat::Significant(3)), "0.667");
+        assert_eq!(format(123456., FloatFormat::Significant(2)), "120000.0");
+        assert_eq!(format(0.15, FloatFormat::Significant(0)), "0.1");
+        assert_eq!(format(f64::NAN, FloatFormat::Fixed(2)), "null");
+
+        let value = parse_complete("[1.25, 3, {\"a\": 0.5}]").unwrap();
+        let options = SerializeOptions {
+            float: FloatFormat::Fixed(2),
+            ..Default::default()
+        };
+        assert_eq!(value.to_string_with(&options), r#"[1.25,3,{"a":0.50}]"#);
+    }
+
+    #[test]
+    fn test_display() {
+        let value = JsonValue::Array(vec![JsonValue::Bool(true), JsonValue::Null]);
+        assert_eq!(format!("{}", value), "[true,null]");
+        assert_eq!(format!("{:#}", value), value.to_string_pretty(2));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_to() {
+        let value = parse_complete(r#"{"a": [1, 2.5, "é\n"], "b": {"c": null}}"#).unwrap();
+        let mut out = Vec::new();
+        value.write_to(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), value.to_string());
+        let mut out = Vec::new();
+        value.write_to_pretty(&mut out, 4).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), value.to_string_pretty(4));
+
+        let mut full = [0; 4];
+        let error = value["a"].write_to(&mut &mut full[..]).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(&full, b"[1,2");
+    }
+
+    #[test]
+    fn test_canonical() {
+        let a =
+            parse_complete(r#"{"b": [1, {"z": null, "y": 2.50}], "a": "x", "é": true}"#).unwrap();
+        let mut b = JsonValue::Object(Default::default());
+        b.insert("é", JsonValue::Bool(true));
+        b.insert("a", JsonValue::from("x"));
+        b.insert(
+            "b",
+            parse_complete(r#"[1, {"y": 2.5, "z": null}]"#).unwrap(),
+        );
+        assert!(a.deep_eq(&b));
+        assert_eq!(a.to_string_canonical(), b.to_string_canonical());
+        assert_eq!(
+            a.to_string_canonical(),
+            r#"{"a":"x","b":[1,{"y":2.5,"z":null}],"é":true}"#
+        );
+        assert_eq!(JsonValue::Number(0.1).to_string_canonical(), "0.1");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = r#"{
+            "name": "say \"hi\"\t\\",
+            "list": [1, -2.5, 3e-9, 1e300, true, false, null, [], {}],
+            "nested": {"empty": "", "unicode": "é😀\u001f"}
+        }"#;
+        let value = parse_complete(input).unwrap();
+        assert_eq!(parse_complete(&value.to_string()), Ok(value.clone()));
+        assert_eq!(parse_complete(&value.to_string_pretty(2)), Ok(value));
+    }
+
+    #[test]
+    fn test_round_trip_keys() {
+        let input = r#"{"a\"b": 1, "ключ": {"7": "x", "10": [{"ключ": null}]},
+            "\\/\u0000\n": {"a\"b": true}, "😀": {"</script>": 2}}"#;
+        let value = parse_complete(input).unwrap();
+        let representations = [
+            parse_borrowed(input).unwrap().1.into_owned(),
+            parse_interned(input).unwrap().into_owned(),
+            parse_int_keyed(input).unwrap().into_owned(),
+        ];
+        let all_escapes = SerializeOptions {
+            escape_forward_slash: true,
+            escape_html: true,
+            ascii_only: true,
+            ..Default::default()
+        };
+        for parsed in representations {
+            assert_eq!(parsed, value);
+            for output in [
+                parsed.to_string(),
+                parsed.to_string_pretty(2),
+                parsed.to_string_canonical(),
+                parsed.to_string_with(&all_escapes),
+            ] {
+                assert_eq!(parse_complete(&output), Ok(value.clone()), "{}", output);
+            }
+        }
+        assert!(value.to_string().contains(r#""a\"b":1"#));
+        assert!(value
+            .to_string_with(&all_escapes)
+            .contains(r#""\u043a\u043b\u044e\u0447":"#));
+    }
+}
@@ -0,0 +1,134 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{convert::TryFrom, fmt};
+
+use serde::{
+    de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor},
+    ser::{Serialize, Serializer},
+};
+
+use super::{JsonValue, Map};
+
+/// Maps each variant onto the matching part of the serde data model, with
+/// `Null` as the unit value, a `RawNumber` converted as when parsing and a
+/// `BigInt` as the nearest `f64`.
+impl Serialize for JsonValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            JsonValue::Number(f) => serializer.serialize_f64(*f),
+            JsonValue::Int(i) => serializer.serialize_i64(*i),
+            JsonValue::RawNumber(_) => match (self.as_i64(), self.as_f64()) {
+                (Some(i), _) => serializer.serialize_i64(i),
+                (None, f) => serializer.serialize_f64(f.unwrap_or(f64::NAN)),
+            },
+            #[cfg(feature = "bigint")]
+            JsonValue::BigInt(_) => serializer.serialize_f64(self.as_f64().unwrap_or(f64::NAN)),
+            JsonValue::Str(s) => serializer.serialize_str(s),
+            JsonValue::Bool(b) => serializer.serialize_bool(*b),
+            JsonValue::Null => serializer.serialize_unit(),
+            JsonValue::Object(map) => serializer.collect_map(map),
+            JsonValue::Array(items) => serializer.collect_seq(items),
+        }
+    }
+}
+
+struct JsonValueVisitor;
+
+impl<'de> Visitor<'de> for JsonValueVisitor {
+    type Value = JsonValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_bool<E: de::Error>(self, b: bool) -> Result<JsonValue, E> {
+        Ok(JsonValue::Bool(b))
+    }
+
+    fn visit_i64<E: de::Error>(self, i: i64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Int(i))
+    }
+
+    /// Integers beyond the range of `i64` become floats, as when parsing.
+    fn visit_u64<E: de::Error>(self, u: u64) -> Result<JsonValue, E> {
+        match i64::try_from(u) {
+            Ok(i) => Ok(JsonValue::Int(i)),
+            Err(_) => Ok(JsonValue::Number(u as f64)),
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, f: f64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Number(f))
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<JsonValue, E> {
+        Ok(JsonValue::Str(s.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, s: String) -> Result<JsonValue, E> {
+        Ok(JsonValue::Str(s))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<JsonValue, D::Error> {
+        Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JsonValue, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(JsonValue::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<JsonValue, A::Error> {
+        let mut map = Map::default();
+        while let Some((k, v)) = access.next_entry()? {
+            map.insert(k, v);
+        }
+        Ok(JsonValue::Object(map))
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<JsonValue, D::Error> {
+        deserializer.deserialize_any(JsonValueVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    const DOCUMENT: &str = r#"{"name": "a \"quoted\"\nline", "n": [1, -2, 1.5, 2.0, -0.25],
+        "flags": {"on": true, "off": false, "none": null}, "empty": [{}, []]}"#;
+
+    #[test]
+    fn test_serialize() {
+        let value = parse_complete(DOCUMENT).unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), value.to_string());
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let value = parse_complete(DOCUMENT).unwrap();
+        assert_eq!(serde_json::from_str::<JsonValue>(DOCUMENT).unwrap(), value);
+        let text = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<JsonValue>(&text).unwrap(), value);
+        assert_eq!(
+            serde_json::from_str::<JsonValue>("18446744073709551615").unwrap(),
+            JsonValue::Number(18446744073709551615.)
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ops::Range;
+
+use nom::combinator::all_consuming;
+
+use super::pointer::escape;
+use super::{parse_value, Builder, Cow, JsonValue, Map, ParseError, ParseOptions, ValueBuilder};
+
+/// The byte ranges of the values of a document, keyed by their JSON Pointer.
+pub type Spans = BTreeMap<String, Range<usize>>;
+
+/// Where a value is within its container.
+enum Token {
+    Index(usize),
+    Key(String),
+}
+
+/// Builds a [`JsonValue`] like [`ValueBuilder`], recording the span of each
+/// value under its path.
+struct SpanBuilder<'a> {
+    input: &'a str,
+    /// For each open container, the index of its current element or the
+    /// escaped key of its current member.
+    path: Vec<Token>,
+    spans: Spans,
+}
+
+impl<'a> Builder<'a> for SpanBuilder<'a> {
+    type Value = JsonValue;
+    type Object = Map;
+
+    fn null(&mut self) -> JsonValue {
+        ValueBuilder.null()
+    }
+
+    fn bool(&mut self, b: bool) -> JsonValue {
+        ValueBuilder.bool(b)
+    }
+
+    fn int(&mut self, i: i64) -> JsonValue {
+        ValueBuilder.int(i)
+    }
+
+    fn float(&mut self, f: f64) -> JsonValue {
+        ValueBuilder.float(f)
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) -> JsonValue {
+        ValueBuilder.string(s)
+    }
+
+    fn raw_number(&mut self, token: &'a str) -> JsonValue {
+        ValueBuilder.raw_number(token)
+    }
+
+    #[cfg(feature = "bigint")]
+    fn big_int(&mut self, token: &'a str) -> JsonValue {
+        ValueBuilder.big_int(token)
+    }
+
+    fn begin_array(&mut self) {
+        self.path.push(Token::Index(0));
+    }
+
+    fn array(&mut self, items: Vec<JsonValue>) -> JsonValue {
+        self.path.pop();
+        ValueBuilder.array(items)
+    }
+
+    fn begin_object(&mut self) {
+        self.path.push(Token::Key(String::new()));
+    }
+
+    fn key(&mut self, key: &str) {
+        if let Some(token) = self.path.last_mut() {
+            *token = Token::Key(escape(key));
+        }
+    }
+
+    fn new_object(&mut self, capacity: usize) -> Map {
+        ValueBuilder.new_object(capacity)
+    }
+
+    fn contains_key(&self, object: &Map, key: &str) -> bool {
+        ValueBuilder.contains_key(object, key)
+    }
+
+    fn insert(&mut self, object: &mut Map, key: Cow<'a, str>, value: JsonValue) {
+        ValueBuilder.insert(object, key, value);
+    }
+
+    fn object(&mut self, object: Map) -> JsonValue {
+        self.path.pop();
+        ValueBuilder.object(object)
+    }
+
+    fn span(&mut self, start: &'a str, end: &'a str) {
+        let mut pointer = String::new();
+        for token in &self.path {
+            pointer.push('/');
+            match token {
+                Token::Index(i) => pointer.push_str(&i.to_string()),
+                Token::Key(key) => pointer.push_str(key),
+            }
+        }
+        let offset = |s: &str| s.as_ptr() as usize - self.input.as_ptr() as usize;
+        self.spans.insert(pointer, offset(start)..offset(end));
+        if let Some(Token::Index(i)) = self.path.last_mut() {
+            *i += 1;
+        }
+    }
+}
+
+/// Parses `input` as a whole JSON document like
+/// [`parse_document`](super::parse_document), also returning the byte range
+/// each value was parsed from.
+///
+/// The span of an array or object runs from its opening to its closing
+/// bracket; that of a string includes its quotes.
+pub fn parse_with_spans(input: &str) -> Result<(JsonValue, Spans), ParseError> {
+    let mut builder = SpanBuilder {
+        input,
+        path: Vec::new(),
+        spans: Spans::new(),
+    };
+    let result =
+        all_consuming(|i| parse_value(i, &ParseOptions::default(), 0, &mut builder))(input);
+    match result {
+        Ok((_, value)) => Ok((value, builder.spans)),
+        Err(e) => Err(ParseError::new(input, e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_spans() {
+        let input = r#" {"users": [{"id": 1}, {"e/mail": "a@b.c" }], "ok": true} "#;
+        let (value, spans) = parse_with_spans(input).unwrap();
+        assert_eq!(value, parse_complete(input).unwrap());
+
+        let email = &spans["/users/1/e~1mail"];
+        assert_eq!(&input[email.clone()], r#""a@b.c""#);
+        assert_eq!(&input[spans["/users/1"].clone()], r#"{"e/mail": "a@b.c" }"#);
+        assert_eq!(&input[spans["/users/0/id"].clone()], "1");
+        assert_eq!(&input[spans["/ok"].clone()], "true");
+        assert_eq!(spans[""], 1..input.len() - 1);
+        assert_eq!(spans.len(), 7);
+
+        let (_, spans) = parse_with_spans("[[], 2]").unwrap();
+        assert_eq!(spans["/0"], 1..3);
+        assert_eq!(spans["/1"], 5..6);
+
+        let error = parse_with_spans("[1, }").unwrap_err();
+        assert_eq!(error.offset, 4);
+    }
+}
@@ -0,0 +1,176 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use super::{parse_document_with, Builder, JsonValue, Map, ParseError, ParseOptions, ValueBuilder};
+
+/// Counts of what a document contains, from [`parse_with_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Every value, containers and scalars alike.
+    pub values: usize,
+    pub objects: usize,
+    pub arrays: usize,
+    /// String values, not counting object keys.
+    pub strings: usize,
+    pub numbers: usize,
+    /// The deepest nesting of arrays and objects, 0 for a scalar document.
+    pub max_depth: usize,
+    /// The length of the document in bytes.
+    pub bytes: usize,
+}
+
+/// Builds a [`JsonValue`] like [`ValueBuilder`], counting what it builds.
+#[derive(Default)]
+struct StatsBuilder {
+    stats: ParseStats,
+    depth: usize,
+}
+
+impl StatsBuilder {
+    fn open(&mut self) {
+        self.stats.values += 1;
+        self.depth += 1;
+        self.stats.max_depth = self.stats.max_depth.max(self.depth);
+    }
+}
+
+impl<'a> Builder<'a> for StatsBuilder {
+    type Value = JsonValue;
+    type Object = Map;
+
+    fn null(&mut self) -> JsonValue {
+        self.stats.values += 1;
+        ValueBuilder.null()
+    }
+
+    fn bool(&mut self, b: bool) -> JsonValue {
+        self.stats.values += 1;
+        ValueBuilder.bool(b)
+    }
+
+    fn int(&mut self, i: i64) -> JsonValue {
+        self.stats.values += 1;
+        self.stats.numbers += 1;
+        ValueBuilder.int(i)
+    }
+
+    fn float(&mut self, f: f64) -> JsonValue {
+        self.stats.values += 1;
+        self.stats.numbers += 1;
+        ValueBuilder.float(f)
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) -> JsonValue {
+        self.stats.values += 1;
+        self.stats.strings += 1;
+        ValueBuilder.string(s)
+    }
+
+    fn raw_number(&mut self, token: &'a str) -> JsonValue {
+        self.stats.values += 1;
+        self.stats.numbers += 1;
+        ValueBuilder.raw_number(token)
+    }
+
+    #[cfg(feature = "bigint")]
+    fn big_int(&mut self, token: &'a str) -> JsonValue {
+        self.stats.values += 1;
+        self.stats.numbers += 1;
+        ValueBuilder.big_int(token)
+    }
+
+    fn begin_array(&mut self) {
+        self.stats.arrays += 1;
+        self.open();
+    }
+
+    fn array(&mut self, items: Vec<JsonValue>) -> JsonValue {
+        self.depth -= 1;
+        ValueBuilder.array(items)
+    }
+
+    fn begin_object(&mut self) {
+        self.stats.objects += 1;
+        self.open();
+    }
+
+    fn new_object(&mut self, capacity: usize) -> Map {
+        ValueBuilder.new_object(capacity)
+    }
+
+    fn contains_key(&self, object: &Map, key: &str) -> bool {
+        ValueBuilder.contains_key(object, key)
+    }
+
+    fn insert(&mut self, object: &mut Map, key: Cow<'a, str>, value: JsonValue) {
+        ValueBuilder.insert(object, key, value);
+    }
+
+    fn object(&mut self, object: Map) -> JsonValue {
+        self.depth -= 1;
+        ValueBuilder.object(object)
+    }
+}
+
+/// Parses `input` as a whole JSON document like
+/// [`parse_document`](super::parse_document), also counting the containers
+/// and scalars in it as they are parsed.
+pub fn parse_with_stats(input: &str) -> Result<(JsonValue, ParseStats), ParseError> {
+    parse_with_stats_options(input, &ParseOptions::default())
+}
+
+/// Parses and counts like [`parse_with_stats`], accepting what
+/// [`parse_with_options`](super::parse_with_options) does with `options`.
+pub fn parse_with_stats_options(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<(JsonValue, ParseStats), ParseError> {
+    let mut builder = StatsBuilder::default();
+    match parse_document_with(input, options, &mut builder) {
+        Ok(value) => {
+            builder.stats.bytes = input.len();
+            Ok((value, builder.stats))
+        }
+        Err(e) => Err(ParseError::new(input, e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let input = r#"{"a": [1, 2.5, {"b": "x"}, []], "c": "y", "d": [null, true]} "#;
+        let (value, stats) = parse_with_stats(input).unwrap();
+        assert_eq!(value, parse_complete(input).unwrap());
+        assert_eq!(
+            stats,
+            ParseStats {
+                values: 11,
+                objects: 2,
+                arrays: 3,
+                strings: 2,
+                numbers: 2,
+                max_depth: 3,
+                bytes: input.len(),
+            }
+        );
+        assert_eq!(parse_with_stats("1").unwrap().1.max_depth, 0);
+        assert!(parse_with_stats("[1,]").is_err());
+
+        let input = "// counted\n[1, {a: 'x'}, Infinity,]";
+        let (value, stats) = parse_with_stats_options(input, &ParseOptions::json5()).unwrap();
+        assert_eq!(
+            value,
+            super::super::parse_with_options(input, &ParseOptions::json5()).unwrap()
+        );
+        assert_eq!((stats.values, stats.strings, stats.max_depth), (5, 1, 2));
+        let options = ParseOptions {
+            max_depth: 1,
+            ..ParseOptions::default()
+        };
+        let error = parse_with_stats_options("[[1]]", &options).unwrap_err();
+        assert_eq!(error.kind, super::super::ErrorKind::DepthLimitExceeded);
+    }
+}
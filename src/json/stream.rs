@@ -0,0 +1,873 @@
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::mem;
+
+use nom::{character::complete::char, error::context, Err};
+
+use super::{
+    collect_members, error::line_column, expecting, parse, parse_hooked_number, parse_key_token,
+    parse_scalar, pointer, skip_ws, Builder, DuplicateKeyPolicy, Error, ErrorKind, JsonValue,
+    ParseError, ParseOptions, ValueBuilder, VALUE_TOKENS, VALUE_TOKENS_OR_CLOSE,
+};
+
+/// A place in the document fed to a [`StreamParser`].
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    offset: usize,
+    /// As in [`ParseError::line`] and [`ParseError::column`].
+    line: usize,
+    column: usize,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl Position {
+    /// The position of byte `offset` of `text`, which starts here.
+    fn advance(self, text: &str, offset: usize) -> Position {
+        let (line, column) = line_column(text, offset);
+        Position {
+            offset: self.offset + offset,
+            line: self.line + line - 1,
+            column: if line == 1 {
+                self.column + column - 1
+            } else {
+                column
+            },
+        }
+    }
+}
+
+/// An array or object that has been opened but not yet closed, with what has
+/// been parsed of it.
+#[derive(Debug, Clone)]
+enum Open {
+    Array {
+        at: Position,
+        items: Vec<JsonValue>,
+    },
+    /// The members parsed, with where each key starts, and the key of the
+    /// member whose value is being parsed.
+    Object {
+        at: Position,
+        members: Vec<(Position, String, JsonValue)>,
+        key: Option<(Position, String)>,
+    },
+}
+
+impl Open {
+    fn at(&self) -> Position {
+        match self {
+            Open::Array { at, .. } | Open::Object { at, .. } => *at,
+        }
+    }
+
+    fn context(&self) -> &'static str {
+        match self {
+            Open::Array { .. } => "array",
+            Open::Object { .. } => "object",
+        }
+    }
+
+    fn open(&self) -> char {
+        match self {
+            Open::Array { .. } => '[',
+            Open::Object { .. } => '{',
+        }
+    }
+
+    fn close(&self) -> char {
+        match self {
+            Open::Array { .. } => ']',
+            Open::Object { .. } => '}',
+        }
+    }
+}
+
+/// What a [`StreamParser`] expects next, `first` if nothing has been parsed
+/// in the innermost container yet.
+#[derive(Debug, Clone, Copy, Default)]
+enum Expect {
+    /// The document.
+    #[default]
+    Root,
+    /// An element of the innermost array, or its closing bracket.
+    Element { first: bool },
+    /// The value of an element, which has been counted.
+    ElementValue { first: bool },
+    /// A member of the innermost object, or its closing brace.
+    Member { first: bool },
+    /// The key of a member, which has been counted.
+    Key { first: bool },
+    /// The colon after the key just parsed.
+    Colon,
+    /// The value of the member whose key the innermost object holds.
+    MemberValue,
+    /// A comma or the closing bracket of the innermost container.
+    Separator,
+    /// Nothing but whitespace, as the document is complete.
+    Done,
+}
+
+/// Whether `c` may be part of a number, keyword or unquoted key, so that a
+/// token of those ending with it may go on in the next chunk.
+fn is_token_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '+' | '-' | '.' | '_' | '$')
+}
+
+/// Scans the string, number, keyword or unquoted key at the start of `input`
+/// for its end, from byte `from` of it on. If it does not end within `input`,
+/// returns how far it was scanned, where scanning may resume once there is
+/// more of it.
+fn unfinished_token(input: &str, from: usize, options: &ParseOptions) -> Option<usize> {
+    let bytes = input.as_bytes();
+    match bytes[0] {
+        quote @ (b'"' | b'\'') if quote == b'"' || options.allow_single_quotes => {
+            let mut i = from.max(1);
+            while i < bytes.len() {
+                match bytes[i] {
+                    b if b == quote => return None,
+                    // Resume at the backslash of an escape cut short.
+                    b'\\' if i + 1 == bytes.len() => return Some(i),
+                    b'\\' => i += 2,
+                    _ => i += 1,
+                }
+            }
+            Some(bytes.len())
+        }
+        _ => match input[from..].find(|c| !is_token_char(c)) {
+            Some(_) => None,
+            None => Some(input.len()),
+        },
+    }
+}
+
+/// Whether what `options` allows between tokens at the start of `input` may
+/// go on past its end, as a comment does that has not been closed.
+fn in_comment(input: &str, options: &ParseOptions) -> bool {
+    if !options.allow_comments {
+        return false;
+    }
+    let mut input = input;
+    loop {
+        input = input.trim_start_matches(options.whitespace);
+        if let Some(comment) = input.strip_prefix("//") {
+            match comment.find('\n') {
+                Some(end) => input = &comment[end..],
+                None => return true,
+            }
+        } else if let Some(comment) = input.strip_prefix("/*") {
+            match comment.find("*/") {
+                Some(end) => input = &comment[end + 2..],
+                None => return true,
+            }
+        } else {
+            return input == "/";
+        }
+    }
+}
+
+/// Parses a document that arrives in chunks, such as reads from a socket.
+///
+/// Each chunk is parsed as far as it goes when it is fed, so a value may be
+/// split across chunks at any character boundary. Only the arrays and
+/// objects left open and the input of a token cut short by the end of a
+/// chunk are kept between chunks, not the text of the document, and the
+/// first error stops parsing, ignoring the chunks fed after it. The document
+/// parses as it would with [`parse_with_options`](super::parse_with_options),
+/// and fails with the same error.
+#[derive(Debug, Clone, Default)]
+pub struct StreamParser {
+    options: ParseOptions,
+    /// The input fed but not yet parsed.
+    pending: String,
+    /// Where `pending` starts in the document.
+    base: Position,
+    /// How far the token at the start of `pending` has been scanned without
+    /// finding its end, so that a long token fed in small chunks is scanned
+    /// only once.
+    scanned: usize,
+    /// Whether parsing stopped at a token cut short, rather than between
+    /// tokens.
+    partial: bool,
+    stack: Vec<Open>,
+    expect: Expect,
+    /// The key parsed before the colon that is expected.
+    key: Option<(Position, String)>,
+    /// The document, once parsed.
+    root: Option<JsonValue>,
+    /// The number of elements and members started so far.
+    elements: usize,
+    /// The number of bytes fed so far.
+    fed: usize,
+    error: Option<ParseError>,
+}
+
+impl StreamParser {
+    pub fn new() -> Self {
+        StreamParser::default()
+    }
+
+    /// A parser accepting what [`parse_with_options`](super::parse_with_options)
+    /// does with `options`.
+    pub fn with_options(options: ParseOptions) -> Self {
+        StreamParser {
+            options,
+            ..StreamParser::default()
+        }
+    }
+
+    /// Parses the next chunk of the document, as far as it goes.
+    pub fn feed(&mut self, chunk: &str) {
+        if self.error.is_some() {
+            return;
+        }
+        self.fed += chunk.len();
+        if self.fed > self.options.max_input_bytes {
+            self.error = Some(ParseError::at("", 0, ErrorKind::InputTooLarge));
+            return;
+        }
+        self.pending.push_str(chunk);
+        self.advance(false);
+    }
+
+    /// Parses the end of the document, returning it.
+    pub fn finish(mut self) -> Result<JsonValue, ParseError> {
+        if self.error.is_none() {
+            self.advance(true);
+        }
+        match (self.error, self.root) {
+            (Some(error), _) => Err(error),
+            (None, Some(root)) => Ok(root),
+            (None, None) => unreachable!("the document ended without an error"),
+        }
+    }
+
+    /// Parses what has been fed, to its end if it is `last`, then drops the
+    /// input parsed.
+    fn advance(&mut self, last: bool) {
+        let mut pending = mem::take(&mut self.pending);
+        match self.parse_pending(&pending, last) {
+            Ok(parsed) => {
+                self.base = self.base.advance(&pending, parsed);
+                pending.drain(..parsed);
+                self.pending = pending;
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    /// Parses `pending` until it runs out or only a token cut short is left,
+    /// returning how much was parsed. The end of `pending` is the end of the
+    /// document if it is `last`.
+    ///
+    /// Each state fails as [`parse_container`](super::parse_container) does
+    /// in its place, so the errors are those of parsing the whole document.
+    fn parse_pending(&mut self, pending: &str, last: bool) -> Result<usize, ParseError> {
+        let mut at = 0;
+        self.partial = false;
+        loop {
+            let input = &pending[at..];
+            if self.base.offset + at == 0 && self.options.strip_bom {
+                if let Some(rest) = input.strip_prefix('\u{FEFF}') {
+                    at = pending.len() - rest.len();
+                    continue;
+                }
+            }
+            if !last && in_comment(input, &self.options) {
+                return Ok(at);
+            }
+            let rest = match skip_ws(input, &self.options) {
+                Ok((rest, _)) => rest,
+                Err(e) => return Err(self.locate(pending, self.fail(e))),
+            };
+            at = pending.len() - rest.len();
+            if rest.is_empty() && !last {
+                return Ok(at);
+            }
+            match self.expect {
+                Expect::Root => {
+                    if self.options.require_container_root && !rest.starts_with(['[', '{']) {
+                        let error = Error::new(rest, ErrorKind::ContainerRootRequired);
+                        return Err(self.locate(pending, Err::Failure(error)));
+                    }
+                    if rest.is_empty() {
+                        let mut error = Error::new(rest, ErrorKind::EmptyInput);
+                        error.expected = VALUE_TOKENS;
+                        return Err(self.locate(pending, self.fail(Err::Error(error))));
+                    }
+                    match self.value(pending, rest, last, VALUE_TOKENS)? {
+                        Some(len) => at += len,
+                        None => return Ok(at),
+                    }
+                }
+                Expect::Element { first } | Expect::Member { first } => {
+                    let close = self.stack.last().unwrap().close();
+                    if rest.starts_with(close) && (first || self.options.allow_trailing_comma) {
+                        self.close()?;
+                        at += 1;
+                        continue;
+                    }
+                    self.elements += 1;
+                    if self.elements > self.options.max_total_elements {
+                        let error = Error::new(rest, ErrorKind::TooManyElements);
+                        return Err(self.locate(pending, self.fail(Err::Failure(error))));
+                    }
+                    self.expect = match self.expect {
+                        Expect::Element { .. } => Expect::ElementValue { first },
+                        _ => Expect::Key { first },
+                    };
+                }
+                Expect::ElementValue { first } => {
+                    let expected = if first {
+                        VALUE_TOKENS_OR_CLOSE
+                    } else {
+                        VALUE_TOKENS
+                    };
+                    if rest.is_empty() {
+                        return Err(self.unclosed(expected));
+                    }
+                    if !first && rest.starts_with([',', ']']) {
+                        let mut error = Error::new(rest, ErrorKind::ExpectedValue);
+                        error.expected = VALUE_TOKENS;
+                        return Err(self.locate(pending, self.fail(Err::Failure(error))));
+                    }
+                    match self.value(pending, rest, last, expected)? {
+                        Some(len) => at += len,
+                        None => return Ok(at),
+                    }
+                }
+                Expect::Key { first } => {
+                    let expected: &[_] = if first { &["string", "}"] } else { &["string"] };
+                    if rest.is_empty() {
+                        return Err(self.unclosed(expected));
+                    }
+                    if self.cut_short(rest, last) {
+                        return Ok(at);
+                    }
+                    match parse_key_token(rest, &self.options) {
+                        Ok((after, key)) => {
+                            let key_at = self.base.advance(pending, at);
+                            self.key = Some((key_at, key.into_owned()));
+                            self.expect = Expect::Colon;
+                            at = pending.len() - after.len();
+                        }
+                        Err(e) => {
+                            let error = self.fail(expecting(e, rest, expected));
+                            return Err(self.locate(pending, error));
+                        }
+                    }
+                }
+                Expect::Colon => match char::<_, Error<'_>>(':')(rest) {
+                    Ok(_) => {
+                        let (key_at, key) = self.key.take().unwrap();
+                        if key.len() > self.options.max_string_length {
+                            let error = Error::new("", ErrorKind::StringTooLong);
+                            return Err(self.error_at(key_at, error));
+                        }
+                        if let Some(Open::Object {
+                            key: pending_key, ..
+                        }) = self.stack.last_mut()
+                        {
+                            *pending_key = Some((key_at, key));
+                        }
+                        self.expect = Expect::MemberValue;
+                        at += 1;
+                    }
+                    Err(e) => return Err(self.locate(pending, self.fail(e))),
+                },
+                Expect::MemberValue => {
+                    if rest.is_empty() {
+                        return Err(self.unclosed(VALUE_TOKENS));
+                    }
+                    match self.value(pending, rest, last, VALUE_TOKENS)? {
+                        Some(len) => at += len,
+                        None => return Ok(at),
+                    }
+                }
+                Expect::Separator => {
+                    let open = self.stack.last().unwrap();
+                    let close = open.close();
+                    let expected: &[_] = if close == ']' {
+                        &[",", "]"]
+                    } else {
+                        &[",", "}"]
+                    };
+                    if rest.is_empty() {
+                        return Err(self.unclosed(expected));
+                    }
+                    if rest.starts_with(',') {
+                        self.expect = match open {
+                            Open::Array { .. } => Expect::Element { first: false },
+                            Open::Object { .. } => Expect::Member { first: false },
+                        };
+                    } else if rest.starts_with(close) {
+                        self.close()?;
+                    } else {
+                        let mut error = Error::new(rest, ErrorKind::ExpectedSeparator(close));
+                        error.expected = expected;
+                        return Err(self.locate(pending, self.fail(Err::Failure(error))));
+                    }
+                    at += 1;
+                }
+                Expect::Done => {
+                    if rest.is_empty() {
+                        return Ok(at);
+                    }
+                    let error = Error::new(rest, ErrorKind::Nom(nom::error::ErrorKind::Eof));
+                    return Err(self.locate(pending, Err::Failure(error)));
+                }
+            }
+        }
+    }
+
+    /// Whether the token at the start of `rest` may go on past the end of
+    /// the input, which it does not if that is `last`.
+    fn cut_short(&mut self, rest: &str, last: bool) -> bool {
+        match unfinished_token(rest, self.scanned, &self.options) {
+            Some(scanned) if !last => {
+                self.scanned = scanned;
+                self.partial = true;
+                true
+            }
+            _ => {
+                self.scanned = 0;
+                false
+            }
+        }
+    }
+
+    /// Parses the value at the start of `rest`, the rest of `pending`, where
+    /// `expected` would be accepted, returning how much of `rest` it took,
+    /// only its opener for an array or object, or `None` if it is a token
+    /// cut short.
+    fn value(
+        &mut self,
+        pending: &str,
+        rest: &str,
+        last: bool,
+        expected: &'static [&'static str],
+    ) -> Result<Option<usize>, ParseError> {
+        if rest.starts_with(['[', '{']) {
+            let at = self.base.advance(pending, pending.len() - rest.len());
+            let (open, expect) = if rest.starts_with('[') {
+                let items = Vec::new();
+                (Open::Array { at, items }, Expect::Element { first: true })
+            } else {
+                let (members, key) = (Vec::new(), None);
+                (
+                    Open::Object { at, members, key },
+                    Expect::Member { first: true },
+                )
+            };
+            self.stack.push(open);
+            if self.stack.len() > self.options.max_depth {
+                let error = Error::new(&rest[1..], ErrorKind::DepthLimitExceeded);
+                return Err(self.locate(pending, self.fail(Err::Failure(error))));
+            }
+            self.expect = expect;
+            return Ok(Some(1));
+        }
+        if self.cut_short(rest, last) {
+            return Ok(None);
+        }
+        let (after, value) = match parse_hooked_number(rest, &self.options, &mut ValueBuilder) {
+            Some(hooked) => hooked,
+            None => {
+                let parsed = if self.stack.is_empty() {
+                    parse_scalar(rest, &self.options)
+                } else {
+                    context("parse", |i| parse_scalar(i, &self.options))(rest)
+                };
+                match parsed {
+                    Ok((after, scalar)) => (after, ValueBuilder.scalar(scalar)),
+                    Err(e) => {
+                        let error = self.fail(expecting(e, rest, expected));
+                        return Err(self.locate(pending, error));
+                    }
+                }
+            }
+        };
+        self.add(value);
+        Ok(Some(rest.len() - after.len()))
+    }
+
+    /// Adds `value` to the innermost container, or makes it the document.
+    fn add(&mut self, value: JsonValue) {
+        match self.stack.last_mut() {
+            None => {
+                self.root = Some(value);
+                self.expect = Expect::Done;
+                return;
+            }
+            Some(Open::Array { items, .. }) => items.push(value),
+            Some(Open::Object { members, key, .. }) => {
+                let (at, key) = key.take().unwrap();
+                members.push((at, key, value));
+            }
+        }
+        self.expect = Expect::Separator;
+    }
+
+    /// Closes the innermost container, building it from what it holds.
+    fn close(&mut self) -> Result<(), ParseError> {
+        let value = match self.stack.last_mut().unwrap() {
+            Open::Array { items, .. } => ValueBuilder.array(mem::take(items)),
+            Open::Object { members, .. } => {
+                let members = mem::take(members);
+                let mut object = ValueBuilder.new_object(members.len());
+                let policy = self.options.duplicate_keys;
+                if policy == DuplicateKeyPolicy::Collect {
+                    let members = members
+                        .into_iter()
+                        .map(|(_, key, value)| (("", Cow::Owned(key)), value));
+                    collect_members(&mut ValueBuilder, &mut object, members);
+                } else {
+                    for (at, key, value) in members {
+                        if ValueBuilder.contains_key(&object, &key) {
+                            if policy == DuplicateKeyPolicy::Error {
+                                let error = Error::new("", ErrorKind::DuplicateKey(key));
+                                return Err(self.error_at(at, error));
+                            }
+                            if policy == DuplicateKeyPolicy::FirstWins {
+                                continue;
+                            }
+                        }
+                        ValueBuilder.insert(&mut object, Cow::Owned(key), value);
+                    }
+                }
+                ValueBuilder.object(object)
+            }
+        };
+        self.stack.pop();
+        self.add(value);
+        Ok(())
+    }
+
+    /// Puts `error` in the contexts of the open containers, as if they had
+    /// been parsed recursively, and records the path of the value it
+    /// occurred in.
+    fn fail<'p>(&self, error: Err<Error<'p>>) -> Err<Error<'p>> {
+        let mut error = match error {
+            Err::Error(e) | Err::Failure(e) => e,
+            incomplete => return incomplete,
+        };
+        for (level, open) in self.stack.iter().enumerate().rev() {
+            error.context.push(open.context());
+            if level > 0 {
+                error.context.push("parse");
+            }
+        }
+        if !self.stack.is_empty() {
+            error.path = self.path();
+        }
+        error.context.push("parse");
+        Err::Failure(error)
+    }
+
+    /// The JSON Pointer of the value being parsed in the innermost container.
+    fn path(&self) -> String {
+        let mut path = String::new();
+        for open in &self.stack {
+            let token = match open {
+                Open::Array { items, .. } => items.len().to_string(),
+                Open::Object {
+                    key: Some((_, key)),
+                    ..
+                } => pointer::escape(key),
+                Open::Object { .. } => continue,
+            };
+            path.push('/');
+            path.push_str(&token);
+        }
+        path
+    }
+
+    /// Locates `error`, produced while parsing `pending`, in the document.
+    fn locate(&self, pending: &str, error: Err<Error<'_>>) -> ParseError {
+        let error = ParseError::new(pending, error);
+        let at = self.base.advance(pending, error.offset);
+        ParseError {
+            offset: at.offset,
+            line: at.line,
+            column: at.column,
+            ..error
+        }
+    }
+
+    /// Locates `error`, whose input is empty, at `at` in the document, in
+    /// the contexts of the open containers.
+    fn error_at(&self, at: Position, error: Error<'_>) -> ParseError {
+        let error = ParseError::new("", self.fail(Err::Failure(error)));
+        ParseError {
+            offset: at.offset,
+            line: at.line,
+            column: at.column,
+            ..error
+        }
+    }
+
+    /// The error for the document ending inside the innermost container,
+    /// located at its opener, where `expected` would have been accepted.
+    fn unclosed(&self, expected: &'static [&'static str]) -> ParseError {
+        let open = self.stack.last().unwrap();
+        let mut error = Error::new("", ErrorKind::Unclosed(open.open()));
+        error.expected = expected;
+        self.error_at(open.at(), error)
+    }
+}
+
+/// Whether `s` is a `\uXXXX` or other escape cut short.
+fn is_escape_prefix(s: &str) -> bool {
+    match s.strip_prefix('\\') {
+        Some(rest) => match rest.strip_prefix('u') {
+            Some(hex) => hex.len() < 4 && hex.bytes().all(|b| b.is_ascii_hexdigit()),
+            None => rest.is_empty(),
+        },
+        None => false,
+    }
+}
+
+/// Whether `s`, the rest of a string from where parsing it failed, is cut
+/// short: nothing, an escape cut short, or a high surrogate escape lacking
+/// all or part of its low surrogate.
+fn is_string_tail(s: &str) -> bool {
+    let high = s
+        .strip_prefix("\\u")
+        .and_then(|hex| hex.get(..4))
+        .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+        .is_some_and(|unit| (0xD800..0xDC00).contains(&unit));
+    let s = if high { &s[6..] } else { s };
+    s.is_empty() || is_escape_prefix(s)
+}
+
+/// Whether `token`, which the input ends with, is only cut short of a
+/// string, number or keyword, given `rest`, the input from where parsing it
+/// failed.
+fn is_cut_short(token: &str, rest: &str) -> bool {
+    if token.starts_with('"') {
+        return is_string_tail(rest);
+    }
+    ["true", "false", "null"]
+        .iter()
+        .any(|word| word.starts_with(token))
+        || parse(&format!("{}0", token)).is_ok()
+}
+
+/// Parses `input` as a whole JSON document like [`parse`], but fails with
+/// [`ErrorKind::NeedMoreInput`] where `input` is only the start of one, for
+/// a caller buffering a document to tell whether to wait for more of it.
+///
+/// That is when the input ends inside a string, number, keyword, array or
+/// object with nothing malformed before the end, where the error is then
+/// located. A document that is complete but could go on, such as the number
+/// `12`, parses as it is.
+pub fn parse_streaming(input: &str) -> Result<JsonValue, ParseError> {
+    let mut parser = StreamParser::new();
+    parser.feed(input);
+    if parser.error.is_some() {
+        return parser.finish();
+    }
+    // What the end of the input cut short: the token parsing stopped at, or
+    // else the innermost container, or else the document.
+    let token = parser.partial.then_some(parser.base.offset);
+    let container = parser.stack.last().map(|open| open.at().offset);
+    parser.finish().map_err(|error| {
+        let at = match token {
+            _ if matches!(error.kind, ErrorKind::Unclosed(_)) => container,
+            Some(at) if is_cut_short(&input[at..], &input[error.offset..]) => Some(at),
+            Some(_) => None,
+            None => Some(container.unwrap_or(input.len())),
+        };
+        match at {
+            Some(at) => ParseError {
+                context: error.context,
+                path: error.path,
+                expected: error.expected,
+                ..ParseError::at(input, input.len(), ErrorKind::NeedMoreInput { at })
+            },
+            None => error,
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{parse_document, parse_with_options};
+    use super::*;
+
+    /// Feeds `input` to a parser with `options` in chunks split at `splits`,
+    /// byte offsets in ascending order.
+    fn feed_split(input: &str, splits: &[usize], options: &ParseOptions) -> StreamParser {
+        let mut parser = StreamParser::with_options(options.clone());
+        let mut start = 0;
+        for &end in splits.iter().chain([input.len()].iter()) {
+            parser.feed(&input[start..end]);
+            start = end;
+        }
+        parser
+    }
+
+    /// Checks that `input` parses with `options` the same whole, fed one
+    /// character at a time, and split in two chunks at every character.
+    fn check_chunks(input: &str, options: &ParseOptions) {
+        let expected = parse_with_options(input, options).map_err(|e| ParseError::new(input, e));
+        let boundaries: Vec<usize> = input.char_indices().map(|(i, _)| i).skip(1).collect();
+        assert_eq!(
+            feed_split(input, &boundaries, options).finish(),
+            expected,
+            "{:?} one character at a time",
+            input
+        );
+        for &split in &boundaries {
+            assert_eq!(
+                feed_split(input, &[split], options).finish(),
+                expected,
+                "{:?} split at {}",
+                input,
+                split
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = r#"{"key": ["café", -1.5e3, {"nested": true}], "ü": null}"#;
+        let mut parser = StreamParser::new();
+        for (i, c) in input.char_indices() {
+            parser.feed(&input[i..i + c.len_utf8()]);
+        }
+        assert_eq!(parser.finish(), parse_document(input));
+
+        let mut parser = StreamParser::new();
+        parser.feed("[1, ");
+        parser.feed("2");
+        assert_eq!(parser.finish(), parse_document("[1, 2"));
+    }
+
+    #[test]
+    fn test_stream_chunks() {
+        let options = ParseOptions::default();
+        for input in [
+            // Split mid-string, mid-escape and mid-surrogate pair.
+            r#"["plain", "a\"b\\c\/d", "é😀", "ü€😀"]"#,
+            // Split mid-number and mid-keyword.
+            "[0, -12, 3.25, -1.5e+30, 6E-7, true, false, null]",
+            "12345",
+            " \"top\" ",
+            r#"{"a": {"b": [[], {}, [1, {"c": "d"}]]}, "e": -0.5}"#,
+            "[1 2]",
+            r#"{"a": 1, "a": 2}"#,
+            r#"["a\x"]"#,
+            "[1.]",
+            r#"{"key" 1}"#,
+            "[1, 2] x",
+            "[1,\u{0}2]",
+            "[\"\u{1}\"]",
+            "{\"a\":",
+            "[[1, 2], [3, ",
+            "",
+        ] {
+            check_chunks(input, &options);
+        }
+        let options = ParseOptions {
+            duplicate_keys: DuplicateKeyPolicy::Error,
+            max_depth: 3,
+            ..ParseOptions::json5()
+        };
+        for input in [
+            "// lead\n{a: 'x\\'y', /* mid */ b: [NaN, -Infinity, +1, 0x1F,], c: .5,} // end",
+            "[1 /* open",
+            r#"{"a": 1, "a": 2}"#,
+            "[[[[1]]]]",
+        ] {
+            check_chunks(input, &options);
+        }
+    }
+
+    #[test]
+    fn test_stream_drops_parsed_input() {
+        let mut parser = StreamParser::new();
+        parser.feed("[");
+        for _ in 0..1000 {
+            parser.feed(r#"{"id": 12345, "name": "abcdef"}, "#);
+            assert!(parser.pending.is_empty());
+        }
+        parser.feed(r#""cut "#);
+        assert_eq!(parser.pending, r#""cut "#);
+        parser.feed(r#"short", 123"#);
+        assert_eq!(parser.pending, "123");
+        parser.feed("]");
+        let value = parser.finish().unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 1002);
+
+        // The first error stops parsing, and what follows is ignored.
+        let mut parser = StreamParser::new();
+        parser.feed("[1 2");
+        assert!(parser.pending.is_empty());
+        parser.feed(", 3]");
+        assert_eq!(parser.finish(), parse_document("[1 2, 3]"));
+    }
+
+    #[test]
+    fn test_parse_streaming() {
+        let error = parse_streaming(r#"{"a":"#).unwrap_err();
+        assert_eq!(
+            (error.offset, error.kind),
+            (5, ErrorKind::NeedMoreInput { at: 0 })
+        );
+        assert_eq!(
+            parse_streaming(r#"{"a": 1}"#),
+            parse_document(r#"{"a": 1}"#)
+        );
+        assert_eq!(parse_streaming("12"), Ok(JsonValue::Int(12)));
+
+        for (input, at) in [
+            ("", 0),
+            (" [1, ", 1),
+            (r#"{"a""#, 0),
+            (r#"{"ab"#, 1),
+            (r#"["x\"#, 1),
+            (r#"["\u00"#, 1),
+            (r#"["\ud83d\u"#, 1),
+            ("[tru", 1),
+            (r#"{"a": [nul"#, 7),
+            ("[1, -", 4),
+            ("1.", 0),
+            ("[1e+", 1),
+            ("[-0", 0),
+        ] {
+            assert_eq!(
+                parse_streaming(input).unwrap_err().kind,
+                ErrorKind::NeedMoreInput { at },
+                "{:?}",
+                input
+            );
+        }
+        // Input malformed before its end fails as it would otherwise.
+        for input in [
+            "[1 2",
+            "[1] tr",
+            r#"{"a" t"#,
+            "[tru ",
+            "[01",
+            "[\"a\nb",
+            r#"["\x"#,
+            r#"["\ud83d\ud83d"#,
+            "[1, 2]]",
+        ] {
+            assert_eq!(parse_streaming(input), parse_document(input), "{:?}", input);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use alloc::vec::Vec;
+
+use super::{ErrorKind, ParseError, ParseOptions};
+
+/// Whether `b` is whitespace as JSON has it, which unlike
+/// `u8::is_ascii_whitespace` leaves out form feeds.
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+/// Whether `b` may appear outside strings, as whitespace, punctuation or part
+/// of a literal or number.
+fn is_structural(b: u8) -> bool {
+    is_whitespace(b)
+        || matches!(b, b'[' | b']' | b'{' | b'}' | b',' | b':')
+        || b.is_ascii_alphanumeric()
+        || matches!(b, b'+' | b'-' | b'.')
+}
+
+/// Checks the structure of `input` without parsing it, in one pass that
+/// builds no values: that brackets balance and match, that strings end and
+/// hold no control characters, that only characters JSON allows appear
+/// outside strings, and that nothing follows the closing bracket of the
+/// document.
+///
+/// This is a much cheaper way than [`parse_document`](super::parse_document)
+/// to reject a large document that is plainly malformed before committing
+/// the memory to parse it, allocating only a stack of the open brackets. The
+/// tokens between brackets are not checked, so `[1 2]` or `{"a"}` pass, and
+/// a document that passes may still fail to parse. Nesting deeper than the
+/// default [`ParseOptions::max_depth`] fails as parsing would.
+pub fn validate_structure(input: &str) -> Result<(), ParseError> {
+    let error = |offset, kind| Err(ParseError::at(input, offset, kind));
+    let max_depth = ParseOptions::default().max_depth;
+    let bytes = input.as_bytes();
+    // The offsets of the brackets not closed yet.
+    let mut open: Vec<usize> = Vec::new();
+    let mut closed = false;
+    let mut empty = true;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if !is_whitespace(b) {
+            if closed {
+                return error(i, ErrorKind::Nom(nom::error::ErrorKind::Eof));
+            }
+            empty = false;
+        }
+        match b {
+            b'"' => loop {
+                i += 1;
+                match bytes.get(i) {
+                    None => return error(i, ErrorKind::Char('"')),
+                    Some(b'"') => break,
+                    // An escape cut short fails at its backslash, as in parsing.
+                    Some(b'\\') if i + 1 == bytes.len() => return error(i, ErrorKind::Char('"')),
+                    Some(b'\\') => i += 1,
+                    Some(&c) if c < 0x20 => return error(i, ErrorKind::Char('"')),
+                    Some(_) => {}
+                }
+            },
+            b'[' | b'{' => {
+                if open.len() == max_depth {
+                    return error(i + 1, ErrorKind::DepthLimitExceeded);
+                }
+                open.push(i);
+            }
+            b']' | b'}' => {
+                let opener = if b == b']' { b'[' } else { b'{' };
+                match open.pop() {
+                    Some(at) if bytes[at] == opener => closed = open.is_empty(),
+                    Some(at) => {
+                        let close = if bytes[at] == b'[' { ']' } else { '}' };
+                        return error(i, ErrorKind::ExpectedSeparator(close));
+                    }
+                    None => return error(i, ErrorKind::Nom(nom::error::ErrorKind::Eof)),
+                }
+            }
+            b if !is_structural(b) => {
+                let c = input[i..].chars().next().unwrap();
+                return error(i, ErrorKind::UnexpectedChar(c));
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    if let Some(&at) = open.last() {
+        return error(at, ErrorKind::Unclosed(bytes[at] as char));
+    }
+    if empty {
+        return error(input.len(), ErrorKind::EmptyInput);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_document;
+    use super::*;
+
+    #[test]
+    fn test_validate_structure() {
+        for input in [
+            r#"{"a": [1, {"b": "]}\"["}], "c": -1.5e3, "d": null}"#,
+            " [] ",
+            "true",
+            r#""é""#,
+        ] {
+            assert_eq!(validate_structure(input), Ok(()), "{:?}", input);
+            assert!(parse_document(input).is_ok());
+        }
+        assert_eq!(validate_structure("[1 2]"), Ok(()));
+
+        // Where the structure is broken, the error matches the parser's.
+        for input in [
+            r#"{"a": [1, 2}"#,
+            r#"{"a": [1, {"#,
+            r#"["abc"#,
+            "[1]]",
+            "[1] x",
+            "[1]\x0c",
+            " \n ",
+            "[\"a\nb\"]",
+            "\"\\",
+        ] {
+            let error = validate_structure(input).unwrap_err();
+            let expected = parse_document(input).unwrap_err();
+            assert_eq!(
+                (error.offset, &error.kind),
+                (expected.offset, &expected.kind),
+                "{:?}",
+                input
+            );
+        }
+        let error = validate_structure("[1, 'a']").unwrap_err();
+        assert_eq!(
+            (error.offset, error.kind),
+            (4, ErrorKind::UnexpectedChar('\''))
+        );
+        let deep = "[".repeat(129);
+        let error = validate_structure(&deep).unwrap_err();
+        let expected = parse_document(&deep).unwrap_err();
+        assert_eq!(
+            (error.offset, error.kind),
+            (expected.offset, ErrorKind::DepthLimitExceeded)
+        );
+    }
+}
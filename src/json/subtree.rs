@@ -0,0 +1,166 @@
+use alloc::string::String;
+
+use super::{
+    parse_document, parse_string, parse_value, pointer::escape, pointer::parse_index,
+    pointer::tokens, skip_element, ErrorKind, JsonValue, ParseError, ParseOptions, ValueBuilder,
+    JSON_WHITESPACE,
+};
+
+/// The outcome of looking up a member or element without parsing its
+/// siblings.
+enum Lookup<'a> {
+    /// The input starting with the value.
+    Found(&'a str),
+    /// The value is a scalar, or an array or object without the member or
+    /// element.
+    Missing,
+    /// Scanning found the input malformed.
+    Malformed,
+}
+
+/// Finds the member or element `token` of the array or object at the start
+/// of `input`, scanning past the others. Of several members with the key, the
+/// last one is found, as parsing keeps it.
+fn find<'a>(input: &'a str, token: &str) -> Lookup<'a> {
+    let (close, index) = match input.chars().next() {
+        Some('{') => ('}', None),
+        Some('[') => match parse_index(token) {
+            Some(index) => (']', Some(index)),
+            None => return Lookup::Missing,
+        },
+        _ => return Lookup::Missing,
+    };
+    let mut rest = input[1..].trim_start_matches(JSON_WHITESPACE);
+    if rest.starts_with(close) {
+        return Lookup::Missing;
+    }
+    let mut found = None;
+    for i in 0.. {
+        let value = match index {
+            Some(_) => rest,
+            None => {
+                let (after, key) = match parse_string(rest) {
+                    Ok(key) => key,
+                    Err(_) => return Lookup::Malformed,
+                };
+                let after = after.trim_start_matches(JSON_WHITESPACE);
+                let value = match after.strip_prefix(':') {
+                    Some(value) => value.trim_start_matches(JSON_WHITESPACE),
+                    None => return Lookup::Malformed,
+                };
+                if key == token {
+                    found = Some(value);
+                }
+                value
+            }
+        };
+        if index == Some(i) {
+            return Lookup::Found(value);
+        }
+        let end = skip_element(value);
+        match end.chars().next() {
+            Some(',') => rest = end[1..].trim_start_matches(JSON_WHITESPACE),
+            Some(c) if c == close => break,
+            _ => return Lookup::Malformed,
+        }
+    }
+    found.map_or(Lookup::Missing, Lookup::Found)
+}
+
+/// Parses only the value `pointer` refers to in `input`, a whole JSON
+/// document, as [`JsonValue::pointer`] would find it after parsing it all.
+///
+/// The containers on the way to the value are scanned for the member or
+/// element the next reference token names, skipping past the others by
+/// balancing brackets and quotes without building anything, which saves
+/// the time and memory of parsing a large document to keep a small part.
+/// Skipped values are not validated, so input that is malformed there may go
+/// unnoticed; if scanning finds the structure itself malformed, the whole
+/// document is parsed to report the error.
+///
+/// A pointer that does not resolve fails with
+/// [`ErrorKind::PointerNotFound`], located at the value that lacks the next
+/// token, with that value's pointer as the error's path.
+pub fn parse_subtree(input: &str, pointer: &str) -> Result<JsonValue, ParseError> {
+    let not_found = |at: &str, path: String| ParseError {
+        path,
+        ..ParseError::at(input, input.len() - at.len(), ErrorKind::PointerNotFound)
+    };
+    let tokens = match tokens(pointer) {
+        Some(tokens) => tokens,
+        None => return Err(not_found(input, String::new())),
+    };
+    let mut rest = input.trim_start_matches(JSON_WHITESPACE);
+    let mut path = String::new();
+    let mut depth = 0;
+    for token in tokens {
+        rest = match find(rest, &token) {
+            Lookup::Found(value) => value,
+            Lookup::Missing => return Err(not_found(rest, path)),
+            Lookup::Malformed => {
+                return match parse_document(input)?.pointer(pointer) {
+                    Some(value) => Ok(value.clone()),
+                    None => Err(not_found(input, String::new())),
+                }
+            }
+        };
+        path.push('/');
+        path.push_str(&escape(&token));
+        depth += 1;
+    }
+    match parse_value(rest, &ParseOptions::default(), depth, &mut ValueBuilder) {
+        Ok((_, value)) => Ok(value),
+        Err(e) => Err(ParseError::new(input, e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_parse_subtree() {
+        let input = r#"{
+            "meta": {"skipped": ["]", "}", "\"{"], "n": [[[1]]]},
+            "data": {"count": 2, "items": [{"id": 1}, {"id": "two"}], "tail": nul},
+            "big": [1, 2, 3]
+        }"#;
+        let items = parse_subtree(input, "/data/items").unwrap();
+        assert_eq!(
+            items,
+            parse_complete(r#"[{"id": 1}, {"id": "two"}]"#).unwrap()
+        );
+        // Only the target is parsed, so the malformed `nul` after it and the
+        // skipped siblings are never materialized.
+        assert!(parse_complete(input).is_err());
+        assert_eq!(parse_subtree(input, "/data/items/1/id"), Ok("two".into()));
+        assert_eq!(parse_subtree(input, "/big/2"), Ok(JsonValue::Int(3)));
+        assert_eq!(
+            parse_subtree(r#"{"a~/b": 1}"#, "/a~0~1b"),
+            Ok(JsonValue::Int(1))
+        );
+        assert_eq!(
+            parse_subtree(r#"{"a": 1, "a": 2}"#, "/a"),
+            Ok(JsonValue::Int(2))
+        );
+        assert_eq!(
+            parse_subtree(" [1] ", ""),
+            Ok(parse_complete("[1]").unwrap())
+        );
+
+        let error = parse_subtree(input, "/data/missing").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::PointerNotFound);
+        assert_eq!(error.path, "/data");
+        assert_eq!(error.offset, input.find(r#"{"count""#).unwrap());
+        for pointer in ["/big/3", "/big/01", "/data/count/x", "nope"] {
+            let error = parse_subtree(input, pointer).unwrap_err();
+            assert_eq!(error.kind, ErrorKind::PointerNotFound, "{}", pointer);
+        }
+
+        let error = parse_subtree(r#"{"a" 1, "b": 2}"#, "/b").unwrap_err();
+        assert_eq!(error, parse_document(r#"{"a" 1, "b": 2}"#).unwrap_err());
+        assert!(parse_subtree(r#"{"a": [1, "b": 2}"#, "/b").is_err());
+        assert!(parse_subtree(r#"{"a": [1, 2}"#, "/a").is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use core::ops::Range;
+
+use nom::{branch::alt, combinator::map, error::make_error};
+
+use super::{
+    number_literal, parse_bool, parse_null, parse_string, ParseError, Res, JSON_WHITESPACE,
+};
+
+/// What a [`Token`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// One of `[`, `]`, `{`, `}`, `:` and `,`.
+    Punctuation,
+    /// A string, including its quotes.
+    String,
+    Number,
+    /// `true`, `false` or `null`.
+    Keyword,
+    /// A run of whitespace.
+    Whitespace,
+}
+
+/// A piece of the input, from [`tokenize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// The byte range of the token in the input.
+    pub span: Range<usize>,
+    /// The 1-based line the token starts on.
+    pub line: usize,
+    /// The 1-based column the token starts at, counted in characters.
+    pub column: usize,
+}
+
+/// The tokens of an input, in order.
+struct Tokens<'a> {
+    input: &'a str,
+    rest: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Tokens<'a> {
+    /// Recognizes the token at the start of the remaining input, returning the
+    /// input following it.
+    fn token(&self) -> Res<'a, TokenKind> {
+        let rest = self.rest;
+        match rest.chars().next() {
+            Some(c) if JSON_WHITESPACE.contains(&c) => Ok((
+                rest.trim_start_matches(JSON_WHITESPACE),
+                TokenKind::Whitespace,
+            )),
+            Some('[' | ']' | '{' | '}' | ':' | ',') => Ok((&rest[1..], TokenKind::Punctuation)),
+            Some('"') => map(parse_string, |_| TokenKind::String)(rest),
+            Some('-' | '0'..='9') => map(number_literal, |_| TokenKind::Number)(rest),
+            Some(_) => alt((
+                map(parse_bool, |_| TokenKind::Keyword),
+                map(parse_null, |_| TokenKind::Keyword),
+            ))(rest),
+            None => Err(nom::Err::Error(make_error(
+                rest,
+                nom::error::ErrorKind::Eof,
+            ))),
+        }
+    }
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Result<Token, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let start = self.input.len() - self.rest.len();
+        let (rest, kind) = match self.token() {
+            Ok(token) => token,
+            Err(e) => {
+                self.rest = &self.rest[self.rest.len()..];
+                return Some(Err(ParseError::new(self.input, e)));
+            }
+        };
+        let token = Token {
+            kind,
+            span: start..self.input.len() - rest.len(),
+            line: self.line,
+            column: self.column,
+        };
+        let text = &self.rest[..self.rest.len() - rest.len()];
+        match text.rfind('\n') {
+            Some(i) => {
+                self.line += text.matches('\n').count();
+                self.column = text[i + 1..].chars().count() + 1;
+            }
+            None => self.column += text.chars().count(),
+        }
+        self.rest = rest;
+        Some(Ok(token))
+    }
+}
+
+/// Splits `input` into the tokens of the JSON grammar without checking how
+/// they are arranged, as a syntax highlighter needs.
+///
+/// The tokens cover the input without gaps. Once a token is malformed its
+/// error is returned and no further tokens are.
+pub fn tokenize(input: &str) -> impl Iterator<Item = Result<Token, ParseError>> + '_ {
+    Tokens {
+        input,
+        rest: input,
+        line: 1,
+        column: 1,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::ErrorKind;
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let input = "{\"a\\n\": [-1.5e3,\n\ttrue, null]}";
+        let tokens: Vec<_> = tokenize(input).map(Result::unwrap).collect();
+        let kinds: Vec<_> = tokens.iter().map(|t| t.kind).collect();
+        use TokenKind::*;
+        assert_eq!(
+            kinds,
+            [
+                Punctuation,
+                String,
+                Punctuation,
+                Whitespace,
+                Punctuation,
+                Number,
+                Punctuation,
+                Whitespace,
+                Keyword,
+                Punctuation,
+                Whitespace,
+                Keyword,
+                Punctuation,
+                Punctuation,
+            ]
+        );
+        let texts: Vec<_> = tokens.iter().map(|t| &input[t.span.clone()]).collect();
+        assert_eq!(texts.concat(), input);
+        assert_eq!(texts[1], "\"a\\n\"");
+        assert_eq!(texts[5], "-1.5e3");
+        assert_eq!((tokens[5].line, tokens[5].column), (1, 10));
+        assert_eq!((tokens[8].line, tokens[8].column), (2, 2));
+        assert_eq!((tokens[13].line, tokens[13].column), (2, 13));
+
+        let results: Vec<_> = tokenize("[nul, 1]").collect();
+        assert_eq!(results.len(), 2);
+        let error = results[1].clone().unwrap_err();
+        assert_eq!(error.offset, 1);
+        assert_eq!(error.kind, ErrorKind::Nom(nom::error::ErrorKind::Tag));
+        assert!(tokenize("\"open").next().unwrap().is_err());
+        assert_eq!(tokenize("").count(), 0);
+    }
+}
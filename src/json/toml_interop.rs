@@ -0,0 +1,93 @@
+use alloc::string::ToString;
+use core::convert::TryFrom;
+
+use super::{ConversionError, JsonValue};
+
+/// Integers become `Int` and floats `Number`; TOML datetimes, which JSON
+/// lacks, become strings in their TOML form.
+impl From<toml::Value> for JsonValue {
+    fn from(value: toml::Value) -> Self {
+        match value {
+            toml::Value::String(s) => JsonValue::Str(s),
+            toml::Value::Integer(i) => JsonValue::Int(i),
+            toml::Value::Float(f) => JsonValue::Number(f),
+            toml::Value::Boolean(b) => JsonValue::Bool(b),
+            toml::Value::Datetime(d) => JsonValue::Str(d.to_string()),
+            toml::Value::Array(items) => {
+                JsonValue::Array(items.into_iter().map(JsonValue::from).collect())
+            }
+            toml::Value::Table(table) => {
+                JsonValue::Object(table.into_iter().map(|(k, v)| (k, v.into())).collect())
+            }
+        }
+    }
+}
+
+/// Converts `value` for [`TryFrom`], whose method `toml::Value` shadows with
+/// an inherent `try_from` taking any serializable value.
+fn to_toml(value: JsonValue) -> Result<toml::Value, ConversionError> {
+    Ok(match value {
+        JsonValue::Null => return Err(ConversionError::UnexpectedType("a value other than null")),
+        JsonValue::Bool(b) => toml::Value::Boolean(b),
+        JsonValue::Str(s) => toml::Value::String(s),
+        JsonValue::Array(items) => {
+            toml::Value::Array(items.into_iter().map(to_toml).collect::<Result<_, _>>()?)
+        }
+        JsonValue::Object(map) => toml::Value::Table(
+            map.into_iter()
+                .map(|(k, v)| Ok((k, to_toml(v)?)))
+                .collect::<Result<_, ConversionError>>()?,
+        ),
+        number => match (number.as_i64(), number.as_f64()) {
+            (Some(i), _) => toml::Value::Integer(i),
+            (None, f) => toml::Value::Float(f.unwrap_or(f64::NAN)),
+        },
+    })
+}
+
+/// TOML has no null, so a value containing `Null` anywhere fails to convert
+/// rather than silently losing members or elements. Numbers are converted
+/// like [`JsonValue::as_i64`] when they are integers, and like
+/// [`JsonValue::as_f64`] otherwise.
+impl TryFrom<JsonValue> for toml::Value {
+    type Error = ConversionError;
+
+    fn try_from(value: JsonValue) -> Result<Self, ConversionError> {
+        to_toml(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_toml_round_trip() {
+        let value = parse_complete(
+            r#"{"name": "x", "port": 8080, "ratio": 0.5, "on": true,
+                "tags": ["a", "b"], "db": {"hosts": [{"h": "a"}, {"h": "b"}]}}"#,
+        )
+        .unwrap();
+        let converted: toml::Value = TryFrom::try_from(value.clone()).unwrap();
+        assert_eq!(converted["port"], toml::Value::Integer(8080));
+        assert_eq!(converted["ratio"], toml::Value::Float(0.5));
+        let text = toml::to_string(&converted).unwrap();
+        let back = JsonValue::from(text.parse::<toml::Value>().unwrap());
+        assert_eq!(back, value);
+
+        let datetime = "when = 1979-05-27T07:32:00Z"
+            .parse::<toml::Value>()
+            .unwrap();
+        assert_eq!(
+            JsonValue::from(datetime)["when"],
+            JsonValue::from("1979-05-27T07:32:00Z")
+        );
+        let error: Result<toml::Value, _> =
+            TryFrom::try_from(parse_complete(r#"{"a": [1, null]}"#).unwrap());
+        assert_eq!(
+            error,
+            Err(ConversionError::UnexpectedType("a value other than null"))
+        );
+    }
+}
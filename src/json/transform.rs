@@ -0,0 +1,272 @@
+use alloc::{vec, vec::Vec};
+
+use super::{pointer::parse_index, JsonValue};
+
+/// A step from a value to one of its children, as passed to
+/// [`JsonValue::walk`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSegment<'a> {
+    /// The member of an object with this key.
+    Key(&'a str),
+    /// The element of an array at this index.
+    Index(usize),
+}
+
+/// Calls `f` with `value`, found at `path`, and then with each of its
+/// descendants.
+fn walk_from<'a>(
+    value: &'a JsonValue,
+    path: &mut Vec<PathSegment<'a>>,
+    f: &mut impl FnMut(&[PathSegment<'a>], &'a JsonValue),
+) {
+    f(path, value);
+    match value {
+        JsonValue::Object(map) => {
+            for (key, v) in map {
+                path.push(PathSegment::Key(key));
+                walk_from(v, path, f);
+                path.pop();
+            }
+        }
+        JsonValue::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                path.push(PathSegment::Index(i));
+                walk_from(v, path, f);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn retain_in(value: &mut JsonValue, f: &mut impl FnMut(&str, &JsonValue) -> bool) {
+    match value {
+        JsonValue::Object(map) => {
+            map.retain(|key, v| f(key, v));
+            for v in map.values_mut() {
+                retain_in(v, f);
+            }
+        }
+        JsonValue::Array(items) => {
+            for v in items {
+                retain_in(v, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Options for [`JsonValue::prune`]. The default removes only `null`
+/// members, as [`JsonValue::prune_nulls`] does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruneOptions {
+    /// Also remove members whose value is an empty array or object, including
+    /// one left empty by pruning its own members.
+    pub empty_containers: bool,
+}
+
+fn prune_in(value: &mut JsonValue, options: PruneOptions) {
+    match value {
+        JsonValue::Object(map) => {
+            for v in map.values_mut() {
+                prune_in(v, options);
+            }
+            map.retain(|_, v| match v {
+                JsonValue::Null => false,
+                JsonValue::Array(items) => !(options.empty_containers && items.is_empty()),
+                JsonValue::Object(map) => !(options.empty_containers && map.is_empty()),
+                _ => true,
+            });
+        }
+        JsonValue::Array(items) => {
+            for v in items {
+                prune_in(v, options);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn map_leaves(value: JsonValue, f: &mut impl FnMut(JsonValue) -> JsonValue) -> JsonValue {
+    match value {
+        JsonValue::Object(map) => JsonValue::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, map_leaves(v, f)))
+                .collect(),
+        ),
+        JsonValue::Array(items) => {
+            JsonValue::Array(items.into_iter().map(|v| map_leaves(v, f)).collect())
+        }
+        leaf => f(leaf),
+    }
+}
+
+impl JsonValue {
+    /// Keeps only the object members, at any depth, for which `f` returns
+    /// true when called with their key and value.
+    ///
+    /// The members of an object are tested before those nested in them, so
+    /// the contents of a dropped member are never visited.
+    pub fn retain(&mut self, mut f: impl FnMut(&str, &JsonValue) -> bool) {
+        retain_in(self, &mut f);
+    }
+
+    /// Removes the object members, at any depth, whose value is `null`.
+    pub fn prune_nulls(&mut self) {
+        self.prune(PruneOptions::default());
+    }
+
+    /// Removes the object members, at any depth, whose value is `null` or,
+    /// as `options` selects, an empty container.
+    ///
+    /// Members are pruned after those nested in them, so an object holding
+    /// only `null` members is removed as empty too. Array elements are never
+    /// removed, since that would move the elements after them, but the
+    /// objects in arrays are pruned.
+    pub fn prune(&mut self, options: PruneOptions) {
+        prune_in(self, options);
+    }
+
+    /// Replaces each value at any depth that is not an array or an object
+    /// with what `f` returns for it.
+    pub fn map_values(self, mut f: impl FnMut(JsonValue) -> JsonValue) -> JsonValue {
+        map_leaves(self, &mut f)
+    }
+
+    /// Calls `f` with every value in the tree and the path to it from this
+    /// one, parents before their children, starting with this value and an
+    /// empty path.
+    pub fn walk<'a>(&'a self, mut f: impl FnMut(&[PathSegment<'a>], &'a JsonValue)) {
+        walk_from(self, &mut Vec::new(), &mut f);
+    }
+
+    /// Converts an object whose keys are array indices, such as
+    /// `{"0": "a", "2": "c"}`, to the array with each member at the index of
+    /// its key and `fill` at the indices no key names, here `["a", fill,
+    /// "c"]`.
+    ///
+    /// Keys are read as JSON Pointer indices are, in decimal without leading
+    /// zeros. Returns `None` if a key is not an index or the value is not an
+    /// object. The array is as long as the largest index plus one, however
+    /// few members the object has.
+    pub fn object_to_sparse_array(&self, fill: JsonValue) -> Option<JsonValue> {
+        let map = self.as_object()?;
+        let mut members = Vec::with_capacity(map.len());
+        for (key, value) in map {
+            members.push((parse_index(key)?, value));
+        }
+        let len = members.iter().map(|&(i, _)| i + 1).max().unwrap_or(0);
+        let mut items = vec![fill; len];
+        for (i, value) in members {
+            items[i] = value.clone();
+        }
+        Some(JsonValue::Array(items))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        let mut value = parse_complete(
+            r#"{"user": "a", "password": "x",
+                "accounts": [{"id": 1, "password": "y"}, {"nested": {"password": {"z": 1}}}]}"#,
+        )
+        .unwrap();
+        let mut found = Vec::new();
+        value.walk(|path, _| {
+            if let Some(PathSegment::Key("password")) = path.last() {
+                found.push(path.to_vec());
+            }
+        });
+        assert_eq!(found.len(), 3);
+        assert!(found.contains(&vec![
+            PathSegment::Key("accounts"),
+            PathSegment::Index(0),
+            PathSegment::Key("password"),
+        ]));
+
+        value.retain(|key, _| key != "password");
+        assert_eq!(
+            value,
+            parse_complete(r#"{"user": "a", "accounts": [{"id": 1}, {"nested": {}}]}"#).unwrap()
+        );
+        let mut count = 0;
+        value.walk(|_, _| count += 1);
+        assert_eq!(count, 7);
+    }
+
+    #[test]
+    fn test_prune() {
+        let input = r#"{"a": null, "b": {"c": null, "d": 1}, "e": [], "f": [null, {"g": null}],
+            "h": {"i": {"j": null}}}"#;
+        let mut value = parse_complete(input).unwrap();
+        value.prune_nulls();
+        assert_eq!(
+            value,
+            parse_complete(r#"{"b": {"d": 1}, "e": [], "f": [null, {}], "h": {"i": {}}}"#).unwrap()
+        );
+
+        let mut value = parse_complete(input).unwrap();
+        value.prune(PruneOptions {
+            empty_containers: true,
+        });
+        assert_eq!(
+            value,
+            parse_complete(r#"{"b": {"d": 1}, "f": [null, {}]}"#).unwrap()
+        );
+
+        let mut value = JsonValue::Null;
+        value.prune_nulls();
+        assert_eq!(value, JsonValue::Null);
+    }
+
+    #[test]
+    fn test_object_to_sparse_array() {
+        let value = parse_complete(r#"{"0": "a", "2": "c"}"#).unwrap();
+        assert_eq!(
+            value.object_to_sparse_array(JsonValue::Null),
+            Some(parse_complete(r#"["a", null, "c"]"#).unwrap())
+        );
+        let value = parse_complete(r#"{"3": [1], "1": {"b": 2}}"#).unwrap();
+        assert_eq!(
+            value.object_to_sparse_array(0.into()),
+            Some(parse_complete(r#"[0, {"b": 2}, 0, [1]]"#).unwrap())
+        );
+        let empty = JsonValue::Object(Default::default());
+        assert_eq!(
+            empty.object_to_sparse_array(JsonValue::Null),
+            Some(JsonValue::Array(vec![]))
+        );
+
+        for input in [r#"{"0": 1, "a": 2}"#, r#"{"-1": 1}"#, r#"{"01": 1}"#, "[1]"] {
+            let value = parse_complete(input).unwrap();
+            assert_eq!(
+                value.object_to_sparse_array(JsonValue::Null),
+                None,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_map_values() {
+        let value = parse_complete(r#"{"a": [1, "s", {"b": 2}], "c": {}}"#).unwrap();
+        let value = value.map_values(|v| match v {
+            JsonValue::Int(i) => JsonValue::Int(i * 10),
+            other => other,
+        });
+        assert_eq!(
+            value,
+            parse_complete(r#"{"a": [10, "s", {"b": 20}], "c": {}}"#).unwrap()
+        );
+        assert_eq!(
+            JsonValue::Null.map_values(|_| JsonValue::Bool(true)),
+            JsonValue::Bool(true)
+        );
+    }
+}
@@ -0,0 +1,942 @@
+use alloc::{
+    string::{String, ToString},
+    vec::{self, Vec},
+};
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    mem,
+    ops::Index,
+};
+
+use super::{Entry, JsonValue, Map};
+
+static NULL: JsonValue = JsonValue::Null;
+
+impl JsonValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the number as an `f64`, converting an `Int` or `BigInt` or
+    /// parsing a `RawNumber` if necessary.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(f) => Some(*f),
+            JsonValue::Int(i) => Some(*i as f64),
+            JsonValue::RawNumber(token) => token.parse().ok(),
+            #[cfg(feature = "bigint")]
+            JsonValue::BigInt(b) => b.to_string().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an `Int`, or of a `RawNumber` written as an integer
+    /// that fits. Floats are not converted, even when they have no fractional
+    /// part.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            JsonValue::Int(i) => Some(*i),
+            JsonValue::RawNumber(token) => token.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn as_object_mut(&mut self) -> Option<&mut Map> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Takes the elements of an array without copying them.
+    pub fn into_array(self) -> Option<Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Takes the members of an object without copying them.
+    pub fn into_object(self) -> Option<Map> {
+        match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns the member `key` of an object, or `None` when the key is
+    /// missing or the value is not an object.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        self.as_object().and_then(|map| map.get(key))
+    }
+
+    /// Returns the element `index` of an array, or `None` when the index is
+    /// out of bounds or the value is not an array.
+    pub fn get_index(&self, index: usize) -> Option<&JsonValue> {
+        self.as_array().and_then(|items| items.get(index))
+    }
+
+    /// Returns whether the value is an object with the member `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.as_object().is_some_and(|map| map.contains_key(key))
+    }
+
+    /// Returns the number of elements of an array or members of an object, or
+    /// `None` for other values.
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            JsonValue::Array(items) => Some(items.len()),
+            JsonValue::Object(map) => Some(map.len()),
+            _ => None,
+        }
+    }
+
+    /// Returns whether an array or object has no elements or members, or
+    /// `None` for other values.
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Returns the member `key` of an object for modification.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut JsonValue> {
+        match self {
+            JsonValue::Object(map) => map.get_mut(key),
+            _ => None,
+        }
+    }
+
+    /// Sets the member `key` of an object, returning the value it replaced.
+    /// Does nothing to other values.
+    pub fn insert(&mut self, key: impl Into<String>, value: JsonValue) -> Option<JsonValue> {
+        match self {
+            JsonValue::Object(map) => map.insert(key.into(), value),
+            _ => None,
+        }
+    }
+
+    /// Returns the member `key` of an object for in-place insertion or
+    /// modification, like `HashMap::entry`, or `None` for other values.
+    pub fn entry(&mut self, key: impl Into<String>) -> Option<Entry<'_>> {
+        match self {
+            JsonValue::Object(map) => Some(map.entry(key.into())),
+            _ => None,
+        }
+    }
+
+    /// Returns the member `key` of an object for modification, first inserting
+    /// the value `f` returns if the object lacks the member, so that getting
+    /// the same key again returns the value inserted the first time. Returns
+    /// `None` for other values, without calling `f`.
+    pub fn get_or_insert_with(
+        &mut self,
+        key: impl Into<String>,
+        f: impl FnOnce() -> JsonValue,
+    ) -> Option<&mut JsonValue> {
+        match self {
+            JsonValue::Object(map) => Some(map.entry(key.into()).or_insert_with(f)),
+            _ => None,
+        }
+    }
+
+    /// Moves the value out, leaving `Null` in its place, like `Option::take`.
+    pub fn take(&mut self) -> JsonValue {
+        mem::take(self)
+    }
+
+    /// Moves the value out, leaving `value` in its place.
+    pub fn replace(&mut self, value: JsonValue) -> JsonValue {
+        mem::replace(self, value)
+    }
+
+    /// Appends an element to an array. Does nothing to other values.
+    pub fn push(&mut self, value: JsonValue) {
+        if let JsonValue::Array(items) = self {
+            items.push(value);
+        }
+    }
+
+    /// Iterates over the members of an object; other values have none.
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &JsonValue)> {
+        self.as_object().into_iter().flatten()
+    }
+
+    pub fn entries_mut(&mut self) -> impl Iterator<Item = (&String, &mut JsonValue)> {
+        let map = match self {
+            JsonValue::Object(map) => Some(map),
+            _ => None,
+        };
+        map.into_iter().flat_map(|map| map.iter_mut())
+    }
+
+    /// Iterates over the elements of an array; other values have none.
+    pub fn elements(&self) -> impl Iterator<Item = &JsonValue> {
+        self.as_array().into_iter().flatten()
+    }
+
+    pub fn elements_mut(&mut self) -> impl Iterator<Item = &mut JsonValue> {
+        let items = match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        };
+        items.into_iter().flatten()
+    }
+
+    /// Compares two values structurally, with stricter number semantics than
+    /// `==`.
+    ///
+    /// Numbers are compared by their bits, so `NaN` equals `NaN` while `0.0`
+    /// and `-0.0` differ, an `Int` never equals a `Number`, and a `RawNumber`
+    /// only equals one with the same text. Objects are equal when they have
+    /// the same members in any order; arrays when they have equal elements in
+    /// the same order.
+    pub fn deep_eq(&self, other: &JsonValue) -> bool {
+        match (self, other) {
+            (JsonValue::Number(a), JsonValue::Number(b)) => a.to_bits() == b.to_bits(),
+            (JsonValue::Object(a), JsonValue::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(k, v)| b.get(k).is_some_and(|w| v.deep_eq(w)))
+            }
+            (JsonValue::Array(a), JsonValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(v, w)| v.deep_eq(w))
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Returns whether an array has an element equal to `needle` by
+    /// [`deep_eq`](JsonValue::deep_eq); other values have none.
+    pub fn array_contains(&self, needle: &JsonValue) -> bool {
+        self.elements().any(|v| v.deep_eq(needle))
+    }
+
+    /// Returns whether an object has a member whose value equals `needle` by
+    /// [`deep_eq`](JsonValue::deep_eq); other values have none.
+    pub fn object_contains_value(&self, needle: &JsonValue) -> bool {
+        self.entries().any(|(_, v)| v.deep_eq(needle))
+    }
+
+    /// Compares two values by a total order across all values, for sorting.
+    ///
+    /// Values of different kinds are ordered `Null`, booleans, numbers,
+    /// strings, arrays and then objects. Numbers of every variant are ordered
+    /// by value as `f64::total_cmp` orders them, except that `-0.0` equals
+    /// `0.0` as with `==`, with `Int`s compared exactly and ties between
+    /// variants broken in the order `Int`, `Number`, `RawNumber`, `BigInt`.
+    /// Every `NaN`, as parsed with [`ParseOptions::allow_nan`], is made
+    /// positive, so it comes after every other number including `Infinity`,
+    /// whatever its sign, and equals any other `NaN`. Strings are
+    /// ordered by their UTF-8 bytes and arrays element by element, a prefix
+    /// first. Objects are ordered as the lists of their members sorted by
+    /// key, comparing each key and then its value.
+    ///
+    /// This is also the order of [`Ord`] for `JsonValue`.
+    ///
+    /// [`ParseOptions::allow_nan`]: super::ParseOptions::allow_nan
+    pub fn total_cmp(&self, other: &JsonValue) -> Ordering {
+        match (self, other) {
+            (JsonValue::Bool(a), JsonValue::Bool(b)) => a.cmp(b),
+            (JsonValue::Int(a), JsonValue::Int(b)) => a.cmp(b),
+            #[cfg(feature = "bigint")]
+            (JsonValue::BigInt(a), JsonValue::BigInt(b)) => a.cmp(b),
+            (JsonValue::Str(a), JsonValue::Str(b)) => a.cmp(b),
+            (JsonValue::Array(a), JsonValue::Array(b)) => {
+                let mut pairs = a.iter().zip(b);
+                pairs
+                    .find_map(|(v, w)| Some(v.total_cmp(w)).filter(|o| o.is_ne()))
+                    .unwrap_or_else(|| a.len().cmp(&b.len()))
+            }
+            (JsonValue::Object(a), JsonValue::Object(b)) => {
+                let (a, b) = (sorted_members(a), sorted_members(b));
+                let mut pairs = a.iter().zip(&b);
+                pairs
+                    .find_map(|((k, v), (l, w))| {
+                        Some(k.cmp(l).then_with(|| v.total_cmp(w))).filter(|o| o.is_ne())
+                    })
+                    .unwrap_or_else(|| a.len().cmp(&b.len()))
+            }
+            (a, b) => match (a.as_f64(), b.as_f64()) {
+                (Some(x), Some(y)) => canonical(x)
+                    .total_cmp(&canonical(y))
+                    .then_with(|| rank(a).cmp(&rank(b)))
+                    .then_with(|| match (a, b) {
+                        (JsonValue::RawNumber(s), JsonValue::RawNumber(t)) => s.cmp(t),
+                        _ => Ordering::Equal,
+                    }),
+                _ => rank(a).cmp(&rank(b)),
+            },
+        }
+    }
+
+    /// Returns a copy with the members of every object sorted by key as
+    /// [`to_string_canonical`](JsonValue::to_string_canonical) writes them,
+    /// and with the elements of every array sorted by
+    /// [`total_cmp`](JsonValue::total_cmp) too if `sort_arrays` is set, so
+    /// that documents differing only in those orders compare equal.
+    ///
+    /// The order of an object's members is only kept with the default
+    /// `map-index` feature; `==` ignores it either way.
+    pub fn sorted(&self, sort_arrays: bool) -> JsonValue {
+        match self {
+            JsonValue::Object(map) => JsonValue::Object(
+                sorted_members(map)
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), v.sorted(sort_arrays)))
+                    .collect(),
+            ),
+            JsonValue::Array(items) => {
+                let mut items: Vec<_> = items.iter().map(|v| v.sorted(sort_arrays)).collect();
+                if sort_arrays {
+                    items.sort_by(JsonValue::total_cmp);
+                }
+                JsonValue::Array(items)
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+/// The members of `map` sorted by key.
+fn sorted_members(map: &Map) -> Vec<(&String, &JsonValue)> {
+    let mut members: Vec<_> = map.iter().collect();
+    members.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    members
+}
+
+/// `x` as [`JsonValue::total_cmp`] orders it: `-0.0` as `0.0`, and any
+/// `NaN` as the same positive one.
+fn canonical(x: f64) -> f64 {
+    if x.is_nan() {
+        f64::from_bits(0x7ff8_0000_0000_0000)
+    } else {
+        x + 0.
+    }
+}
+
+/// The position of the kind of `value` in the order of
+/// [`JsonValue::total_cmp`], with each number variant ranked separately.
+fn rank(value: &JsonValue) -> u8 {
+    match value {
+        JsonValue::Null => 0,
+        JsonValue::Bool(_) => 1,
+        JsonValue::Int(_) => 2,
+        JsonValue::Number(_) => 3,
+        JsonValue::RawNumber(_) => 4,
+        #[cfg(feature = "bigint")]
+        JsonValue::BigInt(_) => 5,
+        JsonValue::Str(_) => 6,
+        JsonValue::Array(_) => 7,
+        JsonValue::Object(_) => 8,
+    }
+}
+
+/// Iterates over the elements of an array by value; other values have none.
+impl IntoIterator for JsonValue {
+    type Item = JsonValue;
+    type IntoIter = vec::IntoIter<JsonValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_array().unwrap_or_default().into_iter()
+    }
+}
+
+/// Looks up an object member, yielding `Null` when the key is missing or the
+/// value is not an object.
+impl Index<&str> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, key: &str) -> &JsonValue {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+/// Looks up an array element, yielding `Null` when the index is out of bounds
+/// or the value is not an array.
+impl Index<usize> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, index: usize) -> &JsonValue {
+        self.get_index(index).unwrap_or(&NULL)
+    }
+}
+
+/// Makes values usable as keys of maps and members of sets.
+///
+/// As with `f64`, a value containing a `NaN` number is unequal to itself, so
+/// a set can hold it repeatedly and never finds it.
+impl Eq for JsonValue {}
+
+/// Orders values by [`JsonValue::total_cmp`], so that they can be sorted
+/// and kept in a `BTreeSet` whatever their kinds.
+///
+/// The order agrees with `==` but for `NaN`, which `==` finds unequal to
+/// itself and the order finds equal, as with [`Eq`].
+impl Ord for JsonValue {
+    fn cmp(&self, other: &JsonValue) -> Ordering {
+        self.total_cmp(other)
+    }
+}
+
+impl PartialOrd for JsonValue {
+    fn partial_cmp(&self, other: &JsonValue) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Hashes values consistently with both `==` and
+/// [`deep_eq`](JsonValue::deep_eq): numbers by their bit pattern, except that
+/// `-0.0` hashes like `0.0` since the two compare equal, and objects by their
+/// members in key order, whatever order they were inserted in.
+impl Hash for JsonValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            JsonValue::Number(f) => {
+                let f = if *f == 0. { 0. } else { *f };
+                f.to_bits().hash(state);
+            }
+            JsonValue::Int(i) => i.hash(state),
+            #[cfg(feature = "bigint")]
+            JsonValue::BigInt(b) => b.hash(state),
+            JsonValue::RawNumber(s) | JsonValue::Str(s) => s.hash(state),
+            JsonValue::Bool(b) => b.hash(state),
+            JsonValue::Null => {}
+            JsonValue::Object(map) => sorted_members(map).hash(state),
+            JsonValue::Array(items) => items.hash(state),
+        }
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(b: bool) -> Self {
+        JsonValue::Bool(b)
+    }
+}
+
+impl From<i64> for JsonValue {
+    fn from(i: i64) -> Self {
+        JsonValue::Int(i)
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(f: f64) -> Self {
+        JsonValue::Number(f)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl From<num_bigint::BigInt> for JsonValue {
+    fn from(b: num_bigint::BigInt) -> Self {
+        JsonValue::BigInt(b)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(s: &str) -> Self {
+        JsonValue::Str(s.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(s: String) -> Self {
+        JsonValue::Str(s)
+    }
+}
+
+impl From<Vec<JsonValue>> for JsonValue {
+    fn from(items: Vec<JsonValue>) -> Self {
+        JsonValue::Array(items)
+    }
+}
+
+impl From<Map> for JsonValue {
+    fn from(map: Map) -> Self {
+        JsonValue::Object(map)
+    }
+}
+
+/// Builds an object from a `HashMap`, in its iteration order. When [`Map`] is
+/// a `HashMap` with `std`'s hasher, this is the `From<Map>` conversion.
+#[cfg(all(
+    feature = "std",
+    any(
+        feature = "map-btree",
+        feature = "ahash",
+        all(
+            feature = "preserve_order",
+            not(feature = "map-btree"),
+            not(feature = "map-hash")
+        )
+    )
+))]
+impl From<std::collections::HashMap<String, JsonValue>> for JsonValue {
+    fn from(map: std::collections::HashMap<String, JsonValue>) -> Self {
+        JsonValue::Object(map.into_iter().collect())
+    }
+}
+
+/// The default value is `Null`.
+impl Default for JsonValue {
+    fn default() -> Self {
+        JsonValue::Null
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{parse_complete, parse_with_options, ParseOptions};
+    use super::*;
+
+    #[test]
+    fn test_accessors() {
+        let value =
+            parse_complete(r#"{"s": "x", "f": 1.5, "i": 2, "b": true, "n": null, "a": [1]}"#)
+                .unwrap();
+        let object = value.as_object().unwrap();
+        assert_eq!(object["s"].as_str(), Some("x"));
+        assert_eq!(object["f"].as_f64(), Some(1.5));
+        assert_eq!(object["i"].as_f64(), Some(2.));
+        assert_eq!(object["i"].as_i64(), Some(2));
+        assert_eq!(object["f"].as_i64(), None);
+        assert_eq!(object["b"].as_bool(), Some(true));
+        assert!(object["n"].is_null());
+        assert_eq!(object["a"].as_array(), Some(&vec![JsonValue::Int(1)]));
+
+        assert_eq!(object["s"].as_f64(), None);
+        assert_eq!(object["f"].as_str(), None);
+        assert_eq!(object["n"].as_bool(), None);
+        assert_eq!(object["b"].as_array(), None);
+        assert_eq!(object["a"].as_object(), None);
+        assert!(!object["b"].is_null());
+    }
+
+    #[test]
+    fn test_accessors_mut() {
+        let mut value = parse_complete(r#"{"a": [3, 1, 2], "b": 1, "c": null}"#).unwrap();
+        value.get_mut("a").unwrap().as_array_mut().unwrap().sort();
+        assert_eq!(value["a"], parse_complete("[1, 2, 3]").unwrap());
+        value.as_object_mut().unwrap().retain(|_, v| !v.is_null());
+        assert_eq!(
+            value,
+            parse_complete(r#"{"a": [1, 2, 3], "b": 1}"#).unwrap()
+        );
+
+        assert_eq!(value.as_array_mut(), None);
+        assert_eq!(value.get_mut("a").unwrap().as_object_mut(), None);
+        assert_eq!(JsonValue::Null.as_array_mut(), None);
+    }
+
+    #[test]
+    fn test_into() {
+        use alloc::collections::BTreeMap;
+
+        let value = parse_complete(r#"[{"name": "a", "tags": ["x"]}, "s", 1]"#).unwrap();
+        let mut items = value.clone().into_array().unwrap();
+        assert_eq!(items.len(), 3);
+        let mut object: BTreeMap<String, JsonValue> =
+            items.remove(0).into_object().unwrap().into_iter().collect();
+        assert_eq!(object.remove("name"), Some("a".into()));
+        let tags: Vec<JsonValue> = object.remove("tags").unwrap().into_iter().collect();
+        assert_eq!(tags, ["x".into()]);
+        assert!(object.is_empty());
+
+        let owned: Vec<_> = value.into_iter().skip(1).collect();
+        assert_eq!(owned, ["s".into(), JsonValue::Int(1)]);
+        assert_eq!(JsonValue::Int(1).into_iter().count(), 0);
+        assert_eq!(JsonValue::Null.into_array(), None);
+        assert_eq!(JsonValue::Array(Vec::new()).into_object(), None);
+    }
+
+    #[test]
+    fn test_from() {
+        assert_eq!(JsonValue::default(), JsonValue::Null);
+        let v: JsonValue = "hello".into();
+        assert_eq!(v, JsonValue::Str("hello".to_string()));
+        let v: JsonValue = 3.25.into();
+        assert_eq!(v, JsonValue::Number(3.25));
+        assert_eq!(JsonValue::from(-3), JsonValue::Int(-3));
+        assert_eq!(JsonValue::from(true), JsonValue::Bool(true));
+        assert_eq!(
+            JsonValue::from("s".to_string()),
+            JsonValue::Str("s".to_string())
+        );
+        assert_eq!(
+            JsonValue::from(vec![JsonValue::Null]),
+            JsonValue::Array(vec![JsonValue::Null])
+        );
+
+        let mut map = Map::default();
+        map.insert("a".to_string(), JsonValue::Int(1));
+        assert_eq!(JsonValue::from(map.clone()), JsonValue::Object(map.clone()));
+        #[cfg(feature = "std")]
+        {
+            let hash_map: std::collections::HashMap<_, _> = map.clone().into_iter().collect();
+            assert_eq!(JsonValue::from(hash_map), JsonValue::Object(map));
+        }
+    }
+
+    #[cfg(feature = "map-btree")]
+    #[test]
+    fn test_btree_order() {
+        let value = parse_complete(r#"{"b": 1, "c": {"z": 0, "y": 0}, "a": 2}"#).unwrap();
+        let keys: Vec<_> = value.entries().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["a", "b", "c"]);
+        assert_eq!(value.to_string(), r#"{"a":2,"b":1,"c":{"y":0,"z":0}}"#);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash() {
+        use std::collections::{hash_map::DefaultHasher, HashSet};
+
+        fn hash(value: &JsonValue) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = parse_complete(r#"{"x": 1, "y": [true, {"p": null, "q": "s"}]}"#).unwrap();
+        let b = parse_complete(r#"{"y": [true, {"q": "s", "p": null}], "x": 1}"#).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+        assert_eq!(hash(&JsonValue::Number(0.)), hash(&JsonValue::Number(-0.)));
+
+        let mut set = HashSet::new();
+        assert!(set.insert(a));
+        assert!(!set.insert(b));
+        assert!(set.insert(JsonValue::Int(1)));
+        assert!(set.insert(JsonValue::Number(1.)));
+        assert!(set.insert(JsonValue::Number(f64::NAN)));
+        assert!(set.insert(JsonValue::Number(f64::NAN)));
+        assert_eq!(set.len(), 5);
+    }
+
+    #[test]
+    fn test_index() {
+        let value = parse_complete(r#"{"user": {"roles": ["admin", "dev"]}}"#).unwrap();
+        assert_eq!(
+            value["user"]["roles"][0],
+            JsonValue::Str("admin".to_string())
+        );
+        assert_eq!(value["user"]["roles"][1].as_str(), Some("dev"));
+        assert!(value["user"]["roles"][2].is_null());
+        assert!(value["missing"]["roles"][0].is_null());
+        assert!(value["user"]["roles"]["admin"].is_null());
+        assert!(value[0].is_null());
+    }
+
+    #[test]
+    fn test_get() {
+        let value = parse_complete(r#"{"a": [null, {"b": 1}]}"#).unwrap();
+        assert_eq!(
+            value.get("a").and_then(|v| v.get_index(0)),
+            Some(&JsonValue::Null)
+        );
+        assert_eq!(
+            value
+                .get("a")
+                .and_then(|v| v.get_index(1))
+                .and_then(|v| v.get("b")),
+            Some(&JsonValue::Int(1))
+        );
+        assert_eq!(value.get("missing"), None);
+        assert_eq!(value.get("a").and_then(|v| v.get_index(2)), None);
+        assert_eq!(value.get("a").and_then(|v| v.get("b")), None);
+        assert_eq!(value.get_index(0), None);
+        assert_eq!(JsonValue::Null.get("a"), None);
+    }
+
+    #[test]
+    fn test_len() {
+        let array = parse_complete("[1, [2, 3]]").unwrap();
+        assert_eq!((array.len(), array.is_empty()), (Some(2), Some(false)));
+        assert!(!array.contains_key("0"));
+        let object = parse_complete(r#"{"a": 1, "b": null}"#).unwrap();
+        assert_eq!((object.len(), object.is_empty()), (Some(2), Some(false)));
+        assert!(object.contains_key("b"));
+        assert!(!object.contains_key("c"));
+        let empty = parse_complete("{}").unwrap();
+        assert_eq!((empty.len(), empty.is_empty()), (Some(0), Some(true)));
+        let scalar = JsonValue::from("abc");
+        assert_eq!((scalar.len(), scalar.is_empty()), (None, None));
+        assert!(!scalar.contains_key("a"));
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut value = parse_complete(r#"{"a": 1, "b": [2, 3]}"#).unwrap();
+        let mut keys = value.entries().map(|(k, _)| k.as_str()).collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(keys, ["a", "b"]);
+        assert_eq!(value.elements().count(), 0);
+        assert_eq!(value["a"].entries().count(), 0);
+
+        for (_, v) in value.entries_mut() {
+            for item in v.elements_mut() {
+                *item = JsonValue::Null;
+            }
+        }
+        let items = value["b"].elements().collect::<Vec<_>>();
+        assert_eq!(items, [&JsonValue::Null, &JsonValue::Null]);
+    }
+
+    #[test]
+    fn test_deep_eq() {
+        let nan = JsonValue::Number(f64::NAN);
+        assert!(nan.deep_eq(&nan));
+        assert_ne!(nan, nan);
+        assert!(!JsonValue::Number(0.0).deep_eq(&JsonValue::Number(-0.0)));
+        assert_eq!(JsonValue::Number(0.0), JsonValue::Number(-0.0));
+        assert!(!JsonValue::Int(1).deep_eq(&JsonValue::Number(1.0)));
+
+        let a = parse_complete(r#"{"x": [1, 2.5, "s"], "y": {"z": null}}"#).unwrap();
+        let b = parse_complete(r#"{"y": {"z": null}, "x": [1, 2.5, "s"]}"#).unwrap();
+        assert!(a.deep_eq(&b));
+        let c = parse_complete(r#"{"y": {"z": null}, "x": [2.5, 1, "s"]}"#).unwrap();
+        assert!(!a.deep_eq(&c));
+        let d = parse_complete(r#"{"x": [1, 2.5, "s"]}"#).unwrap();
+        assert!(!a.deep_eq(&d));
+        assert!(!d.deep_eq(&a));
+    }
+
+    #[test]
+    fn test_contains() {
+        let roles = parse_complete(r#"["user", "admin", {"team": [1, 2]}]"#).unwrap();
+        assert!(roles.array_contains(&"admin".into()));
+        assert!(roles.array_contains(&parse_complete(r#"{"team": [1, 2]}"#).unwrap()));
+        assert!(!roles.array_contains(&"root".into()));
+        assert!(!roles.object_contains_value(&"admin".into()));
+
+        let owners = parse_complete(r#"{"a": "admin", "b": 1.5}"#).unwrap();
+        assert!(owners.object_contains_value(&"admin".into()));
+        assert!(owners.object_contains_value(&JsonValue::Number(1.5)));
+        assert!(!owners.object_contains_value(&"a".into()));
+        assert!(!owners.object_contains_value(&JsonValue::Int(1)));
+        assert!(!owners.array_contains(&"admin".into()));
+        assert!(!JsonValue::Str("admin".into()).array_contains(&"admin".into()));
+        assert!(!JsonValue::Null.object_contains_value(&JsonValue::Null));
+    }
+
+    #[test]
+    fn test_sorted() {
+        let a = parse_complete(r#"{"b": [3, "x", null, 1.5, true], "a": {"d": [2, 1], "c": 0}}"#);
+        let b = parse_complete(r#"{"a": {"c": 0, "d": [1, 2]}, "b": [true, "x", 1.5, 3, null]}"#);
+        let (a, b) = (a.unwrap(), b.unwrap());
+        assert_ne!(a.sorted(false), b.sorted(false));
+        assert_eq!(a.sorted(true), b.sorted(true));
+        assert_eq!(
+            a.sorted(true)["b"],
+            parse_complete(r#"[null, true, 1.5, 3, "x"]"#).unwrap()
+        );
+        #[cfg(all(
+            feature = "preserve_order",
+            not(feature = "map-btree"),
+            not(feature = "map-hash")
+        ))]
+        assert_eq!(
+            a.sorted(false).to_string(),
+            r#"{"a":{"c":0,"d":[2,1]},"b":[3,"x",null,1.5,true]}"#
+        );
+
+        let ordered = parse_complete(
+            r#"[null, false, true, -1e300, -2, 0, 0.5, 1, 1.0, "", "a", "b", [], [0], [0, 0], [1],
+                {}, {"a": 1}, {"a": 2}, {"b": 0}]"#,
+        )
+        .unwrap();
+        let items = ordered.as_array().unwrap();
+        for (i, v) in items.iter().enumerate() {
+            for (j, w) in items.iter().enumerate() {
+                assert_eq!(v.total_cmp(w), i.cmp(&j), "{} against {}", v, w);
+            }
+        }
+        let nan = JsonValue::Number(f64::NAN);
+        assert_eq!(nan.total_cmp(&nan), Ordering::Equal);
+
+        let negative_nan = JsonValue::Number(-f64::NAN);
+        assert!(negative_nan.as_f64().unwrap().is_sign_negative());
+        assert_eq!(negative_nan.total_cmp(&nan), Ordering::Equal);
+        let mut items = [
+            nan,
+            negative_nan,
+            JsonValue::Number(1.0),
+            JsonValue::Int(-5),
+            JsonValue::Number(f64::NEG_INFINITY),
+        ];
+        items.sort_by(JsonValue::total_cmp);
+        assert_eq!(
+            items[..3],
+            [f64::NEG_INFINITY.into(), (-5).into(), 1.0.into()]
+        );
+        assert!(items[3..].iter().all(|v| v.as_f64().unwrap().is_nan()));
+    }
+
+    #[test]
+    fn test_ord() {
+        let options = ParseOptions {
+            allow_nan: true,
+            ..Default::default()
+        };
+        let ordered = parse_with_options(
+            r#"[null, false, -Infinity, -1, -0.5, 0, 0.0, 1, 1.0, 1.00, Infinity, NaN,
+                "", "0", [], [null], {}]"#,
+            &options,
+        )
+        .unwrap();
+        let mut items = ordered.as_array().unwrap().clone();
+        items.reverse();
+        items.sort();
+        // `deep_eq`, since the NaN is unequal to itself by `==`.
+        assert!(JsonValue::Array(items).deep_eq(&ordered));
+        assert!(JsonValue::Int(5) < JsonValue::Str(String::new()));
+        assert!(JsonValue::Str("b".into()) < JsonValue::Array(Vec::new()));
+        assert!(JsonValue::Bool(true) < JsonValue::Number(f64::NEG_INFINITY));
+
+        // Zeros of either sign are equal, as with `==`; NaN is above every
+        // other number, and equal to itself.
+        assert_eq!(
+            JsonValue::Number(-0.0).cmp(&JsonValue::Number(0.0)),
+            Ordering::Equal
+        );
+        let nan = JsonValue::Number(f64::NAN);
+        assert!(nan > JsonValue::Number(f64::INFINITY));
+        assert!(nan > JsonValue::Int(i64::MAX));
+        assert!(nan < JsonValue::Str(String::new()));
+        assert_eq!(nan.cmp(&nan), Ordering::Equal);
+
+        let set: alloc::collections::BTreeSet<_> =
+            parse_complete(r#"[3, "a", 1, null, "a", [2], 1]"#)
+                .unwrap()
+                .into_iter()
+                .collect();
+        assert_eq!(
+            set.into_iter().collect::<Vec<_>>(),
+            parse_complete(r#"[null, 1, 3, "a", [2]]"#)
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .clone()
+        );
+    }
+
+    #[test]
+    fn test_mutation() {
+        let mut value = parse_complete(r#"{"name": "x", "tags": ["a"]}"#).unwrap();
+        assert_eq!(value.insert("count", JsonValue::Int(1)), None);
+        assert_eq!(
+            value.insert("name", "y".into()),
+            Some(JsonValue::Str("x".to_string()))
+        );
+        value.get_mut("tags").unwrap().push("b".into());
+        *value.get_mut("count").unwrap() = JsonValue::Int(2);
+        assert_eq!(
+            parse_complete(&value.to_string()),
+            parse_complete(r#"{"name": "y", "tags": ["a", "b"], "count": 2}"#)
+        );
+
+        let mut array = parse_complete("[1]").unwrap();
+        assert_eq!(array.get_mut("a"), None);
+        assert_eq!(array.insert("a", JsonValue::Null), None);
+        array.push(JsonValue::Null);
+        assert_eq!(array.to_string(), "[1,null]");
+        let mut scalar = JsonValue::Int(1);
+        scalar.push(JsonValue::Null);
+        assert_eq!(scalar, JsonValue::Int(1));
+    }
+
+    #[test]
+    fn test_take() {
+        let mut doc = parse_complete(r#"{"a": {"b": [1, {"c": true}]}, "d": 2}"#).unwrap();
+        let b = doc.pointer_mut("/a/b").unwrap().take();
+        assert_eq!(b, parse_complete(r#"[1, {"c": true}]"#).unwrap());
+        assert_eq!(
+            parse_complete(&doc.to_string()),
+            parse_complete(r#"{"a": {"b": null}, "d": 2}"#)
+        );
+
+        let old = doc.get_mut("d").unwrap().replace(b);
+        assert_eq!(old, JsonValue::Int(2));
+        assert_eq!(
+            parse_complete(&doc.to_string()),
+            parse_complete(r#"{"a": {"b": null}, "d": [1, {"c": true}]}"#)
+        );
+        assert_eq!(JsonValue::Null.take(), JsonValue::Null);
+    }
+
+    #[test]
+    fn test_entry() {
+        let mut counts = JsonValue::Object(Map::default());
+        for word in "the cat saw the dog and the cat".split(' ') {
+            counts
+                .entry(word)
+                .unwrap()
+                .and_modify(|n| *n = JsonValue::Int(n.as_i64().unwrap() + 1))
+                .or_insert(JsonValue::Int(1));
+        }
+        assert_eq!(
+            counts,
+            parse_complete(r#"{"the": 3, "cat": 2, "saw": 1, "dog": 1, "and": 1}"#).unwrap()
+        );
+        let tags = counts
+            .entry("tags")
+            .unwrap()
+            .or_insert(JsonValue::Array(Vec::new()));
+        tags.push("a".into());
+        assert_eq!(counts["tags"], parse_complete(r#"["a"]"#).unwrap());
+        assert!(JsonValue::Array(Vec::new()).entry("a").is_none());
+    }
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let mut config = parse_complete(r#"{"name": "x"}"#).unwrap();
+        for tag in ["a", "b"] {
+            config
+                .get_or_insert_with("plugins", || JsonValue::Array(Vec::new()))
+                .unwrap()
+                .push(tag.into());
+        }
+        assert_eq!(config["plugins"], parse_complete(r#"["a", "b"]"#).unwrap());
+        let name = config.get_or_insert_with("name", || unreachable!());
+        assert_eq!(name, Some(&mut JsonValue::Str("x".to_string())));
+
+        let mut array = JsonValue::Array(Vec::new());
+        assert_eq!(array.get_or_insert_with("a", || unreachable!()), None);
+        assert_eq!(array, JsonValue::Array(Vec::new()));
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use nom::combinator::all_consuming;
+
+use super::{parse_value, Builder, ParseError, ParseOptions};
+
+/// Receives the parts of a document from [`parse_events`] in the order they
+/// appear in the input. Every callback does nothing by default.
+pub trait Visitor {
+    fn on_null(&mut self) {}
+    fn on_bool(&mut self, _b: bool) {}
+    fn on_number(&mut self, _n: f64) {}
+    /// Called for numbers parsed as [`JsonValue::Int`](super::JsonValue::Int),
+    /// passing them on to [`on_number`](Visitor::on_number) by default.
+    fn on_int(&mut self, i: i64) {
+        self.on_number(i as f64);
+    }
+    fn on_string(&mut self, _s: &str) {}
+    fn on_array_start(&mut self) {}
+    fn on_array_end(&mut self) {}
+    fn on_object_start(&mut self) {}
+    /// Called with the key of each member, before the callbacks for its value.
+    fn on_key(&mut self, _key: &str) {}
+    fn on_object_end(&mut self) {}
+}
+
+/// Drives a [`Visitor`] with the events of the parse.
+struct Events<'v, V>(&'v mut V);
+
+impl<'a, 'v, V: Visitor> Builder<'a> for Events<'v, V> {
+    type Value = ();
+    type Object = ();
+
+    fn null(&mut self) {
+        self.0.on_null();
+    }
+
+    fn bool(&mut self, b: bool) {
+        self.0.on_bool(b);
+    }
+
+    fn int(&mut self, i: i64) {
+        self.0.on_int(i);
+    }
+
+    fn float(&mut self, f: f64) {
+        self.0.on_number(f);
+    }
+
+    fn string(&mut self, s: Cow<'a, str>) {
+        self.0.on_string(&s);
+    }
+
+    fn begin_array(&mut self) {
+        self.0.on_array_start();
+    }
+
+    fn array(&mut self, _: Vec<()>) {
+        self.0.on_array_end();
+    }
+
+    fn begin_object(&mut self) {
+        self.0.on_object_start();
+    }
+
+    fn key(&mut self, key: &str) {
+        self.0.on_key(key);
+    }
+
+    fn new_object(&mut self, _: usize) {}
+
+    fn contains_key(&self, _: &(), _: &str) -> bool {
+        false
+    }
+
+    fn insert(&mut self, _: &mut (), _: Cow<'a, str>, _: ()) {}
+
+    fn object(&mut self, _: ()) {
+        self.0.on_object_end();
+    }
+}
+
+/// Parses `input` as a whole JSON document, passing its parts to `visitor`
+/// instead of building a value.
+///
+/// Parts are passed on as they are parsed, so a malformed document may have
+/// been partly visited when the error is returned.
+pub fn parse_events<V: Visitor>(input: &str, visitor: &mut V) -> Result<(), ParseError> {
+    let mut events = Events(visitor);
+    let result = all_consuming(|i| parse_value(i, &ParseOptions::default(), 0, &mut events))(input);
+    result.map(drop).map_err(|e| ParseError::new(input, e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct Stats {
+        strings: usize,
+        sum: f64,
+        events: Vec<String>,
+    }
+
+    impl Visitor for Stats {
+        fn on_number(&mut self, n: f64) {
+            self.sum += n;
+        }
+
+        fn on_string(&mut self, s: &str) {
+            self.strings += 1;
+            self.events.push(format!("{:?}", s));
+        }
+
+        fn on_array_start(&mut self) {
+            self.events.push("[".to_string());
+        }
+
+        fn on_array_end(&mut self) {
+            self.events.push("]".to_string());
+        }
+
+        fn on_object_start(&mut self) {
+            self.events.push("{".to_string());
+        }
+
+        fn on_key(&mut self, key: &str) {
+            self.events.push(format!("{}:", key));
+        }
+
+        fn on_object_end(&mut self) {
+            self.events.push("}".to_string());
+        }
+    }
+
+    #[test]
+    fn test_visitor() {
+        let mut stats = Stats::default();
+        let input = r#"{"a": ["x", 1, {"b": "y\n", "c": 2.5}], "d": [[], "z", null]}"#;
+        assert_eq!(parse_events(input, &mut stats), Ok(()));
+        assert_eq!(stats.strings, 3);
+        assert_eq!(stats.sum, 3.5);
+        assert_eq!(
+            stats.events.join(" "),
+            r#"{ a: [ "x" { b: "y\n" c: } ] d: [ [ ] "z" ] }"#
+        );
+
+        let error = parse_events("[1, x]", &mut Stats::default()).unwrap_err();
+        assert_eq!(error.offset, 4);
+    }
+}
@@ -0,0 +1,91 @@
+use core::convert::TryFrom;
+
+use super::{ConversionError, JsonValue, Map};
+
+/// Numbers that fit an `i64` become `Int` and all others `Number`, and a
+/// tagged value becomes the value it tags. Mapping keys must be strings.
+impl TryFrom<serde_yaml::Value> for JsonValue {
+    type Error = ConversionError;
+
+    fn try_from(value: serde_yaml::Value) -> Result<Self, ConversionError> {
+        Ok(match value {
+            serde_yaml::Value::Null => JsonValue::Null,
+            serde_yaml::Value::Bool(b) => JsonValue::Bool(b),
+            serde_yaml::Value::Number(n) => match n.as_i64() {
+                Some(i) => JsonValue::Int(i),
+                None => JsonValue::Number(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_yaml::Value::String(s) => JsonValue::Str(s),
+            serde_yaml::Value::Sequence(items) => JsonValue::Array(
+                items
+                    .into_iter()
+                    .map(JsonValue::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            serde_yaml::Value::Mapping(mapping) => {
+                let mut map = Map::default();
+                for (k, v) in mapping {
+                    match k {
+                        serde_yaml::Value::String(k) => map.insert(k, JsonValue::try_from(v)?),
+                        _ => return Err(ConversionError::UnexpectedType("a string key")),
+                    };
+                }
+                JsonValue::Object(map)
+            }
+            serde_yaml::Value::Tagged(tagged) => JsonValue::try_from(tagged.value)?,
+        })
+    }
+}
+
+/// Every value has a YAML form; numbers are converted like
+/// [`JsonValue::as_i64`] when they are integers, and like
+/// [`JsonValue::as_f64`] otherwise.
+impl From<JsonValue> for serde_yaml::Value {
+    fn from(value: JsonValue) -> Self {
+        match value {
+            JsonValue::Null => serde_yaml::Value::Null,
+            JsonValue::Bool(b) => serde_yaml::Value::Bool(b),
+            JsonValue::Str(s) => serde_yaml::Value::String(s),
+            JsonValue::Array(items) => serde_yaml::Value::Sequence(
+                items.into_iter().map(serde_yaml::Value::from).collect(),
+            ),
+            JsonValue::Object(map) => serde_yaml::Value::Mapping(
+                map.into_iter()
+                    .map(|(k, v)| (serde_yaml::Value::String(k), v.into()))
+                    .collect(),
+            ),
+            number => serde_yaml::Value::Number(match (number.as_i64(), number.as_f64()) {
+                (Some(i), _) => i.into(),
+                (None, f) => f.unwrap_or(f64::NAN).into(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::parse_complete;
+    use super::*;
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let value = parse_complete(
+            r#"{"name": "x", "port": 8080, "ratio": 0.5, "none": null, "on": false,
+                "list": [1, -2, 2.5, "3", [], {}], "nested": {"a": {"b": [null]}}}"#,
+        )
+        .unwrap();
+        let converted = serde_yaml::Value::from(value.clone());
+        assert_eq!(converted["port"], serde_yaml::Value::from(8080));
+        let text = serde_yaml::to_string(&converted).unwrap();
+        let back = serde_yaml::from_str::<serde_yaml::Value>(&text).unwrap();
+        assert_eq!(JsonValue::try_from(back), Ok(value));
+
+        let tagged: serde_yaml::Value = serde_yaml::from_str("a: !point [1, 2]").unwrap();
+        assert_eq!(
+            JsonValue::try_from(tagged),
+            Ok(parse_complete(r#"{"a": [1, 2]}"#).unwrap())
+        );
+        let error = JsonValue::try_from(serde_yaml::from_str::<serde_yaml::Value>("1: x").unwrap());
+        assert_eq!(error, Err(ConversionError::UnexpectedType("a string key")));
+    }
+}
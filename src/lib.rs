@@ -0,0 +1,10 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod json;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec;
+}
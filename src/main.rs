@@ -1,16 +1,226 @@
-pub mod json;
+use std::{
+    env, fs,
+    io::{self, BufWriter, Read, Write},
+    ops::Deref,
+    process,
+    time::Instant,
+};
 
-use std::{env, fs, process, time::Instant};
+use parser::json;
+
+// Exit codes of the CLI.
+const EXIT_USAGE: i32 = 1;
+const EXIT_IO: i32 = 2;
+const EXIT_PARSE: i32 = 3;
+
+/// How the parsed document is printed.
+enum Output {
+    /// Rust debug formatting.
+    Debug,
+    /// Compact JSON.
+    Compact,
+    /// JSON indented by two spaces.
+    Pretty,
+    /// Nothing: the document is only checked to be valid.
+    Check,
+}
+
+/// The bytes of the document.
+enum Input {
+    Read(Vec<u8>),
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Deref for Input {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Input::Read(content) => content,
+            #[cfg(feature = "mmap")]
+            Input::Mapped(map) => map,
+        }
+    }
+}
+
+/// Reads the document at `path`, or stdin when it is `None` or `-`.
+fn read_input(path: Option<&str>) -> io::Result<Input> {
+    match path {
+        None | Some("-") => {
+            let mut content = Vec::new();
+            io::stdin().read_to_end(&mut content)?;
+            Ok(Input::Read(content))
+        }
+        Some(path) => read_file(path),
+    }
+}
+
+#[cfg(not(feature = "mmap"))]
+fn read_file(path: &str) -> io::Result<Input> {
+    fs::read(path).map(Input::Read)
+}
+
+/// Maps the file at `path` into memory, unless it is empty and so cannot be
+/// mapped on every platform.
+#[cfg(feature = "mmap")]
+fn read_file(path: &str) -> io::Result<Input> {
+    let file = fs::File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(Input::Read(Vec::new()));
+    }
+    // SAFETY: the mapping is only read while parsing, and the file is not
+    // expected to be modified by another process in the meantime.
+    unsafe { memmap2::Mmap::map(&file) }.map(Input::Mapped)
+}
+
+/// Writes `value` to stdout as JSON, indented by `indent` spaces per level if
+/// given, followed by a newline.
+fn print_json(value: &json::JsonValue, indent: Option<usize>) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    match indent {
+        Some(indent) => value.write_to_pretty(&mut out, indent)?,
+        None => value.write_to(&mut out)?,
+    }
+    writeln!(out)?;
+    out.flush()
+}
+
+/// Parses `content` like `parse_lenient`, also counting what it holds.
+fn parse_counting(
+    content: &[u8],
+    options: &json::ParseOptions,
+) -> Result<(json::JsonValue, json::ParseStats), json::ParseError> {
+    match std::str::from_utf8(content) {
+        Ok(input) => json::parse_with_stats_options(input, options),
+        // Let `parse_bytes` locate the invalid byte.
+        Err(_) => Err(json::parse_bytes(content).unwrap_err()),
+    }
+}
+
+/// Parses `content` like `json::parse_bytes`, accepting what `options` do.
+fn parse_lenient(
+    content: &[u8],
+    options: &json::ParseOptions,
+) -> Result<json::JsonValue, json::ParseError> {
+    match std::str::from_utf8(content) {
+        Ok(input) => {
+            json::parse_with_options(input, options).map_err(|e| json::ParseError::new(input, e))
+        }
+        Err(_) => Err(json::parse_bytes(content).unwrap_err()),
+    }
+}
+
+/// Reports on stderr how long parsing took and, if they were counted, what
+/// the document holds, the latter as a JSON object for scripts.
+fn report(duration: u128, stats: Option<&json::ParseStats>) {
+    match stats {
+        Some(stats) => eprintln!(
+            "{}",
+            parser::json!({
+                "parse_micros": duration as i64,
+                "nodes": stats.values as i64,
+                "max_depth": stats.max_depth as i64,
+            })
+        ),
+        None => eprintln!("{}μs", duration),
+    }
+}
 
 fn main() {
     let args = env::args().collect::<Vec<_>>();
-    if args.len() != 2 {
-        process::exit(1);
+    let usage = || -> ! {
+        eprintln!(
+            "usage: {} [--compact | --pretty | --check [--verbose]] [--stats] \
+             [--allow-comments] [--allow-trailing-commas] [--allow-nan] [--json5] \
+             [--max-depth N] [FILE]",
+            args[0]
+        );
+        process::exit(EXIT_USAGE);
+    };
+    let mut output = Output::Debug;
+    let mut count = false;
+    let mut verbose = false;
+    let mut path = None;
+    let mut options = json::ParseOptions::default();
+    let mut lenient = false;
+    let mut flags = args[1..].iter();
+    while let Some(arg) = flags.next() {
+        match arg.as_str() {
+            "--compact" => output = Output::Compact,
+            "--pretty" => output = Output::Pretty,
+            "--check" | "--validate" => output = Output::Check,
+            "--verbose" => verbose = true,
+            "--stats" => count = true,
+            "--allow-comments" => options.allow_comments = true,
+            "--allow-trailing-commas" => options.allow_trailing_comma = true,
+            "--allow-nan" => options.allow_nan = true,
+            // Every JSON5 option only accepts more, so the bundle keeps the
+            // other flags given, except for the depth it leaves alone.
+            "--json5" => {
+                options = json::ParseOptions {
+                    max_depth: options.max_depth,
+                    ..json::ParseOptions::json5()
+                }
+            }
+            "--max-depth" => match flags.next().and_then(|n| n.parse().ok()) {
+                Some(depth) => options.max_depth = depth,
+                None => usage(),
+            },
+            flag if flag.starts_with("--") => usage(),
+            _ if path.is_some() => usage(),
+            arg => path = Some(arg),
+        }
+        lenient |= arg.starts_with("--allow-") || arg == "--json5" || arg == "--max-depth";
     }
-    let path = &args[1];
-    let content = String::from_utf8(fs::read(path).unwrap()).unwrap();
+    let content = match read_input(path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("error reading {}: {}", path.unwrap_or("stdin"), e);
+            process::exit(EXIT_IO);
+        }
+    };
     let now = Instant::now();
-    let (_, result) = json::parse(&content).unwrap();
+    let result = match (count, lenient) {
+        (true, _) => parse_counting(&content, &options).map(|(value, stats)| (value, Some(stats))),
+        (false, true) => parse_lenient(&content, &options).map(|value| (value, None)),
+        (false, false) => json::parse_bytes(&content).map(|value| (value, None)),
+    };
     let duration = now.elapsed().as_micros();
-    println!("{:#?}\n{}μs", result, duration);
+    drop(content);
+    let (result, stats) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(EXIT_PARSE);
+        }
+    };
+    // Only the document goes to stdout, so that it can be redirected; the
+    // parse time is reported on stderr.
+    let stats = stats.as_ref();
+    let indent = match output {
+        Output::Debug => {
+            println!("{:#?}", result);
+            report(duration, stats);
+            return;
+        }
+        // Checking is silent on success, as CI scripts want, unless asked
+        // for a confirmation or the stats.
+        Output::Check => {
+            if verbose {
+                println!("{}: valid JSON", path.unwrap_or("stdin"));
+            }
+            if let Some(stats) = stats {
+                report(duration, Some(stats));
+            }
+            return;
+        }
+        Output::Compact => None,
+        Output::Pretty => Some(2),
+    };
+    if let Err(e) = print_json(&result, indent) {
+        eprintln!("error writing stdout: {}", e);
+        process::exit(EXIT_IO);
+    }
+    report(duration, stats);
 }
@@ -0,0 +1,147 @@
+//! Counts the allocations the parsers make, to keep optimizations that
+//! avoid them from regressing.
+#![cfg(feature = "std")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use parser::json::{parse_document, parse_interned, validate_structure};
+
+/// The system allocator, counting allocations.
+struct Counting;
+
+thread_local! {
+    /// The allocations made on this thread, so that tests running at the
+    /// same time do not count each other's.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // The count is gone once the thread is being torn down.
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Returns the number of allocations `f` makes.
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn test_validate_structure_allocations() {
+    let mut input = String::from("[");
+    for i in 0..10_000 {
+        input.push_str(&format!("{{\"id\": {}, \"tags\": [\"a\", \"b\"]}},", i));
+    }
+    input.push_str("null]");
+
+    // Only the stack of open brackets is allocated, growing to depth 3.
+    let validating = allocations(|| validate_structure(&input).unwrap());
+    assert!(validating <= 3, "{} allocations", validating);
+    let parsing = allocations(|| {
+        parse_document(&input).unwrap();
+    });
+    assert!(parsing > 10_000, "{} allocations", parsing);
+}
+
+#[test]
+fn test_number_array_allocations() {
+    let numbers: Vec<_> = (0..1000)
+        .map(|i| match i % 2 {
+            0 => i.to_string(),
+            _ => format!("{}.5", i),
+        })
+        .collect();
+    let input = format!("[{}]", numbers.join(", "));
+
+    // Numbers need no allocation of their own: only the elements parsed so
+    // far are, growing by doubling, before they are moved into the array.
+    let parsing = allocations(|| {
+        parse_document(&input).unwrap();
+    });
+    assert!(parsing <= 12, "{} allocations", parsing);
+}
+
+#[test]
+fn test_large_array_allocations() {
+    let numbers: Vec<_> = (0..100_000).map(|i| i.to_string()).collect();
+    let input = format!("[{}]", numbers.join(", "));
+
+    // The array is built with exactly the capacity it needs once closed, so
+    // its size only adds the doublings of the scratch stack, not one
+    // reallocation per doubling of the array as well.
+    let parsing = allocations(|| {
+        parse_document(&input).unwrap();
+    });
+    assert!(parsing <= 20, "{} allocations", parsing);
+}
+
+#[test]
+fn test_small_object_allocations() {
+    let objects: Vec<_> = (0..1000)
+        .map(|i| format!(r#"{{"id": {}, "ok": true, "tag": null}}"#, i))
+        .collect();
+    let input = format!("[{}]", objects.join(", "));
+
+    // Each object allocates its keys and its map, at most two allocations
+    // with `map-index`, and nothing else: its members are collected on
+    // scratch stacks shared by the whole document, and scalars, keywords
+    // included, need no allocation.
+    let parsing = allocations(|| {
+        parse_document(&input).unwrap();
+    });
+    assert!(parsing <= 1000 * (3 + 2) + 16, "{} allocations", parsing);
+}
+
+#[cfg(feature = "arena")]
+#[test]
+fn test_parse_in_allocations() {
+    let objects: Vec<_> = (0..1000)
+        .map(|i| format!(r#"{{"id": {}, "tags": ["a\nb", "c"], "n\u00e9": null}}"#, i))
+        .collect();
+    let input = format!("[{}]", objects.join(", "));
+
+    // Strings, elements and members all go straight into the arena, so only
+    // the scratch stacks shared by the whole document, growing by doubling,
+    // are allocated on the heap.
+    let arena = bumpalo::Bump::with_capacity(1 << 20);
+    let parsing = allocations(|| {
+        parser::json::parse_in(&input, &arena).unwrap();
+    });
+    assert!(parsing <= 24, "{} allocations", parsing);
+}
+
+#[test]
+fn test_interned_allocations() {
+    let rows: Vec<_> = (0..10_000)
+        .map(|i| {
+            let status = ["active", "inactive", "pending"][i % 3];
+            let region = ["eu", "us"][i % 2];
+            format!(r#"{{"status": "{}", "region": "{}"}}"#, status, region)
+        })
+        .collect();
+    let input = format!("[{}]", rows.join(", "));
+
+    // Each row allocates its map, at most two allocations with
+    // `map-index`, and its two keys and two values as well unless they
+    // are interned, which allocates the seven distinct strings only once.
+    let parsing = allocations(|| {
+        parse_document(&input).unwrap();
+    });
+    assert!(parsing >= 10_000 * 4, "{} allocations", parsing);
+    let interning = allocations(|| {
+        parse_interned(&input).unwrap();
+    });
+    assert!(interning <= 10_000 * 2 + 64, "{} allocations", interning);
+}
@@ -0,0 +1,104 @@
+//! Runs the `parser` binary on the files in `tests/fixtures`.
+#![cfg(feature = "std")]
+
+use std::process::{Command, Output};
+
+use parser::json::parse;
+
+/// Runs the binary with `args`, from the fixtures directory.
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_parser"))
+        .args(args)
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_check() {
+    let output = run(&["--check", "valid.json"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty() && output.stderr.is_empty());
+
+    let output = run(&["--validate", "--verbose", "valid.json"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "valid.json: valid JSON\n"
+    );
+
+    let output = run(&["--check", "invalid.json"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("parse error at line 1, column 39"),
+        "{}",
+        stderr
+    );
+
+    let output = run(&["--check", "missing.json"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("error reading missing.json: "));
+
+    assert_eq!(run(&["--check", "--bogus"]).status.code(), Some(1));
+}
+
+#[test]
+fn test_parse_options() {
+    let output = run(&["--compact", "--allow-comments", "lenient.json"]);
+    assert_eq!(output.status.code(), Some(3));
+    let output = run(&[
+        "--compact",
+        "--allow-comments",
+        "--allow-trailing-commas",
+        "lenient.json",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    // Without `map-index` the members may be written in any order.
+    assert_eq!(
+        parse(&String::from_utf8(output.stdout).unwrap()),
+        parse(r#"{"name": "lenient", "values": [1, 2.5]}"#)
+    );
+
+    assert_eq!(run(&["--check", "json5.json"]).status.code(), Some(3));
+    assert_eq!(
+        run(&["--check", "--allow-nan", "json5.json"]).status.code(),
+        Some(3)
+    );
+    let output = run(&["--check", "--max-depth", "3", "--json5", "json5.json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let output = run(&["--check", "--json5", "--max-depth", "1", "json5.json"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("maximum nesting depth exceeded"));
+
+    for args in [
+        &["--max-depth", "valid.json"][..],
+        &["--max-depth"],
+        &["--allow-everything", "valid.json"],
+    ] {
+        assert_eq!(run(args).status.code(), Some(1), "{:?}", args);
+    }
+
+    let output = run(&["--check", "--stats", "--json5", "json5.json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        parse(stderr.trim()).unwrap().get("nodes").is_some(),
+        "{}",
+        stderr
+    );
+    let output = run(&[
+        "--check",
+        "--stats",
+        "--max-depth",
+        "1",
+        "--json5",
+        "json5.json",
+    ]);
+    assert_eq!(output.status.code(), Some(3));
+}
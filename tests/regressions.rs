@@ -0,0 +1,181 @@
+//! Replays inputs that once crashed or confused a parser, each as the
+//! smallest reproducer found, through every public way of parsing.
+//!
+//! An input passes if each parser either returns a value or fails with a
+//! clean error located within it. To add a reproducer, append it to
+//! `CORPUS` with a note on what it exercises.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use parser::json::{
+    parse, parse_borrowed, parse_bytes, parse_int_keyed, parse_interned, parse_lines, parse_many,
+    parse_recovering, parse_rows, parse_streaming, parse_subtree, parse_with_diagnostics,
+    parse_with_options, parse_with_spans, resync, tokenize, validate_structure, ErrorKind,
+    ParseError, ParseOptions, StreamParser,
+};
+
+/// The reproducers, by what they exercise.
+const CORPUS: &[(&str, &[u8])] = &[
+    ("empty", b""),
+    ("whitespace only", b" \t\r\n"),
+    ("unterminated string", b"\"abc"),
+    ("unterminated key", b"{\"a"),
+    ("unterminated string in array", b"[\"abc"),
+    ("trailing backslash", b"\"\\"),
+    ("escape at end of key", b"{\"\\"),
+    ("short unicode escape", b"\"\\u12\""),
+    ("unicode escape at end", b"\"\\u"),
+    ("non-hex unicode escape", b"\"\\u00G0\""),
+    ("unknown escape", b"\"\\x41\""),
+    ("lone high surrogate", b"\"\\ud800\""),
+    ("lone low surrogate", b"\"\\udc00\""),
+    ("reversed surrogates", b"\"\\udc00\\ud800\""),
+    ("high surrogate before short escape", b"\"\\ud83d\\u12\""),
+    ("surrogate pair", b"\"\\ud83d\\ude00\""),
+    ("escaped NUL", b"\"\\u0000\""),
+    ("raw control character", b"\"a\x01b\""),
+    ("raw NUL between tokens", b"[1,\x002]"),
+    ("byte order mark", b"\xef\xbb\xbf[1]"),
+    ("huge exponent", b"1e400"),
+    ("huge negative exponent", b"-1e400"),
+    ("tiny exponent", b"1e-400"),
+    ("exponent overflowing an integer", b"1e99999999999999999999"),
+    ("integer above u64", b"123456789012345678901234567890"),
+    ("integer at i64::MIN", b"-9223372036854775808"),
+    ("leading zero", b"012"),
+    ("bare minus", b"-"),
+    ("bare exponent", b"1e"),
+    ("bare fraction", b"1."),
+    ("hex number", b"0x1F"),
+    ("keyword cut short", b"tru"),
+    ("keyword run on", b"nulll"),
+    ("missing value after colon", b"{\"a\":}"),
+    ("missing colon", b"{\"a\" 1}"),
+    ("non-string key", b"{1: 2}"),
+    ("double comma", b"[1,,2]"),
+    ("trailing comma", b"[1,]"),
+    ("mismatched brackets", b"[1}"),
+    ("extra closing bracket", b"[1]]"),
+    ("closing bracket only", b"}"),
+    ("trailing content after nested array", b"{\"a\":[1,2]x}"),
+    ("open comment", b"[1 /*"),
+    ("invalid UTF-8 start byte", b"\xff"),
+    ("invalid UTF-8 in string", b"[\"\xc3\"]"),
+    ("truncated UTF-8 sequence", b"\"\xf0\x9f\x98"),
+    ("overlong UTF-8 encoding", b"\"\xc0\xaf\""),
+    ("UTF-8 encoded surrogate", b"\"\xed\xa0\x80\""),
+];
+
+/// Panics naming `name` unless `error`, from parsing `input`, is located
+/// within it and displays.
+fn check_error(name: &str, input: &str, error: &ParseError) {
+    assert!(
+        error.offset <= input.len() && input.is_char_boundary(error.offset),
+        "{}: error offset {} outside the input",
+        name,
+        error.offset
+    );
+    let before = &input[..error.offset];
+    let line = before.matches('\n').count() + 1;
+    assert_eq!(error.line, line, "{}: error line", name);
+    assert!(error.column >= 1, "{}: error column", name);
+    assert!(!error.to_string().is_empty(), "{}: error display", name);
+}
+
+/// Runs `input` through each parser, checking the errors it yields.
+fn replay(name: &str, input: &str) {
+    let check = |result: Result<(), ParseError>| {
+        if let Err(error) = result {
+            check_error(name, input, &error);
+        }
+    };
+    check(parse(input).map(drop));
+    check(parse_streaming(input).map(drop));
+    check(validate_structure(input));
+    check(parse_with_spans(input).map(drop));
+    check(parse_interned(input).map(drop));
+    check(parse_int_keyed(input).map(drop));
+    check(parse_subtree(input, "/0").map(drop));
+    check(parse_rows::<(i64,)>(input).map(drop));
+    for options in [ParseOptions::default(), ParseOptions::json5()] {
+        check(parse_with_diagnostics(input, &options).map(drop));
+        if let Err(error) = parse_with_options(input, &options) {
+            check(Err(ParseError::new(input, error)));
+        }
+    }
+    let mut stream = StreamParser::new();
+    for (i, c) in input.char_indices() {
+        stream.feed(&input[i..i + c.len_utf8()]);
+    }
+    assert_eq!(stream.finish(), parse(input), "{}: fed in chunks", name);
+    let _ = parse_borrowed(input);
+    parse_lines(input).for_each(|result| check(result.map(drop)));
+    parse_many(input).for_each(|result| check(result.map(drop)));
+    tokenize(input).for_each(|result| check(result.map(drop)));
+    for error in parse_recovering(input).1 {
+        check_error(name, input, &error);
+    }
+    if let Some(offset) = resync(input) {
+        assert!(offset <= input.len(), "{}: resync offset", name);
+    }
+}
+
+#[test]
+fn test_corpus() {
+    for &(name, bytes) in CORPUS {
+        let outcome = catch_unwind(AssertUnwindSafe(|| {
+            match parse_bytes(bytes) {
+                Ok(_) => {}
+                Err(error) if error.kind == ErrorKind::InvalidUtf8 => {
+                    let valid = std::str::from_utf8(bytes).unwrap_err().valid_up_to();
+                    assert_eq!(error.offset, valid, "{}: invalid UTF-8 offset", name);
+                }
+                Err(error) => check_error(name, std::str::from_utf8(bytes).unwrap(), &error),
+            }
+            if let Ok(input) = std::str::from_utf8(bytes) {
+                replay(name, input);
+            }
+        }));
+        assert!(outcome.is_ok(), "{}: panicked on {:?}", name, bytes);
+    }
+}
+
+#[test]
+fn test_deep_nesting() {
+    let depth = 100_000;
+    for (name, input) in [
+        ("deep arrays", "[".repeat(depth)),
+        ("deep objects", r#"{"a":"#.repeat(depth)),
+        ("deep mixed", r#"[{"a":"#.repeat(depth / 2)),
+        ("deep balanced", "[".repeat(depth) + &"]".repeat(depth)),
+    ] {
+        let error = parse(&input).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::DepthLimitExceeded, "{}", name);
+        let structure = validate_structure(&input).unwrap_err();
+        assert_eq!(structure.offset, error.offset, "{}", name);
+        replay(name, &input);
+    }
+    let limit = ParseOptions::default().max_depth;
+    assert!(parse(&("[".repeat(limit) + &"]".repeat(limit))).is_ok());
+    let wide = "[".to_string() + &"[],".repeat(depth) + "[]]";
+    assert!(parse(&wide).is_ok());
+}
+
+#[test]
+fn test_invalid_utf8() {
+    for (bytes, offset) in [
+        (&b"\xff"[..], 0),
+        (b"[\"\xc3\"]", 2),
+        (b"\"\xf0\x9f\x98", 1),
+        (b"{\"a\": \"\xc0\xaf\"}", 7),
+        (b"[\"\xed\xa0\x80\"]", 2),
+    ] {
+        let error = parse_bytes(bytes).unwrap_err();
+        assert_eq!(
+            (error.offset, error.kind),
+            (offset, ErrorKind::InvalidUtf8),
+            "{:?}",
+            bytes
+        );
+    }
+}